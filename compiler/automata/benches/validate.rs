@@ -0,0 +1,81 @@
+use automata::Automaton;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 200;
+
+fn measure<F>(mut validate: F, sequences: &Vec<String>) -> (Duration, usize)
+where
+    F: FnMut(&str) -> bool,
+{
+    let mut accepted = 0;
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for sequence in sequences {
+            if validate(sequence) {
+                accepted += 1;
+            }
+        }
+    }
+
+    (start.elapsed(), accepted)
+}
+
+fn main() {
+    // a mix of accepted and rejected sequences, similar to the lexemes of a program
+    let inputs = [
+        (
+            "identifier",
+            vec![
+                "counter",
+                "isPrime",
+                "x",
+                "value2",
+                "averylongidentifiername",
+            ],
+        ),
+        (
+            "number",
+            vec!["0", "12345", "-42", "+7", "007", "98765432101234"],
+        ),
+        ("char", vec!["'a'", r"'\''", "'ab'", "' '"]),
+        (
+            "string",
+            vec![
+                r#""Hello, world!""#,
+                r#""a \"quoted\" word""#,
+                r#""unterminated"#,
+            ],
+        ),
+    ];
+
+    for (file, values) in inputs {
+        let automaton = match Automaton::new(&format!("input/{}.dfa", file)) {
+            Ok(automaton) => automaton,
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        };
+        let compiled = automaton.compile();
+
+        // repeat the values so that each round does a meaningful amount of work
+        let sequences: Vec<String> = values
+            .iter()
+            .cycle()
+            .take(values.len() * 1000)
+            .map(|value| String::from(*value))
+            .collect();
+
+        let (hashed_time, hashed_accepted) = measure(|s| automaton.validate(s), &sequences);
+        let (compiled_time, compiled_accepted) = measure(|s| compiled.validate(s), &sequences);
+        assert_eq!(hashed_accepted, compiled_accepted);
+
+        println!(
+            "{:>10}: hashed {:>10.2?}, compiled {:>10.2?} ({:.1}x)",
+            file,
+            hashed_time,
+            compiled_time,
+            hashed_time.as_secs_f64() / compiled_time.as_secs_f64()
+        );
+    }
+}
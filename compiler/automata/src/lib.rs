@@ -1,3 +1,13 @@
 mod models;
 
 pub use models::automaton::Automaton;
+pub use models::builder::AutomatonBuilder;
+pub use models::codegen::{generate_rust_module, write_rust_module};
+pub use models::compiled::{CompiledDfa, CompiledMoore};
+pub use models::error::{AutomatonError, SourceLocation};
+pub use models::fuzz::{fuzz, FuzzReport};
+pub use models::learning::{learn_lstar, learn_rpni, Samples, Teacher};
+pub use models::pda::{Acceptance, Pda, PdaTransition, EMPTY};
+pub use models::random::{random_automaton, Random, RandomConfig};
+pub use models::serialize::JSON_VERSION;
+pub use models::transducer::{Mealy, Moore};
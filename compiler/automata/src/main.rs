@@ -3,13 +3,28 @@ mod tests;
 mod utils;
 mod view;
 
+use std::env;
+use std::process::ExitCode;
 use view::menu::Menu;
 
-fn main() {
+fn main() -> ExitCode {
+    // run a single command if any arguments were given
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 {
+        return match view::cli::run(&args[1..]) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("ERROR: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
     // create a new menu and show it
     let main_menu = Menu::new();
     main_menu.show();
 
     // this will be printed after the user closes the menu
     println!("Done!");
+    ExitCode::SUCCESS
 }
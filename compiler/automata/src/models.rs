@@ -1,2 +1,15 @@
+mod analysis;
 pub mod automaton;
+pub mod builder;
+pub mod codegen;
+pub mod compiled;
+pub mod error;
+pub mod fuzz;
+pub mod learning;
+pub mod minimize;
+pub mod pda;
+pub mod random;
+pub mod regex;
+pub mod serialize;
 pub mod state;
+pub mod transducer;
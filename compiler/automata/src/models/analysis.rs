@@ -0,0 +1,323 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use super::automaton::Automaton;
+use super::state::StateLabel;
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // returns the states that can be reached from the initial state
+    pub fn reachable_states(&self) -> Set<S> {
+        let mut reachable = Set::from([self.get_initial_state().clone()]);
+        let mut queue = VecDeque::from([self.get_initial_state().clone()]);
+
+        while let Some(state) = queue.pop_front() {
+            for symbol in self.get_alphabet() {
+                if let Some(next_state) = self.get_transitions().get(&(state.clone(), *symbol)) {
+                    if reachable.insert(next_state.clone()) {
+                        queue.push_back(next_state.clone());
+                    }
+                }
+            }
+        }
+
+        reachable
+    }
+
+    // returns the states from which a final state can be reached
+    pub fn coreachable_states(&self) -> Set<S> {
+        let mut coreachable = self.get_final_states().clone();
+
+        // keep walking the transitions backwards until nothing changes
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for ((start_state, _), end_state) in self.get_transitions() {
+                if coreachable.contains(end_state) && coreachable.insert(start_state.clone()) {
+                    changed = true;
+                }
+            }
+        }
+
+        coreachable
+    }
+
+    // returns the states that cannot be reached from the initial state
+    pub fn unreachable_states(&self) -> Set<S> {
+        let reachable = self.reachable_states();
+        self.get_states()
+            .iter()
+            .filter(|state| !reachable.contains(state))
+            .cloned()
+            .collect()
+    }
+
+    // returns the states from which no final state can be reached
+    pub fn dead_states(&self) -> Set<S> {
+        let coreachable = self.coreachable_states();
+        self.get_states()
+            .iter()
+            .filter(|state| !coreachable.contains(state))
+            .cloned()
+            .collect()
+    }
+
+    // returns a warning for every unreachable and every dead state
+    pub fn warnings(&self) -> Vec<String> {
+        let mut unreachable: Vec<S> = self.unreachable_states().into_iter().collect();
+        unreachable.sort();
+
+        let mut dead: Vec<S> = self.dead_states().into_iter().collect();
+        dead.sort();
+
+        let mut warnings = vec![];
+        for state in unreachable {
+            warnings.push(format!(
+                "state '{}' is unreachable from the initial state",
+                state
+            ));
+        }
+        for state in dead {
+            warnings.push(format!("state '{}' cannot reach a final state", state));
+        }
+
+        warnings
+    }
+
+    // returns true if the automaton accepts no sequence at all
+    pub fn is_empty(&self) -> bool {
+        let reachable = self.reachable_states();
+        !self
+            .get_final_states()
+            .iter()
+            .any(|state| reachable.contains(state))
+    }
+
+    // returns true if the automaton accepts a finite number of sequences,
+    // i.e. there is no cycle through states that are both reachable and coreachable
+    pub fn is_finite(&self) -> bool {
+        let useful = self.useful_states();
+
+        // Kahn's algorithm on the subgraph of useful states
+        let mut in_degree: HashMap<S, usize> = HashMap::new();
+        for state in &useful {
+            in_degree.insert(state.clone(), 0);
+        }
+        for ((start_state, _), end_state) in self.get_transitions() {
+            if useful.contains(start_state) && useful.contains(end_state) {
+                let degree = in_degree.get(end_state).cloned().unwrap_or(0);
+                in_degree.insert(end_state.clone(), degree + 1);
+            }
+        }
+
+        let mut queue: VecDeque<S> = useful
+            .iter()
+            .filter(|state| in_degree.get(state) == Some(&0))
+            .cloned()
+            .collect();
+        let mut visited = 0;
+
+        while let Some(state) = queue.pop_front() {
+            visited += 1;
+            for symbol in self.get_alphabet() {
+                let next_state = match self.get_transitions().get(&(state.clone(), *symbol)) {
+                    Some(next_state) if useful.contains(next_state) => next_state.clone(),
+                    _ => continue,
+                };
+
+                let degree = in_degree.get(&next_state).cloned().unwrap_or(0) - 1;
+                in_degree.insert(next_state.clone(), degree);
+                if degree == 0 {
+                    queue.push_back(next_state);
+                }
+            }
+        }
+
+        // every useful state was sorted topologically, so there is no cycle
+        visited == useful.len()
+    }
+
+    // returns the number of accepted sequences of length at most n,
+    // saturating at u128::MAX
+    pub fn count_accepted(&self, n: usize) -> u128 {
+        // paths[state] = number of sequences of the current length that end in state
+        let mut paths: HashMap<S, u128> = HashMap::new();
+        paths.insert(self.get_initial_state().clone(), 1);
+
+        let mut total: u128 = 0;
+        for length in 0..=n {
+            for (state, count) in &paths {
+                if self.get_final_states().contains(state) {
+                    total = total.saturating_add(*count);
+                }
+            }
+
+            if length == n {
+                break;
+            }
+
+            let mut next_paths: HashMap<S, u128> = HashMap::new();
+            for ((start_state, _), end_state) in self.get_transitions() {
+                let count = match paths.get(start_state) {
+                    Some(count) => *count,
+                    None => continue,
+                };
+
+                let existing = next_paths.get(end_state).cloned().unwrap_or(0);
+                next_paths.insert(end_state.clone(), existing.saturating_add(count));
+            }
+            paths = next_paths;
+        }
+
+        total
+    }
+
+    // returns the first k accepted sequences in shortlex order
+    // (shorter sequences first, equal lengths in lexicographic order)
+    pub fn enumerate(&self, k: usize) -> Vec<String> {
+        let mut result = vec![];
+        if k == 0 || self.is_empty() {
+            return result;
+        }
+
+        let alphabet = self.sorted_alphabet();
+        let finite = self.is_finite();
+
+        // can_finish[r] = states from which a final state is reached in exactly r steps
+        let mut can_finish: Vec<Set<S>> = vec![self.get_final_states().clone()];
+
+        let mut length = 0;
+        loop {
+            // a finite language has no sequences longer than the number of states
+            if finite && length >= self.get_states().len() {
+                break;
+            }
+
+            while can_finish.len() <= length {
+                let previous = &can_finish[can_finish.len() - 1];
+                let mut current = Set::new();
+                for ((start_state, _), end_state) in self.get_transitions() {
+                    if previous.contains(end_state) {
+                        current.insert(start_state.clone());
+                    }
+                }
+                can_finish.push(current);
+            }
+
+            // depth first search in lexicographic order, pruned by can_finish
+            let mut stack = vec![(self.get_initial_state().clone(), String::new())];
+            while let Some((state, word)) = stack.pop() {
+                let remaining = length - word.chars().count();
+                if !can_finish[remaining].contains(&state) {
+                    continue;
+                }
+
+                if remaining == 0 {
+                    result.push(word);
+                    if result.len() >= k {
+                        break;
+                    }
+                    continue;
+                }
+
+                // push in reverse so that the smallest symbol is explored first
+                for symbol in alphabet.iter().rev() {
+                    if let Some(next_state) = self.get_transitions().get(&(state.clone(), *symbol))
+                    {
+                        let mut next_word = word.clone();
+                        next_word.push(*symbol);
+                        stack.push((next_state.clone(), next_word));
+                    }
+                }
+            }
+
+            if result.len() >= k {
+                break;
+            }
+            length += 1;
+        }
+
+        result
+    }
+
+    // returns the shortlex smallest accepted sequence, if any
+    pub fn shortest_accepted(&self) -> Option<String> {
+        let alphabet = self.sorted_alphabet();
+
+        let mut visited = Set::from([self.get_initial_state().clone()]);
+        let mut queue = VecDeque::from([(self.get_initial_state().clone(), String::new())]);
+
+        // breadth first search visits every state through its shortlex smallest sequence
+        while let Some((state, word)) = queue.pop_front() {
+            if self.get_final_states().contains(&state) {
+                return Some(word);
+            }
+
+            for symbol in &alphabet {
+                if let Some(next_state) = self.get_transitions().get(&(state.clone(), *symbol)) {
+                    if visited.insert(next_state.clone()) {
+                        let mut next_word = word.clone();
+                        next_word.push(*symbol);
+                        queue.push_back((next_state.clone(), next_word));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    // returns the shortlex smallest rejected sequence over the alphabet, if any
+    pub fn shortest_rejected(&self) -> Option<String> {
+        let alphabet = self.sorted_alphabet();
+
+        if !self.get_final_states().contains(self.get_initial_state()) {
+            return Some(String::new());
+        }
+
+        let mut visited = Set::from([self.get_initial_state().clone()]);
+        let mut queue = VecDeque::from([(self.get_initial_state().clone(), String::new())]);
+
+        // sequences are discovered in shortlex order, so the first rejected one is the smallest
+        while let Some((state, word)) = queue.pop_front() {
+            for symbol in &alphabet {
+                let mut next_word = word.clone();
+                next_word.push(*symbol);
+
+                match self.get_transitions().get(&(state.clone(), *symbol)) {
+                    Some(next_state) => {
+                        if !self.get_final_states().contains(next_state) {
+                            return Some(next_word);
+                        }
+
+                        if visited.insert(next_state.clone()) {
+                            queue.push_back((next_state.clone(), next_word));
+                        }
+                    }
+                    // a missing transition rejects the sequence right away
+                    None => return Some(next_word),
+                }
+            }
+        }
+
+        None
+    }
+
+    fn useful_states(&self) -> Set<S> {
+        let coreachable = self.coreachable_states();
+        self.reachable_states()
+            .into_iter()
+            .filter(|state| coreachable.contains(state))
+            .collect()
+    }
+
+    fn sorted_alphabet(&self) -> Vec<char> {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+
+        alphabet
+    }
+}
@@ -2,163 +2,337 @@ use hash_map::HashMap;
 use lazy_static::lazy_static;
 use std::collections::HashSet as Set;
 
-use std::fs::File;
-use std::io::{BufReader, Lines};
-use utils::{extract_line_data, get_next_line, open_file, InputLine};
+use std::io::{BufRead, BufReader, Lines, Read};
+use utils::{get_next_line, open_file};
 
-use super::state::{NewState, State};
+use super::error::{AutomatonError, SourceLocation, UNNAMED_SOURCE};
+use super::state::{State, StateLabel};
 
 lazy_static! {
     static ref ESCAPES: HashMap<&'static str, char> = {
         let mut map = HashMap::new();
         map.insert(r"\s", ' ');
+        map.insert(r"\t", '\t');
+        map.insert(r"\n", '\n');
+        map.insert(r"\r", '\r');
         map
     };
 }
 
-pub struct Automaton {
+// the number of header lines (alphabet, states, initial state, final states)
+pub const HEADER_LINES: usize = 4;
+
+pub struct Automaton<S = State> {
     alphabet: Set<char>,
-    states: Set<State>,
-    initial_state: State,
-    final_states: Set<State>,
-    transitions: HashMap<(State, char), State>,
+    states: Set<S>,
+    initial_state: S,
+    final_states: Set<S>,
+    transitions: HashMap<(S, char), S>,
+
+    // dense renumbering of the states, used when running the automaton
+    indices: HashMap<S, usize>,
+    dense_transitions: HashMap<(usize, char), usize>,
+    dense_final_states: Vec<bool>,
+}
+
+// the lines of a .dfa file, used to point errors at their location
+pub(super) struct Source<'a> {
+    pub(super) path: &'a str,
+    pub(super) lines: &'a Vec<String>,
+}
+
+impl<'a> Source<'a> {
+    pub(super) fn line(&self, index: usize) -> Option<&'a String> {
+        self.lines.get(index)
+    }
+
+    // the location of a 1-based column on the line with the given index
+    pub(super) fn at(&self, index: usize, column: usize) -> SourceLocation {
+        let line = match self.lines.get(index) {
+            Some(line) => line.as_str(),
+            None => "",
+        };
 
-    used_states: Set<State>,
+        SourceLocation::new(self.path, index + 1, column, line)
+    }
 }
 
+// numbered states, the labels most .dfa files use
+// (the binary itself only loads named states, the library exposes both)
+#[allow(dead_code)]
 impl Automaton {
-    pub fn new(file_path: &str) -> Result<Self, String> {
-        let mut automaton = Self {
+    pub fn new(file_path: &str) -> Result<Self, AutomatonError> {
+        Self::open(file_path)
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, AutomatonError> {
+        Self::parse(reader)
+    }
+}
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // reads the automaton from a .dfa file, with states of any label type
+    pub fn open(file_path: &str) -> Result<Self, AutomatonError> {
+        let mut lines = match open_file(file_path) {
+            Ok(lines) => lines,
+            Err(e) => {
+                let error = AutomatonError::Io {
+                    path: String::from(file_path),
+                    message: e,
+                };
+                return Err(error);
+            }
+        };
+
+        Self::from_lines(file_path, &mut lines)
+    }
+
+    // reads the automaton from any source in the .dfa format
+    pub fn parse<R: Read>(reader: R) -> Result<Self, AutomatonError> {
+        let mut lines = BufReader::new(reader).lines();
+        Self::from_lines(UNNAMED_SOURCE, &mut lines)
+    }
+
+    // builds an automaton directly from its components
+    pub fn from_parts(
+        alphabet: Set<char>,
+        states: Set<S>,
+        initial_state: S,
+        final_states: Set<S>,
+        transitions: HashMap<(S, char), S>,
+    ) -> Result<Self, AutomatonError> {
+        // check if the components are consistent with each other
+        if !states.contains(&initial_state) {
+            let message = format!("initial state '{}' not in set of states", initial_state);
+            return Err(AutomatonError::InconsistentParts { message });
+        }
+        for final_state in &final_states {
+            if !states.contains(final_state) {
+                let message = format!("final state '{}' not in set of states", final_state);
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+        }
+        for ((start_state, symbol), end_state) in &transitions {
+            if !states.contains(start_state) || !states.contains(end_state) {
+                let message = format!(
+                    "transition '{} {} {}' uses a state that is not in the set of states",
+                    start_state, symbol, end_state
+                );
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+            if !alphabet.contains(symbol) {
+                let message = format!("character '{}' missing from alphabet", symbol);
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+        }
+
+        let mut automaton = Self::empty();
+        automaton.alphabet = alphabet;
+        automaton.states = states;
+        automaton.initial_state = initial_state;
+        automaton.final_states = final_states;
+        automaton.transitions = transitions;
+
+        automaton.renumber();
+        Ok(automaton)
+    }
+
+    // renames every state, the function must give different states different labels
+    pub fn map_states<T, F>(&self, label: F) -> Automaton<T>
+    where
+        T: StateLabel,
+        F: Fn(&S) -> T,
+    {
+        let mut transitions = HashMap::new();
+        for ((start_state, symbol), end_state) in &self.transitions {
+            transitions.insert((label(start_state), *symbol), label(end_state));
+        }
+
+        let mut automaton = Automaton::<T>::empty();
+        automaton.alphabet = self.alphabet.clone();
+        automaton.states = self.states.iter().map(&label).collect();
+        automaton.initial_state = label(&self.initial_state);
+        automaton.final_states = self.final_states.iter().map(&label).collect();
+        automaton.transitions = transitions;
+
+        automaton.renumber();
+        automaton
+    }
+
+    fn empty() -> Self {
+        Self {
             alphabet: Set::new(),
             states: Set::new(),
-            initial_state: State::new(),
+            initial_state: S::new(),
             final_states: Set::new(),
             transitions: HashMap::new(),
 
-            used_states: Set::new(),
+            indices: HashMap::new(),
+            dense_transitions: HashMap::new(),
+            dense_final_states: vec![],
+        }
+    }
+
+    fn from_lines<B: BufRead>(path: &str, lines: &mut Lines<B>) -> Result<Self, AutomatonError> {
+        // read every line first, so that errors can quote them
+        let source_lines = match read_lines(path, lines) {
+            Ok(source_lines) => source_lines,
+            Err(e) => return Err(e),
+        };
+
+        let source = Source {
+            path,
+            lines: &source_lines,
         };
 
-        match automaton.parse_file(file_path) {
-            Ok(_) => Ok(automaton),
-            Err(e) => Err(e),
+        let mut automaton = Self::empty();
+        match automaton.parse_source(&source) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
         }
+
+        automaton.renumber();
+        Ok(automaton)
     }
 
     pub fn get_alphabet(&self) -> &Set<char> {
         &self.alphabet
     }
 
-    pub fn get_states(&self) -> &Set<State> {
+    pub fn get_states(&self) -> &Set<S> {
         &self.states
     }
 
-    pub fn get_initial_state(&self) -> &State {
+    pub fn get_initial_state(&self) -> &S {
         &self.initial_state
     }
 
-    pub fn get_final_states(&self) -> &Set<State> {
+    pub fn get_final_states(&self) -> &Set<S> {
         &self.final_states
     }
 
-    pub fn get_transitions(&self) -> &HashMap<(State, char), State> {
+    pub fn get_transitions(&self) -> &HashMap<(S, char), S> {
         &self.transitions
     }
 
+    // returns the state reached from the given state with the given symbol, if any
+    pub fn step(&self, state: &S, symbol: char) -> Option<&S> {
+        self.transitions.get(&(state.clone(), symbol))
+    }
+
     pub fn validate(&self, sequence: &str) -> bool {
-        let mut current_state = self.initial_state;
+        let mut current_state = match self.indices.get(&self.initial_state) {
+            Some(&index) => index,
+            None => return false,
+        };
+
         for symbol in sequence.chars() {
-            if let Some(&next_state) = self.transitions.get(&(current_state, symbol)) {
+            if let Some(&next_state) = self.dense_transitions.get(&(current_state, symbol)) {
                 current_state = next_state;
             } else {
                 return false;
             }
         }
 
-        self.final_states.contains(&current_state)
+        self.dense_final_states[current_state]
     }
 
-    fn parse_file(&mut self, file_path: &str) -> Result<(), String> {
-        let mut lines = match open_file(file_path) {
-            Ok(lines) => lines,
-            Err(e) => return Err(e),
-        };
+    // assigns every state a dense index, in sorted order, and rebuilds
+    // the transition table on top of those indices
+    fn renumber(&mut self) {
+        let mut states: Vec<&S> = self.states.iter().collect();
+        states.sort();
+
+        self.indices = HashMap::new();
+        self.dense_final_states = vec![];
+        for (index, state) in states.into_iter().enumerate() {
+            self.indices.insert(state.clone(), index);
+            self.dense_final_states
+                .push(self.final_states.contains(state));
+        }
+
+        self.dense_transitions = HashMap::new();
+        for ((start_state, symbol), end_state) in &self.transitions {
+            let start_index = self.indices.get(start_state).cloned();
+            let end_index = self.indices.get(end_state).cloned();
+
+            if let (Some(start_index), Some(end_index)) = (start_index, end_index) {
+                self.dense_transitions
+                    .insert((start_index, *symbol), end_index);
+            }
+        }
+    }
 
+    fn parse_source(&mut self, source: &Source) -> Result<(), AutomatonError> {
         // parse alphabet
-        match self.parse_alphabet(lines.next()) {
+        match self.parse_alphabet(source, 0) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
 
         // parse states
-        match self.parse_states(lines.next()) {
+        match self.parse_states(source, 1) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
 
         // parse initial state
-        match self.parse_initial_state(lines.next()) {
+        match self.parse_initial_state(source, 2) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
 
         // parse final states
-        match self.parse_final_states(lines.next()) {
+        match self.parse_final_states(source, 3) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
 
         // parse transitions
-        match self.parse_transitions(&mut lines) {
+        match self.parse_transitions(source, HEADER_LINES) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
 
-        // check if the automaton is consistent
-        // i.e. all states in the set of states are used in transitions
-        match self.consistency_check() {
-            Ok(_) => (),
-            Err(e) => return Err(e),
-        }
+        // unreachable and dead states are reported through `warnings`
+        // instead of rejecting the file
 
         Ok(())
     }
 
-    fn parse_alphabet(&mut self, alphabet: InputLine) -> Result<(), String> {
+    fn parse_alphabet(&mut self, source: &Source, index: usize) -> Result<(), AutomatonError> {
         // check if alphabet is missing
-        let alphabet = match extract_line_data(alphabet) {
+        let alphabet = match source.line(index) {
             Some(alphabet) => alphabet,
             None => {
-                let error = format!("invalid finite automaton file: missing alphabet");
-                return Err(error);
+                let location = source.at(index, 1);
+                return Err(AutomatonError::MissingAlphabet { location });
             }
         };
 
         // add each symbol to the alphabet
-        for symbol in alphabet.split_whitespace() {
-            let value = if let Some(&escape) = ESCAPES.get(&symbol) {
-                escape
-            } else {
-                let mut chars = symbol.chars();
-                let symbol = match chars.next() {
-                    Some(symbol) => symbol,
-                    None => {
-                        let error = format!("empty symbol in alphabet");
-                        return Err(error);
-                    }
-                };
-
-                if chars.next().is_some() {
-                    let error = format!("invalid symbol '{}' in alphabet", symbol);
+        for (column, symbol) in tokens(alphabet) {
+            let value = match parse_symbol(symbol) {
+                Ok(value) => value,
+                Err(_) => {
+                    let error = AutomatonError::InvalidSymbol {
+                        location: source.at(index, column),
+                        token: String::from(symbol),
+                    };
                     return Err(error);
                 }
-
-                symbol
             };
 
             match self.alphabet.insert(value) {
                 true => (),
                 false => {
-                    let error = format!("duplicate symbol '{}' in alphabet", symbol);
+                    let error = AutomatonError::DuplicateSymbol {
+                        location: source.at(index, column),
+                        token: String::from(symbol),
+                    };
                     return Err(error);
                 }
             }
@@ -167,32 +341,32 @@ impl Automaton {
         Ok(())
     }
 
-    fn parse_states(&mut self, states: InputLine) -> Result<(), String> {
+    fn parse_states(&mut self, source: &Source, index: usize) -> Result<(), AutomatonError> {
         // check if set of states is missing
-        let states = match extract_line_data(states) {
+        let states = match source.line(index) {
             Some(states) => states,
             None => {
-                let error = format!("invalid finite automaton file: missing set of states");
-                return Err(error);
+                let location = source.at(index, 1);
+                return Err(AutomatonError::MissingStates { location });
             }
         };
 
         // add each state to the set of states
-        for state in states.split_whitespace() {
+        for (column, token) in tokens(states) {
             // parse state
-            let state = match state.parse::<State>() {
+            let state = match parse_state::<S>(source, index, column, token) {
                 Ok(state) => state,
-                Err(e) => {
-                    let error = format!("invalid state '{}': {}", state, e.to_string());
-                    return Err(error);
-                }
+                Err(e) => return Err(e),
             };
 
             // insert state if it doesn't already exist
             match self.states.insert(state) {
                 true => (),
                 false => {
-                    let error = format!("duplicate state '{}' in set of states", state);
+                    let error = AutomatonError::DuplicateState {
+                        location: source.at(index, column),
+                        token: String::from(token),
+                    };
                     return Err(error);
                 }
             }
@@ -201,73 +375,82 @@ impl Automaton {
         Ok(())
     }
 
-    fn parse_initial_state(&mut self, initial_state: InputLine) -> Result<(), String> {
+    fn parse_initial_state(&mut self, source: &Source, index: usize) -> Result<(), AutomatonError> {
         // check if initial state is missing
-        let initial_state = match extract_line_data(initial_state) {
-            Some(initial_state) => initial_state,
-            None => {
-                let error = format!("invalid finite automaton file: missing initial state");
+        let initial_state = match source.line(index) {
+            Some(initial_state) => tokens(initial_state),
+            None => vec![],
+        };
+
+        let (column, token) = match initial_state.as_slice() {
+            [initial_state] => *initial_state,
+            [] => {
+                let location = source.at(index, 1);
+                return Err(AutomatonError::MissingInitialState { location });
+            }
+            [_, (column, token), ..] => {
+                let error = AutomatonError::InvalidState {
+                    location: source.at(index, *column),
+                    token: String::from(*token),
+                    reason: String::from("expected a single initial state"),
+                };
                 return Err(error);
             }
         };
 
         // parse initial state
-        self.initial_state = match initial_state.parse::<State>() {
+        self.initial_state = match parse_state::<S>(source, index, column, token) {
             Ok(initial_state) => initial_state,
-            Err(e) => {
-                let error = format!(
-                    "invalid initial state '{}': {}",
-                    initial_state,
-                    e.to_string()
-                );
-                return Err(error);
-            }
+            Err(e) => return Err(e),
         };
 
         // check if initial state is in set of states
         if !self.states.contains(&self.initial_state) {
-            let error = format!("initial state '{}' not in set of states", initial_state);
+            let error = AutomatonError::UndefinedState {
+                location: source.at(index, column),
+                token: String::from(token),
+            };
             return Err(error);
         }
 
-        self.used_states.insert(self.initial_state);
         Ok(())
     }
 
-    fn parse_final_states(&mut self, final_states: InputLine) -> Result<(), String> {
+    fn parse_final_states(&mut self, source: &Source, index: usize) -> Result<(), AutomatonError> {
         // check if final states are missing
-        let final_states = match extract_line_data(final_states) {
+        let final_states = match source.line(index) {
             Some(final_states) => final_states,
             None => {
-                let error = format!("invalid finite automaton file: missing final states");
-                return Err(error);
+                let location = source.at(index, 1);
+                return Err(AutomatonError::MissingFinalStates { location });
             }
         };
 
         // add each final state to the set of final states
-        for final_state in final_states.split_whitespace() {
+        for (column, token) in tokens(final_states) {
             // parse final state
-            let final_state = match final_state.parse::<State>() {
+            let final_state = match parse_state::<S>(source, index, column, token) {
                 Ok(final_state) => final_state,
-                Err(e) => {
-                    let error = format!("invalid final state '{}': {}", final_state, e.to_string());
-                    return Err(error);
-                }
+                Err(e) => return Err(e),
             };
 
             // check if final state is in set of states
             if !self.states.contains(&final_state) {
-                let error = format!("final state '{}' not in set of states", final_state);
+                let error = AutomatonError::UndefinedState {
+                    location: source.at(index, column),
+                    token: String::from(token),
+                };
                 return Err(error);
             }
 
             // insert final state if it doesn't already exist
             match self.final_states.insert(final_state) {
-                true => {
-                    self.used_states.insert(final_state);
-                }
+                true => (),
                 false => {
-                    let error = format!("duplicate final state '{}'", final_state);
+                    let error = AutomatonError::DuplicateFinalState {
+                        location: source.at(index, column),
+                        token: String::from(token),
+                    };
                     return Err(error);
                 }
             }
@@ -276,119 +459,217 @@ impl Automaton {
         Ok(())
     }
 
-    fn parse_transitions(
-        &mut self,
-        transitions: &mut Lines<BufReader<File>>,
-    ) -> Result<(), String> {
-        loop {
-            // get the next line from the reader
-            let line = match get_next_line(transitions) {
-                Ok(line) => line,
-                Err(e) => return Err(e),
-            };
-
-            // check if the end of the file has been reached
-            let line = match line {
-                Some(line) => line,
-                None => break,
-            };
+    fn parse_transitions(&mut self, source: &Source, start: usize) -> Result<(), AutomatonError> {
+        for index in start..source.lines.len() {
+            let line = &source.lines[index];
 
             // skip empty lines
-            if line.is_empty() {
+            if line.trim().is_empty() {
                 continue;
             }
 
-            let mut parts: Vec<String> = line.split_whitespace().map(String::from).collect();
             // check if transition is invalid
+            let parts = tokens(line);
             if parts.len() != 3 {
-                let error = format!("invalid transition: {}", line);
+                let error = AutomatonError::MalformedTransition {
+                    location: source.at(index, parts[0].0),
+                    token: String::from(line.trim()),
+                };
                 return Err(error);
             }
 
             // parse start state
-            let start_state = match parts[0].parse::<State>() {
+            let (column, token) = parts[0];
+            let start_state = match parse_state::<S>(source, index, column, token) {
                 Ok(start_state) => start_state,
-                Err(e) => {
-                    let error = format!("invalid start state '{}': {}", parts[0], e.to_string());
-                    return Err(error);
-                }
+                Err(e) => return Err(e),
             };
             // check if start state is in set of states
             if !self.states.contains(&start_state) {
-                let error = format!("start state '{}' not in set of states", start_state);
+                let error = AutomatonError::UndefinedState {
+                    location: source.at(index, column),
+                    token: String::from(token),
+                };
                 return Err(error);
             }
 
-            if let Some(&escape) = ESCAPES.get(&parts[1].as_str()) {
-                parts[1] = String::from(escape);
-            }
-
             // parse symbol
-            if parts[1].len() != 1 {
-                let error = format!("invalid symbol '{}' for transition '{}'", parts[1], line);
-                return Err(error);
-            }
-            // check if symbol is empty
-            let symbol = match parts[1].chars().next() {
-                Some(symbol) => symbol,
-                None => {
-                    let error = format!("missing symbol for transition '{}'", line);
+            let (column, token) = parts[1];
+            let symbol = match parse_symbol(token) {
+                Ok(symbol) => symbol,
+                Err(_) => {
+                    let error = AutomatonError::InvalidSymbol {
+                        location: source.at(index, column),
+                        token: String::from(token),
+                    };
                     return Err(error);
                 }
             };
             // check if symbol is in alphabet
             if !self.alphabet.contains(&symbol) {
-                let error = format!(
-                    "character '{}' missing from alphabet for transition '{}'",
-                    symbol, line
-                );
+                let error = AutomatonError::SymbolNotInAlphabet {
+                    location: source.at(index, column),
+                    token: String::from(token),
+                };
                 return Err(error);
             }
 
             // parse end state
-            let end_state = match parts[2].parse::<State>() {
+            let (column, token) = parts[2];
+            let end_state = match parse_state::<S>(source, index, column, token) {
                 Ok(end_state) => end_state,
-                Err(e) => {
-                    let error = format!("invalid end state '{}': {}", parts[2], e.to_string());
-                    return Err(error);
-                }
+                Err(e) => return Err(e),
             };
             // check if end state is in set of states
             if !self.states.contains(&end_state) {
-                let error = format!("end state '{}' not in set of states", end_state);
+                let error = AutomatonError::UndefinedState {
+                    location: source.at(index, column),
+                    token: String::from(token),
+                };
                 return Err(error);
             }
 
             // check if transition for (start_state, symbol) already exists
-            if self.transitions.get(&(start_state, symbol)).is_some() {
-                let error = format!("duplicate transition key for transition '{}'", line);
+            if self
+                .transitions
+                .get(&(start_state.clone(), symbol))
+                .is_some()
+            {
+                let error = AutomatonError::DuplicateTransition {
+                    location: source.at(index, parts[0].0),
+                    token: String::from(line.trim()),
+                };
                 return Err(error);
             }
 
             // insert transition
             self.transitions.insert((start_state, symbol), end_state);
-            self.used_states.insert(start_state);
-            self.used_states.insert(end_state);
         }
 
         // check if there are no transitions
         if self.transitions.len() == 0 {
-            let error = format!("invalid finite automaton file: missing transitions");
-            return Err(error);
+            let location = source.at(source.lines.len(), 1);
+            return Err(AutomatonError::MissingTransitions { location });
         }
 
         Ok(())
     }
+}
+
+// parses a single symbol of the alphabet, applying escapes
+pub fn parse_symbol(symbol: &str) -> Result<char, String> {
+    if let Some(&escape) = ESCAPES.get(&symbol) {
+        return Ok(escape);
+    }
+
+    // any other character can be written as its code point, i.e. \u{a0}
+    if let Some(code) = symbol
+        .strip_prefix(r"\u{")
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        return match u32::from_str_radix(code, 16).ok().and_then(char::from_u32) {
+            Some(value) => Ok(value),
+            None => {
+                let error = format!("invalid code point in symbol '{}'", symbol);
+                Err(error)
+            }
+        };
+    }
 
-    fn consistency_check(&self) -> Result<(), String> {
-        // check if all states in the set of states are used in transitions
-        for state in &self.states {
-            if !self.used_states.contains(state) {
-                let error = format!("unused state '{}' in the set of states", state);
+    let mut chars = symbol.chars();
+    let value = match chars.next() {
+        Some(value) => value,
+        None => {
+            let error = String::from("empty symbol");
+            return Err(error);
+        }
+    };
+
+    if chars.next().is_some() {
+        let error = format!("invalid symbol '{}'", symbol);
+        return Err(error);
+    }
+
+    Ok(value)
+}
+
+// writes a symbol the way `parse_symbol` reads it
+pub fn format_symbol(symbol: char) -> String {
+    match symbol {
+        ' ' => String::from(r"\s"),
+        '\t' => String::from(r"\t"),
+        '\n' => String::from(r"\n"),
+        '\r' => String::from(r"\r"),
+        // the symbols are separated by whitespace, so it cannot appear as is
+        _ if symbol.is_whitespace() => format!(r"\u{{{:x}}}", symbol as u32),
+        _ => String::from(symbol),
+    }
+}
+
+// splits a line on whitespace, keeping the 1-based column of every token
+pub fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut result = vec![];
+    let mut start: Option<(usize, usize)> = None;
+
+    for (column, (offset, character)) in line.char_indices().enumerate() {
+        match (character.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, offset)),
+            (true, Some((start_column, start_offset))) => {
+                result.push((start_column, &line[start_offset..offset]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    if let Some((start_column, start_offset)) = start {
+        result.push((start_column, &line[start_offset..]));
+    }
+
+    result
+}
+
+// reads every line of a source, mapping read errors to `AutomatonError::Io`
+pub(super) fn read_lines<B: BufRead>(
+    path: &str,
+    lines: &mut Lines<B>,
+) -> Result<Vec<String>, AutomatonError> {
+    let mut source_lines = vec![];
+    loop {
+        match get_next_line(lines) {
+            Ok(Some(line)) => source_lines.push(line),
+            Ok(None) => break,
+            Err(e) => {
+                let error = AutomatonError::Io {
+                    path: String::from(path),
+                    message: e,
+                };
                 return Err(error);
             }
         }
+    }
 
-        Ok(())
+    Ok(source_lines)
+}
+
+pub(super) fn parse_state<S>(
+    source: &Source,
+    index: usize,
+    column: usize,
+    token: &str,
+) -> Result<S, AutomatonError>
+where
+    S: StateLabel,
+{
+    match token.parse::<S>() {
+        Ok(state) => Ok(state),
+        Err(e) => {
+            let error = AutomatonError::InvalidState {
+                location: source.at(index, column),
+                token: String::from(token),
+                reason: e.to_string(),
+            };
+            Err(error)
+        }
     }
 }
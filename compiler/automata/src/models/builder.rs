@@ -0,0 +1,500 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::fs::File;
+use std::io::Write;
+
+use super::automaton::{parse_symbol, Automaton};
+use super::error::AutomatonError;
+use super::state::{State, StateLabel};
+
+// a single change to the draft; every change is undone by applying its inverse
+enum Edit<S> {
+    InsertSymbol(char),
+    DeleteSymbol(char),
+    InsertState(S),
+    DeleteState(S),
+    SetInitialState(Option<S>),
+    InsertFinalState(S),
+    DeleteFinalState(S),
+    InsertTransition((S, char), S),
+    DeleteTransition((S, char)),
+}
+
+// an automaton under construction, which may be incomplete or inconsistent
+// until it is built; every command can be undone
+pub struct AutomatonBuilder<S = State> {
+    alphabet: Set<char>,
+    states: Set<S>,
+    initial_state: Option<S>,
+    final_states: Set<S>,
+    transitions: HashMap<(S, char), S>,
+
+    // the inverse edits of every command, the last command at the end
+    history: Vec<Vec<Edit<S>>>,
+}
+
+impl<S> Default for AutomatonBuilder<S>
+where
+    S: StateLabel,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> AutomatonBuilder<S>
+where
+    S: StateLabel,
+{
+    pub fn new() -> Self {
+        Self {
+            alphabet: Set::new(),
+            states: Set::new(),
+            initial_state: None,
+            final_states: Set::new(),
+            transitions: HashMap::new(),
+
+            history: vec![],
+        }
+    }
+
+    // starts from an existing automaton, i.e. to edit a loaded file
+    pub fn from_automaton(automaton: &Automaton<S>) -> Self {
+        let mut transitions = HashMap::new();
+        for (key, end_state) in automaton.get_transitions() {
+            transitions.insert(key.clone(), end_state.clone());
+        }
+
+        Self {
+            alphabet: automaton.get_alphabet().clone(),
+            states: automaton.get_states().clone(),
+            initial_state: Some(automaton.get_initial_state().clone()),
+            final_states: automaton.get_final_states().clone(),
+            transitions,
+
+            history: vec![],
+        }
+    }
+
+    pub fn get_alphabet(&self) -> &Set<char> {
+        &self.alphabet
+    }
+
+    pub fn get_states(&self) -> &Set<S> {
+        &self.states
+    }
+
+    pub fn get_initial_state(&self) -> Option<&S> {
+        self.initial_state.as_ref()
+    }
+
+    pub fn get_final_states(&self) -> &Set<S> {
+        &self.final_states
+    }
+
+    pub fn get_transitions(&self) -> &HashMap<(S, char), S> {
+        &self.transitions
+    }
+
+    pub fn add_symbol(&mut self, symbol: char) -> Result<(), String> {
+        if self.alphabet.contains(&symbol) {
+            let error = format!("duplicate symbol '{}' in alphabet", symbol);
+            return Err(error);
+        }
+
+        self.commit(vec![Edit::InsertSymbol(symbol)]);
+        Ok(())
+    }
+
+    // removes the symbol together with its transitions
+    pub fn remove_symbol(&mut self, symbol: char) -> Result<(), String> {
+        if !self.alphabet.contains(&symbol) {
+            let error = format!("character '{}' missing from alphabet", symbol);
+            return Err(error);
+        }
+
+        let mut edits: Vec<Edit<S>> = self
+            .transitions
+            .iter()
+            .filter(|((_, other), _)| *other == symbol)
+            .map(|(key, _)| Edit::DeleteTransition(key.clone()))
+            .collect();
+        edits.push(Edit::DeleteSymbol(symbol));
+
+        self.commit(edits);
+        Ok(())
+    }
+
+    pub fn add_state(&mut self, state: S) -> Result<(), String> {
+        if self.states.contains(&state) {
+            let error = format!("duplicate state '{}' in set of states", state);
+            return Err(error);
+        }
+
+        self.commit(vec![Edit::InsertState(state)]);
+        Ok(())
+    }
+
+    // removes the state together with its transitions, and unmarks it as initial or final
+    pub fn remove_state(&mut self, state: &S) -> Result<(), String> {
+        if !self.states.contains(state) {
+            let error = format!("state '{}' not in set of states", state);
+            return Err(error);
+        }
+
+        let mut edits: Vec<Edit<S>> = self
+            .transitions
+            .iter()
+            .filter(|((start_state, _), end_state)| start_state == state || *end_state == state)
+            .map(|(key, _)| Edit::DeleteTransition(key.clone()))
+            .collect();
+        if self.final_states.contains(state) {
+            edits.push(Edit::DeleteFinalState(state.clone()));
+        }
+        if self.initial_state.as_ref() == Some(state) {
+            edits.push(Edit::SetInitialState(None));
+        }
+        edits.push(Edit::DeleteState(state.clone()));
+
+        self.commit(edits);
+        Ok(())
+    }
+
+    pub fn set_initial_state(&mut self, state: S) -> Result<(), String> {
+        match self.check_state(&state) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+
+        self.commit(vec![Edit::SetInitialState(Some(state))]);
+        Ok(())
+    }
+
+    // marks or unmarks the state as final
+    pub fn set_final(&mut self, state: S, is_final: bool) -> Result<(), String> {
+        match self.check_state(&state) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+
+        let edit = match (is_final, self.final_states.contains(&state)) {
+            (true, false) => Edit::InsertFinalState(state),
+            (false, true) => Edit::DeleteFinalState(state),
+            _ => return Ok(()),
+        };
+
+        self.commit(vec![edit]);
+        Ok(())
+    }
+
+    // adds the transition, replacing the previous one for (start_state, symbol)
+    pub fn add_transition(
+        &mut self,
+        start_state: S,
+        symbol: char,
+        end_state: S,
+    ) -> Result<(), String> {
+        match self.check_state(&start_state) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+        match self.check_state(&end_state) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+        if !self.alphabet.contains(&symbol) {
+            let error = format!("character '{}' missing from alphabet", symbol);
+            return Err(error);
+        }
+
+        let key = (start_state, symbol);
+        let mut edits = vec![];
+        if self.transitions.contains_key(&key) {
+            edits.push(Edit::DeleteTransition(key.clone()));
+        }
+        edits.push(Edit::InsertTransition(key, end_state));
+
+        self.commit(edits);
+        Ok(())
+    }
+
+    pub fn remove_transition(&mut self, start_state: &S, symbol: char) -> Result<(), String> {
+        let key = (start_state.clone(), symbol);
+        if !self.transitions.contains_key(&key) {
+            let error = format!("no transition from state '{}' on '{}'", start_state, symbol);
+            return Err(error);
+        }
+
+        self.commit(vec![Edit::DeleteTransition(key)]);
+        Ok(())
+    }
+
+    // reverts the last command, returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let inverses = match self.history.pop() {
+            Some(inverses) => inverses,
+            None => return false,
+        };
+
+        for edit in inverses.into_iter().rev() {
+            self.apply(edit);
+        }
+
+        true
+    }
+
+    // returns the (state, symbol) pairs without a transition, in sorted order
+    pub fn missing_transitions(&self) -> Vec<(S, char)> {
+        let mut states: Vec<&S> = self.states.iter().collect();
+        states.sort();
+        let mut alphabet: Vec<char> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut missing = vec![];
+        for state in states {
+            for symbol in &alphabet {
+                let key = (state.clone(), *symbol);
+                if !self.transitions.contains_key(&key) {
+                    missing.push(key);
+                }
+            }
+        }
+
+        missing
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing_transitions().is_empty()
+    }
+
+    pub fn build(&self) -> Result<Automaton<S>, AutomatonError> {
+        let initial_state = match &self.initial_state {
+            Some(initial_state) => initial_state.clone(),
+            None => {
+                let message = String::from("missing initial state");
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+        };
+
+        let mut transitions = HashMap::new();
+        for (key, end_state) in &self.transitions {
+            transitions.insert(key.clone(), end_state.clone());
+        }
+
+        Automaton::from_parts(
+            self.alphabet.clone(),
+            self.states.clone(),
+            initial_state,
+            self.final_states.clone(),
+            transitions,
+        )
+    }
+
+    // writes the automaton in the .dfa format, with everything sorted
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let output = match self.to_dfa() {
+            Ok(output) => output,
+            Err(e) => return Err(e),
+        };
+
+        let mut file = match File::create(file_path) {
+            Ok(file) => file,
+            Err(e) => {
+                let error = format!("could not create output file: {}", e);
+                return Err(error);
+            }
+        };
+
+        match file.write_all(output.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let error = format!("could not write to output file: {}", e);
+                Err(error)
+            }
+        }
+    }
+
+    // returns the automaton in the canonical .dfa format
+    pub fn to_dfa(&self) -> Result<String, String> {
+        let automaton = match self.build() {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.to_string()),
+        };
+        // the file format needs at least one transition
+        if automaton.get_transitions().len() == 0 {
+            return Err(String::from("missing transitions"));
+        }
+
+        Ok(automaton.to_dfa_string())
+    }
+
+    // runs a textual command, as used by the command line, e.g.
+    // "state q0 q1", "symbol a b", "initial q0", "final q1",
+    // "transition q0 a q1", "remove-state q1" or "undo"
+    pub fn run_command(&mut self, command: &str) -> Result<(), String> {
+        let parts: Vec<&str> = command.split_whitespace().collect();
+        let (name, args) = match parts.split_first() {
+            Some((name, args)) => (*name, args),
+            None => return Err(String::from("empty command")),
+        };
+
+        match (name, args.len()) {
+            ("symbol", 1..) => self.each(args, |builder, arg| match parse_symbol(arg) {
+                Ok(symbol) => builder.add_symbol(symbol),
+                Err(e) => Err(e),
+            }),
+            ("remove-symbol", 1) => match parse_symbol(args[0]) {
+                Ok(symbol) => self.remove_symbol(symbol),
+                Err(e) => Err(e),
+            },
+            ("state", 1..) => self.each(args, |builder, arg| match parse_state::<S>(arg) {
+                Ok(state) => builder.add_state(state),
+                Err(e) => Err(e),
+            }),
+            ("remove-state", 1) => match parse_state::<S>(args[0]) {
+                Ok(state) => self.remove_state(&state),
+                Err(e) => Err(e),
+            },
+            ("initial", 1) => match parse_state::<S>(args[0]) {
+                Ok(state) => self.set_initial_state(state),
+                Err(e) => Err(e),
+            },
+            ("final", 1..) => self.each(args, |builder, arg| match parse_state::<S>(arg) {
+                Ok(state) => builder.set_final(state, true),
+                Err(e) => Err(e),
+            }),
+            ("not-final", 1..) => self.each(args, |builder, arg| match parse_state::<S>(arg) {
+                Ok(state) => builder.set_final(state, false),
+                Err(e) => Err(e),
+            }),
+            ("transition", 3) => {
+                match (
+                    parse_state::<S>(args[0]),
+                    parse_symbol(args[1]),
+                    parse_state::<S>(args[2]),
+                ) {
+                    (Ok(start_state), Ok(symbol), Ok(end_state)) => {
+                        self.add_transition(start_state, symbol, end_state)
+                    }
+                    (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => Err(e),
+                }
+            }
+            ("remove-transition", 2) => match (parse_state::<S>(args[0]), parse_symbol(args[1])) {
+                (Ok(start_state), Ok(symbol)) => self.remove_transition(&start_state, symbol),
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            },
+            ("undo", 0) => match self.undo() {
+                true => Ok(()),
+                false => Err(String::from("nothing to undo")),
+            },
+            _ => {
+                let error = format!("invalid command '{}'", command);
+                Err(error)
+            }
+        }
+    }
+
+    // runs a command for every argument as a single step, so that it is undone at once
+    fn each<F>(&mut self, args: &[&str], command: F) -> Result<(), String>
+    where
+        F: Fn(&mut Self, &str) -> Result<(), String>,
+    {
+        let depth = self.history.len();
+        for arg in args {
+            match command(self, arg) {
+                Ok(_) => (),
+                Err(e) => {
+                    // revert the arguments that were already applied
+                    while self.history.len() > depth {
+                        self.undo();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        // merge the steps into a single one
+        let mut merged = vec![];
+        for inverses in self.history.drain(depth..) {
+            merged.extend(inverses);
+        }
+        if !merged.is_empty() {
+            self.history.push(merged);
+        }
+
+        Ok(())
+    }
+
+    fn check_state(&self, state: &S) -> Result<(), String> {
+        if !self.states.contains(state) {
+            let error = format!("state '{}' not in set of states", state);
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    // applies the edits as a single step of the history
+    fn commit(&mut self, edits: Vec<Edit<S>>) {
+        let inverses = edits.into_iter().map(|edit| self.apply(edit)).collect();
+        self.history.push(inverses);
+    }
+
+    // applies the edit and returns its inverse
+    fn apply(&mut self, edit: Edit<S>) -> Edit<S> {
+        match edit {
+            Edit::InsertSymbol(symbol) => {
+                self.alphabet.insert(symbol);
+                Edit::DeleteSymbol(symbol)
+            }
+            Edit::DeleteSymbol(symbol) => {
+                self.alphabet.remove(&symbol);
+                Edit::InsertSymbol(symbol)
+            }
+            Edit::InsertState(state) => {
+                self.states.insert(state.clone());
+                Edit::DeleteState(state)
+            }
+            Edit::DeleteState(state) => {
+                self.states.remove(&state);
+                Edit::InsertState(state)
+            }
+            Edit::SetInitialState(state) => {
+                let previous = std::mem::replace(&mut self.initial_state, state);
+                Edit::SetInitialState(previous)
+            }
+            Edit::InsertFinalState(state) => {
+                self.final_states.insert(state.clone());
+                Edit::DeleteFinalState(state)
+            }
+            Edit::DeleteFinalState(state) => {
+                self.final_states.remove(&state);
+                Edit::InsertFinalState(state)
+            }
+            Edit::InsertTransition(key, end_state) => {
+                self.transitions.insert(key.clone(), end_state);
+                Edit::DeleteTransition(key)
+            }
+            Edit::DeleteTransition(key) => match self.transitions.get(&key).cloned() {
+                Some(end_state) => {
+                    self.transitions.remove(&key);
+                    Edit::InsertTransition(key, end_state)
+                }
+                None => Edit::DeleteTransition(key),
+            },
+        }
+    }
+}
+
+fn parse_state<S>(token: &str) -> Result<S, String>
+where
+    S: StateLabel,
+{
+    match token.parse::<S>() {
+        Ok(state) => Ok(state),
+        Err(e) => {
+            let error = format!("invalid state '{}': {}", token, e);
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs::File;
+use std::io::Write;
+
+use super::automaton::Automaton;
+use super::state::StateLabel;
+
+const HEADER: &str = "// generated from .dfa files by the automata crate, do not edit by hand\n";
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // generates a standalone Rust function that accepts the same language,
+    // implemented as a `match` over (state, symbol) pairs
+    pub fn generate_rust(&self, function_name: &str) -> String {
+        // number the states densely, in sorted order
+        let mut states: Vec<&S> = self.get_states().iter().collect();
+        states.sort();
+        let index_of = |state: &S| states.iter().position(|s| *s == state).unwrap_or(0);
+
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+
+        let mut code = String::new();
+        code.push_str(&format!(
+            "pub fn {}(sequence: &str) -> bool {{\n",
+            function_name
+        ));
+        code.push_str(&format!(
+            "    let mut state: usize = {};\n",
+            index_of(self.get_initial_state())
+        ));
+        code.push_str("    for symbol in sequence.chars() {\n");
+        code.push_str("        state = match (state, symbol) {\n");
+
+        for (start_index, start_state) in states.iter().enumerate() {
+            // group the symbols of this state by their end state, keeping the order of the alphabet
+            let mut arms: Vec<(usize, Vec<char>)> = vec![];
+            for symbol in &alphabet {
+                let end_state = match self
+                    .get_transitions()
+                    .get(&((*start_state).clone(), *symbol))
+                {
+                    Some(end_state) => end_state,
+                    None => continue,
+                };
+
+                let end_index = index_of(end_state);
+                match arms.iter_mut().find(|(index, _)| *index == end_index) {
+                    Some((_, symbols)) => symbols.push(*symbol),
+                    None => arms.push((end_index, vec![*symbol])),
+                }
+            }
+
+            for (end_index, symbols) in arms {
+                code.push_str(&format!(
+                    "            ({}, {}) => {},\n",
+                    start_index,
+                    symbol_pattern(&symbols),
+                    end_index
+                ));
+            }
+        }
+
+        code.push_str("            _ => return false,\n");
+        code.push_str("        };\n");
+        code.push_str("    }\n\n");
+
+        // the final states, or `false` if there are none
+        let mut final_states: Vec<usize> = self.get_final_states().iter().map(index_of).collect();
+        final_states.sort();
+
+        if final_states.is_empty() {
+            code.push_str("    let _ = state;\n    false\n");
+        } else {
+            let final_states: Vec<String> = final_states.iter().map(|s| s.to_string()).collect();
+            code.push_str(&format!(
+                "    matches!(state, {})\n",
+                final_states.join(" | ")
+            ));
+        }
+        code.push_str("}\n");
+
+        code
+    }
+}
+
+// generates a module with one function for every (file, function name) pair,
+// meant to be called from a build script or from the command line
+pub fn generate_rust_module(automata: &[(&str, &str)]) -> Result<String, String> {
+    let mut code = String::from(HEADER);
+
+    for (file_path, function_name) in automata {
+        let automaton = match Automaton::<String>::open(file_path) {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.diagnostic()),
+        };
+
+        code.push('\n');
+        code.push_str(&automaton.generate_rust(function_name));
+    }
+
+    Ok(code)
+}
+
+// writes the generated module to the given file
+pub fn write_rust_module(automata: &[(&str, &str)], output_path: &str) -> Result<(), String> {
+    let code = match generate_rust_module(automata) {
+        Ok(code) => code,
+        Err(e) => return Err(e),
+    };
+
+    let mut file = match File::create(output_path) {
+        Ok(file) => file,
+        Err(e) => {
+            let error = format!("could not create output file: {}", e);
+            return Err(error);
+        }
+    };
+
+    match file.write_all(code.as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let error = format!("could not write to output file: {}", e);
+            Err(error)
+        }
+    }
+}
+
+// builds a pattern such as 'a'..='z' | '_' out of sorted symbols,
+// merging runs of consecutive characters into ranges
+fn symbol_pattern(symbols: &[char]) -> String {
+    let mut parts: Vec<String> = vec![];
+
+    let mut index = 0;
+    while index < symbols.len() {
+        let start = symbols[index];
+        let mut end = start;
+        while index + 1 < symbols.len() && symbols[index + 1] as u32 == end as u32 + 1 {
+            index += 1;
+            end = symbols[index];
+        }
+
+        match end as u32 - start as u32 {
+            0 => parts.push(format!("{:?}", start)),
+            1 => {
+                parts.push(format!("{:?}", start));
+                parts.push(format!("{:?}", end));
+            }
+            _ => parts.push(format!("{:?}..={:?}", start, end)),
+        }
+
+        index += 1;
+    }
+
+    parts.join(" | ")
+}
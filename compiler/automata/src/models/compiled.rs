@@ -0,0 +1,276 @@
+use hash_map::HashMap;
+
+use super::automaton::Automaton;
+use super::state::StateLabel;
+use super::transducer::Moore;
+
+// row 0 of the transition table is a dead state that loops on every class,
+// and column 0 is the class of every character outside of the alphabet
+const DEAD_STATE: u32 = 0;
+const UNKNOWN_CLASS: u16 = 0;
+const ASCII_SIZE: usize = 128;
+
+pub struct CompiledDfa {
+    // equivalence class of every ASCII character, looked up without hashing
+    ascii_classes: [u16; ASCII_SIZE],
+    // equivalence class of every other character in the alphabet
+    unicode_classes: HashMap<char, u16>,
+    class_count: usize,
+
+    // transitions[state * class_count + class] = next state
+    transitions: Vec<u32>,
+    initial_state: u32,
+    final_states: Vec<bool>,
+}
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // builds a table-driven version of the automaton that accepts the same language
+    pub fn compile(&self) -> CompiledDfa {
+        let (compiled, _) = compile_indexed(self);
+        compiled
+    }
+}
+
+impl<S, O> Moore<S, O>
+where
+    S: StateLabel,
+    O: Clone,
+{
+    // builds a table-driven version of the machine with the same outputs
+    pub fn compile(&self) -> CompiledMoore<O> {
+        let (dfa, indices) = compile_indexed(self.get_automaton());
+
+        let mut outputs = vec![None; dfa.state_count()];
+        for (state, index) in &indices {
+            outputs[*index as usize] = self.get_output(state).cloned();
+        }
+
+        CompiledMoore { dfa, outputs }
+    }
+}
+
+// a table-driven Moore machine, i.e. a compiled automaton with an output per state
+pub struct CompiledMoore<O> {
+    dfa: CompiledDfa,
+    outputs: Vec<Option<O>>,
+}
+
+impl<O> CompiledMoore<O> {
+    // returns the output of the state reached by an accepted sequence
+    pub fn classify(&self, sequence: &str) -> Option<&O> {
+        let state = self.dfa.run(sequence);
+        if !self.dfa.final_states[state as usize] {
+            return None;
+        }
+
+        self.outputs[state as usize].as_ref()
+    }
+
+    // returns the length of the longest prefix of the sequence that has an output,
+    // along with the output, i.e. the next token of a maximal munch lexer
+    pub fn longest_match(&self, sequence: &[char]) -> Option<(usize, &O)> {
+        let (longest, _) = self.longest_match_partial(sequence.iter().cloned());
+        longest
+    }
+
+    // the longest match of a sequence that may go on, i.e. the input read so far:
+    // also tells whether the machine got stuck, otherwise more input may match longer
+    pub fn longest_match_partial<I>(&self, sequence: I) -> (Option<(usize, &O)>, bool)
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut current_state = self.dfa.initial_state;
+        let mut longest = self.output(current_state).map(|output| (0, output));
+
+        for (index, symbol) in sequence.into_iter().enumerate() {
+            current_state = self.dfa.next_state(current_state, self.dfa.class(symbol));
+            if current_state == DEAD_STATE {
+                return (longest, true);
+            }
+
+            if let Some(output) = self.output(current_state) {
+                longest = Some((index + 1, output));
+            }
+        }
+
+        (longest, false)
+    }
+
+    fn output(&self, state: u32) -> Option<&O> {
+        match self.dfa.final_states[state as usize] {
+            true => self.outputs[state as usize].as_ref(),
+            false => None,
+        }
+    }
+}
+
+// compiles the automaton and also returns the index given to every state
+fn compile_indexed<S>(automaton: &Automaton<S>) -> (CompiledDfa, HashMap<S, u32>)
+where
+    S: StateLabel,
+{
+    // number the states densely, in sorted order, leaving 0 for the dead state
+    let mut states: Vec<&S> = automaton.get_states().iter().collect();
+    states.sort();
+
+    let mut indices: HashMap<S, u32> = HashMap::new();
+    for (index, state) in states.iter().enumerate() {
+        indices.insert((*state).clone(), index as u32 + 1);
+    }
+
+    let state_count = states.len() + 1;
+    let mut final_states = vec![false; state_count];
+    for state in automaton.get_final_states() {
+        if let Some(&index) = indices.get(state) {
+            final_states[index as usize] = true;
+        }
+    }
+
+    // the column of a symbol holds its next state from every state;
+    // symbols with identical columns are interchangeable, so they share a class
+    let mut alphabet: Vec<char> = automaton.get_alphabet().iter().cloned().collect();
+    alphabet.sort();
+
+    let mut columns: Vec<Vec<u32>> = vec![vec![DEAD_STATE; state_count]];
+    let mut column_classes: HashMap<Vec<u32>, u16> = HashMap::new();
+    column_classes.insert(vec![DEAD_STATE; state_count], UNKNOWN_CLASS);
+
+    let mut ascii_classes = [UNKNOWN_CLASS; ASCII_SIZE];
+    let mut unicode_classes = HashMap::new();
+
+    for symbol in alphabet {
+        let mut column = vec![DEAD_STATE; state_count];
+        for state in &states {
+            let next_state = match automaton.get_transitions().get(&((*state).clone(), symbol)) {
+                Some(next_state) => next_state,
+                None => continue,
+            };
+
+            let start_index = indices.get(state).cloned().unwrap_or(DEAD_STATE);
+            let end_index = indices.get(next_state).cloned().unwrap_or(DEAD_STATE);
+            column[start_index as usize] = end_index;
+        }
+
+        let class = match column_classes.get(&column) {
+            Some(&class) => class,
+            None => {
+                let class = columns.len() as u16;
+                column_classes.insert(column.clone(), class);
+                columns.push(column);
+                class
+            }
+        };
+
+        if (symbol as usize) < ASCII_SIZE {
+            ascii_classes[symbol as usize] = class;
+        } else {
+            unicode_classes.insert(symbol, class);
+        }
+    }
+
+    // lay the columns out row by row
+    let class_count = columns.len();
+    let mut transitions = vec![DEAD_STATE; state_count * class_count];
+    for (class, column) in columns.iter().enumerate() {
+        for (state, next_state) in column.iter().enumerate() {
+            transitions[state * class_count + class] = *next_state;
+        }
+    }
+
+    let initial_state = indices
+        .get(automaton.get_initial_state())
+        .cloned()
+        .unwrap_or(DEAD_STATE);
+
+    let compiled = CompiledDfa {
+        ascii_classes,
+        unicode_classes,
+        class_count,
+        transitions,
+        initial_state,
+        final_states,
+    };
+
+    (compiled, indices)
+}
+
+impl CompiledDfa {
+    // returns the number of states, including the dead state
+    pub fn state_count(&self) -> usize {
+        self.final_states.len()
+    }
+
+    // returns the number of character classes, including the class of unknown characters
+    pub fn class_count(&self) -> usize {
+        self.class_count
+    }
+
+    pub fn validate(&self, sequence: &str) -> bool {
+        self.final_states[self.run(sequence) as usize]
+    }
+
+    // returns the characters of the alphabet grouped by equivalence class,
+    // leaving out the characters that have no transitions at all
+    pub fn get_classes(&self) -> Vec<Vec<char>> {
+        let mut classes = vec![vec![]; self.class_count];
+        for (symbol, class) in self.ascii_classes.iter().enumerate() {
+            if *class != UNKNOWN_CLASS {
+                classes[*class as usize].push(symbol as u8 as char);
+            }
+        }
+        for (symbol, class) in &self.unicode_classes {
+            classes[*class as usize].push(*symbol);
+        }
+
+        // drop the class of unknown characters
+        classes.remove(UNKNOWN_CLASS as usize);
+        for class in classes.iter_mut() {
+            class.sort();
+        }
+
+        classes
+    }
+
+    // returns the state reached by the sequence, which is the dead state if it got stuck
+    fn run(&self, sequence: &str) -> u32 {
+        let mut current_state = self.initial_state;
+
+        if sequence.is_ascii() {
+            // fast path: every byte is a character and has a class in the array
+            for &byte in sequence.as_bytes() {
+                let class = self.ascii_classes[byte as usize];
+                current_state = self.next_state(current_state, class);
+                if current_state == DEAD_STATE {
+                    return DEAD_STATE;
+                }
+            }
+        } else {
+            for symbol in sequence.chars() {
+                current_state = self.next_state(current_state, self.class(symbol));
+                if current_state == DEAD_STATE {
+                    return DEAD_STATE;
+                }
+            }
+        }
+
+        current_state
+    }
+
+    fn class(&self, symbol: char) -> u16 {
+        if (symbol as usize) < ASCII_SIZE {
+            return self.ascii_classes[symbol as usize];
+        }
+
+        match self.unicode_classes.get(&symbol) {
+            Some(&class) => class,
+            None => UNKNOWN_CLASS,
+        }
+    }
+
+    fn next_state(&self, state: u32, class: u16) -> u32 {
+        self.transitions[state as usize * self.class_count + class as usize]
+    }
+}
@@ -0,0 +1,347 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+// the name used for sources that are not read from a file
+pub const UNNAMED_SOURCE: &str = "<input>";
+
+// where an error happened: the file, the 1-based line and column, and the line itself
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SourceLocation {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub source: String,
+}
+
+impl SourceLocation {
+    pub fn new(path: &str, line: usize, column: usize, source: &str) -> Self {
+        Self {
+            path: String::from(path),
+            line,
+            column,
+            source: String::from(source),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AutomatonError {
+    // the file could not be opened or read
+    Io {
+        path: String,
+        message: String,
+    },
+
+    // one of the header lines is missing
+    MissingAlphabet {
+        location: SourceLocation,
+    },
+    MissingStates {
+        location: SourceLocation,
+    },
+    MissingInitialState {
+        location: SourceLocation,
+    },
+    MissingFinalStates {
+        location: SourceLocation,
+    },
+    MissingTransitions {
+        location: SourceLocation,
+    },
+    MissingStackAlphabet {
+        location: SourceLocation,
+    },
+    MissingInitialStackSymbol {
+        location: SourceLocation,
+    },
+
+    // symbols
+    InvalidSymbol {
+        location: SourceLocation,
+        token: String,
+    },
+    DuplicateSymbol {
+        location: SourceLocation,
+        token: String,
+    },
+    SymbolNotInAlphabet {
+        location: SourceLocation,
+        token: String,
+    },
+    SymbolNotInStackAlphabet {
+        location: SourceLocation,
+        token: String,
+    },
+
+    // states
+    InvalidState {
+        location: SourceLocation,
+        token: String,
+        reason: String,
+    },
+    DuplicateState {
+        location: SourceLocation,
+        token: String,
+    },
+    UndefinedState {
+        location: SourceLocation,
+        token: String,
+    },
+    DuplicateFinalState {
+        location: SourceLocation,
+        token: String,
+    },
+
+    // transitions
+    MalformedTransition {
+        location: SourceLocation,
+        token: String,
+    },
+    DuplicateTransition {
+        location: SourceLocation,
+        token: String,
+    },
+    MalformedPdaTransition {
+        location: SourceLocation,
+        token: String,
+    },
+
+    // transducer outputs
+    DuplicateOutput {
+        location: SourceLocation,
+        token: String,
+    },
+
+    // the components given to `Automaton::from_parts` do not fit together
+    InconsistentParts {
+        message: String,
+    },
+
+    // the JSON representation could not be read
+    InvalidJson {
+        message: String,
+    },
+
+    // a regular expression could not be parsed, the column is 1-based
+    InvalidRegex {
+        pattern: String,
+        column: usize,
+        message: String,
+    },
+}
+
+impl AutomatonError {
+    pub fn get_location(&self) -> Option<&SourceLocation> {
+        match self {
+            Self::Io { .. }
+            | Self::InconsistentParts { .. }
+            | Self::InvalidJson { .. }
+            | Self::InvalidRegex { .. } => None,
+            Self::MissingAlphabet { location }
+            | Self::MissingStates { location }
+            | Self::MissingInitialState { location }
+            | Self::MissingFinalStates { location }
+            | Self::MissingTransitions { location }
+            | Self::MissingStackAlphabet { location }
+            | Self::MissingInitialStackSymbol { location }
+            | Self::InvalidSymbol { location, .. }
+            | Self::DuplicateSymbol { location, .. }
+            | Self::SymbolNotInAlphabet { location, .. }
+            | Self::SymbolNotInStackAlphabet { location, .. }
+            | Self::InvalidState { location, .. }
+            | Self::DuplicateState { location, .. }
+            | Self::UndefinedState { location, .. }
+            | Self::DuplicateFinalState { location, .. }
+            | Self::MalformedTransition { location, .. }
+            | Self::DuplicateTransition { location, .. }
+            | Self::MalformedPdaTransition { location, .. }
+            | Self::DuplicateOutput { location, .. } => Some(location),
+        }
+    }
+
+    // returns the offending token, if the error has one
+    pub fn get_token(&self) -> Option<&str> {
+        match self {
+            Self::InvalidSymbol { token, .. }
+            | Self::DuplicateSymbol { token, .. }
+            | Self::SymbolNotInAlphabet { token, .. }
+            | Self::SymbolNotInStackAlphabet { token, .. }
+            | Self::InvalidState { token, .. }
+            | Self::DuplicateState { token, .. }
+            | Self::UndefinedState { token, .. }
+            | Self::DuplicateFinalState { token, .. }
+            | Self::MalformedTransition { token, .. }
+            | Self::DuplicateTransition { token, .. }
+            | Self::MalformedPdaTransition { token, .. }
+            | Self::DuplicateOutput { token, .. } => Some(token),
+            _ => None,
+        }
+    }
+
+    // replaces the path of the source, i.e. when the automaton was read from memory
+    pub fn with_path(mut self, file_path: &str) -> Self {
+        if let Self::Io { path, .. } = &mut self {
+            *path = String::from(file_path);
+        }
+        if let Some(location) = self.get_location_mut() {
+            location.path = String::from(file_path);
+        }
+
+        self
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::Io { message, .. } => message.clone(),
+            Self::MissingAlphabet { .. } => String::from("missing alphabet"),
+            Self::MissingStates { .. } => String::from("missing set of states"),
+            Self::MissingInitialState { .. } => String::from("missing initial state"),
+            Self::MissingFinalStates { .. } => String::from("missing final states"),
+            Self::MissingTransitions { .. } => String::from("missing transitions"),
+            Self::MissingStackAlphabet { .. } => String::from("missing stack alphabet"),
+            Self::MissingInitialStackSymbol { .. } => String::from("missing initial stack symbol"),
+            Self::InvalidSymbol { token, .. } => format!("invalid symbol '{}'", token),
+            Self::DuplicateSymbol { token, .. } => {
+                format!("duplicate symbol '{}' in alphabet", token)
+            }
+            Self::SymbolNotInAlphabet { token, .. } => {
+                format!("character '{}' missing from alphabet", token)
+            }
+            Self::SymbolNotInStackAlphabet { token, .. } => {
+                format!("symbol '{}' missing from stack alphabet", token)
+            }
+            Self::InvalidState { token, reason, .. } => {
+                format!("invalid state '{}': {}", token, reason)
+            }
+            Self::DuplicateState { token, .. } => {
+                format!("duplicate state '{}' in set of states", token)
+            }
+            Self::UndefinedState { token, .. } => {
+                format!("state '{}' not in set of states", token)
+            }
+            Self::DuplicateFinalState { token, .. } => {
+                format!("duplicate final state '{}'", token)
+            }
+            Self::MalformedTransition { token, .. } => {
+                format!(
+                    "invalid transition '{}', expected: state symbol state",
+                    token
+                )
+            }
+            Self::DuplicateTransition { token, .. } => {
+                format!("duplicate transition key for transition '{}'", token)
+            }
+            Self::MalformedPdaTransition { token, .. } => {
+                format!(
+                    "invalid transition '{}', expected: state input pop state push...",
+                    token
+                )
+            }
+            Self::DuplicateOutput { token, .. } => {
+                format!("duplicate output for state '{}'", token)
+            }
+            Self::InconsistentParts { message } => message.clone(),
+            Self::InvalidJson { message } => format!("invalid JSON: {}", message),
+            Self::InvalidRegex {
+                pattern,
+                column,
+                message,
+            } => format!(
+                "invalid regex '{}' at column {}: {}",
+                pattern, column, message
+            ),
+        }
+    }
+
+    // renders the error with the offending line and a caret under the token, e.g.
+    //
+    // error: duplicate state '1' in set of states
+    //  --> input/example.dfa:2:5
+    //   |
+    // 2 | 0 1 1
+    //   |     ^
+    pub fn diagnostic(&self) -> String {
+        let mut output = format!("error: {}", self.message());
+
+        let location = match self.get_location() {
+            Some(location) => location,
+            None => {
+                if let Self::Io { path, .. } = self {
+                    output.push_str(&format!("\n --> {}", path));
+                }
+                return output;
+            }
+        };
+
+        let line_number = location.line.to_string();
+        let padding = " ".repeat(line_number.len());
+        let width = match self.get_token() {
+            Some(token) => token.chars().count().max(1),
+            None => 1,
+        };
+
+        output.push_str(&format!(
+            "\n{} --> {}:{}:{}",
+            padding, location.path, location.line, location.column
+        ));
+        output.push_str(&format!("\n{} |", padding));
+        output.push_str(&format!("\n{} | {}", line_number, location.source));
+        output.push_str(&format!(
+            "\n{} | {}{}",
+            padding,
+            " ".repeat(location.column.saturating_sub(1)),
+            "^".repeat(width)
+        ));
+
+        output
+    }
+
+    fn get_location_mut(&mut self) -> Option<&mut SourceLocation> {
+        match self {
+            Self::Io { .. }
+            | Self::InconsistentParts { .. }
+            | Self::InvalidJson { .. }
+            | Self::InvalidRegex { .. } => None,
+            Self::MissingAlphabet { location }
+            | Self::MissingStates { location }
+            | Self::MissingInitialState { location }
+            | Self::MissingFinalStates { location }
+            | Self::MissingTransitions { location }
+            | Self::MissingStackAlphabet { location }
+            | Self::MissingInitialStackSymbol { location }
+            | Self::InvalidSymbol { location, .. }
+            | Self::DuplicateSymbol { location, .. }
+            | Self::SymbolNotInAlphabet { location, .. }
+            | Self::SymbolNotInStackAlphabet { location, .. }
+            | Self::InvalidState { location, .. }
+            | Self::DuplicateState { location, .. }
+            | Self::UndefinedState { location, .. }
+            | Self::DuplicateFinalState { location, .. }
+            | Self::MalformedTransition { location, .. }
+            | Self::DuplicateTransition { location, .. }
+            | Self::MalformedPdaTransition { location, .. }
+            | Self::DuplicateOutput { location, .. } => Some(location),
+        }
+    }
+}
+
+impl Display for AutomatonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get_location() {
+            Some(location) => write!(
+                f,
+                "{}:{}:{}: {}",
+                location.path,
+                location.line,
+                location.column,
+                self.message()
+            ),
+            None => match self {
+                Self::Io { path, message } => write!(f, "{}: {}", path, message),
+                _ => write!(f, "{}", self.message()),
+            },
+        }
+    }
+}
+
+impl Error for AutomatonError {}
@@ -0,0 +1,223 @@
+use super::automaton::Automaton;
+use super::random::{random_automaton, Random, RandomConfig, SYMBOLS};
+use super::state::{State, StateLabel};
+
+const MAX_STATES: usize = 8;
+const MAX_ALPHABET: usize = 6;
+const MAX_LENGTH: usize = 12;
+const SEQUENCES_PER_CASE: usize = 20;
+const MUTATIONS_PER_CASE: usize = 5;
+
+// what a fuzzing run went through
+pub struct FuzzReport {
+    pub automata: usize,
+    pub sequences: usize,
+    pub mutations: usize,
+    // mutated files that were rejected by the parser, as they should be
+    pub invalid_mutations: usize,
+}
+
+// generates random automata and checks that they survive being written and parsed
+// again, both as .dfa files and as JSON, and that every way of running them agrees
+// with a reference simulator; the files are also mutated at random to make sure that
+// the parser never panics and that whatever it accepts still runs correctly
+pub fn fuzz(iterations: usize, seed: u64) -> Result<FuzzReport, String> {
+    let mut random = Random::new(seed);
+    let mut report = FuzzReport {
+        automata: 0,
+        sequences: 0,
+        mutations: 0,
+        invalid_mutations: 0,
+    };
+
+    for case in 0..iterations {
+        let mut config =
+            RandomConfig::new(1 + random.below(MAX_STATES), 1 + random.below(MAX_ALPHABET));
+        config.transition_density = 0.3 + 0.7 * random.below(100) as f64 / 100.0;
+        config.final_density = random.below(100) as f64 / 100.0;
+
+        let automaton = random_automaton(&config, &mut random);
+        let fail = |message: String, text: &str| -> Result<FuzzReport, String> {
+            let error = format!("[seed {}, case {}] {}\n{}", seed, case, message, text);
+            Err(error)
+        };
+
+        // write, parse and write again
+        let text = automaton.to_dfa_string();
+        let parsed = match Automaton::from_reader(text.as_bytes()) {
+            Ok(parsed) => parsed,
+            Err(e) => return fail(format!("written automaton does not parse: {}", e), &text),
+        };
+        if !same_parts(&automaton, &parsed) {
+            return fail(
+                String::from("parsed automaton differs from the written one"),
+                &text,
+            );
+        }
+        if parsed.to_dfa_string() != text {
+            return fail(
+                String::from("writing the parsed automaton changes the file"),
+                &text,
+            );
+        }
+
+        // the same through JSON
+        let json = automaton.to_json_string();
+        let from_json: Automaton<State> = match Automaton::from_json(&json) {
+            Ok(from_json) => from_json,
+            Err(e) => return fail(format!("written JSON does not parse: {}", e), &json),
+        };
+        if !same_parts(&automaton, &from_json) || from_json.to_json_string() != json {
+            return fail(String::from("JSON round trip changes the automaton"), &json);
+        }
+        report.automata += 1;
+
+        // run the automata on accepted, rejected and arbitrary sequences
+        let mut sequences = vec![];
+        for _ in 0..SEQUENCES_PER_CASE {
+            let sequence = match random.below(3) {
+                0 => match automaton.random_accepted(MAX_LENGTH, &mut random) {
+                    Some(sequence) if !simulate(&automaton, &sequence) => {
+                        return fail(
+                            format!("random accepted sequence {:?} is rejected", sequence),
+                            &text,
+                        )
+                    }
+                    Some(sequence) => sequence,
+                    None => continue,
+                },
+                1 => match automaton.random_rejected(MAX_LENGTH, &mut random) {
+                    Some(sequence) if simulate(&automaton, &sequence) => {
+                        return fail(
+                            format!("random rejected sequence {:?} is accepted", sequence),
+                            &text,
+                        )
+                    }
+                    Some(sequence) => sequence,
+                    None => continue,
+                },
+                _ => random_sequence(&mut random),
+            };
+            sequences.push(sequence);
+        }
+
+        let compiled = automaton.compile();
+        for sequence in &sequences {
+            let expected = simulate(&automaton, sequence);
+            if automaton.validate(sequence) != expected
+                || parsed.validate(sequence) != expected
+                || compiled.validate(sequence) != expected
+            {
+                return fail(format!("disagreement on sequence {:?}", sequence), &text);
+            }
+            report.sequences += 1;
+        }
+
+        // mutate the file, whatever parses must still run like the reference
+        for _ in 0..MUTATIONS_PER_CASE {
+            let mutated = mutate(&text, &mut random);
+            report.mutations += 1;
+
+            let mutant = match Automaton::from_reader(mutated.as_bytes()) {
+                Ok(mutant) => mutant,
+                Err(_) => {
+                    report.invalid_mutations += 1;
+                    continue;
+                }
+            };
+
+            let compiled = mutant.compile();
+            for sequence in &sequences {
+                let expected = simulate(&mutant, sequence);
+                if mutant.validate(sequence) != expected || compiled.validate(sequence) != expected
+                {
+                    return fail(
+                        format!("mutant disagrees on sequence {:?}", sequence),
+                        &mutated,
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// the reference simulator: follows the transitions as they were parsed,
+// without the dense or compiled tables
+fn simulate<S>(automaton: &Automaton<S>, sequence: &str) -> bool
+where
+    S: StateLabel,
+{
+    let mut current_state = automaton.get_initial_state().clone();
+    for symbol in sequence.chars() {
+        current_state = match automaton.get_transitions().get(&(current_state, symbol)) {
+            Some(next_state) => next_state.clone(),
+            None => return false,
+        };
+    }
+
+    automaton.get_final_states().contains(&current_state)
+}
+
+fn same_parts<S>(automaton: &Automaton<S>, other: &Automaton<S>) -> bool
+where
+    S: StateLabel,
+{
+    automaton.get_alphabet() == other.get_alphabet()
+        && automaton.get_states() == other.get_states()
+        && automaton.get_initial_state() == other.get_initial_state()
+        && automaton.get_final_states() == other.get_final_states()
+        && automaton.get_transitions().len() == other.get_transitions().len()
+        && automaton
+            .get_transitions()
+            .iter()
+            .all(|(key, end_state)| other.get_transitions().get(key) == Some(end_state))
+}
+
+fn random_sequence(random: &mut Random) -> String {
+    let symbols: Vec<char> = SYMBOLS.chars().take(MAX_ALPHABET + 1).collect();
+    let length = random.below(MAX_LENGTH + 1);
+    (0..length)
+        .map(|_| symbols[random.below(symbols.len())])
+        .collect()
+}
+
+// applies a single random edit to the file: deletes, inserts or replaces a character,
+// or deletes, duplicates or swaps lines
+fn mutate(text: &str, random: &mut Random) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let mut lines: Vec<&str> = text.lines().collect();
+    let symbols: Vec<char> = SYMBOLS.chars().chain([' ', '\n']).collect();
+
+    match random.below(6) {
+        0 if !chars.is_empty() => {
+            chars.remove(random.below(chars.len()));
+        }
+        1 => {
+            let symbol = symbols[random.below(symbols.len())];
+            chars.insert(random.below(chars.len() + 1), symbol);
+        }
+        2 if !chars.is_empty() => {
+            let index = random.below(chars.len());
+            chars[index] = symbols[random.below(symbols.len())];
+        }
+        3 if !lines.is_empty() => {
+            lines.remove(random.below(lines.len()));
+            return lines.join("\n");
+        }
+        4 if !lines.is_empty() => {
+            let index = random.below(lines.len());
+            lines.insert(index, lines[index]);
+            return lines.join("\n");
+        }
+        5 if !lines.is_empty() => {
+            let (first, second) = (random.below(lines.len()), random.below(lines.len()));
+            lines.swap(first, second);
+            return lines.join("\n");
+        }
+        _ => (),
+    }
+
+    chars.into_iter().collect()
+}
@@ -0,0 +1,454 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+
+use utils::{get_next_line, open_file};
+
+use super::automaton::Automaton;
+use super::state::{State, StateLabel};
+
+const POSITIVE: char = '+';
+const NEGATIVE: char = '-';
+
+// labelled examples for the passive learner, read from a file in which every line is
+// "+ sequence" for an accepted sequence or "- sequence" for a rejected one
+pub struct Samples {
+    positive: Vec<String>,
+    negative: Vec<String>,
+}
+
+// answers the queries of the active learner
+pub trait Teacher {
+    // returns true if the sequence belongs to the language being learned
+    fn is_member(&self, sequence: &str) -> bool;
+
+    // returns a sequence on which the hypothesis is wrong, or None if it is correct
+    fn find_counterexample(&self, hypothesis: &Automaton<State>) -> Option<String>;
+}
+
+// an automaton can teach its own language
+impl<S> Teacher for Automaton<S>
+where
+    S: StateLabel,
+{
+    fn is_member(&self, sequence: &str) -> bool {
+        self.validate(sequence)
+    }
+
+    fn find_counterexample(&self, hypothesis: &Automaton<State>) -> Option<String> {
+        self.distinguishing_sequence(hypothesis)
+    }
+}
+
+impl Samples {
+    pub fn new(file_path: &str) -> Result<Self, String> {
+        let mut lines = match open_file(file_path) {
+            Ok(lines) => lines,
+            Err(e) => return Err(e),
+        };
+
+        let mut samples = Self {
+            positive: vec![],
+            negative: vec![],
+        };
+        let mut line_number = 0;
+
+        loop {
+            let line = match get_next_line(&mut lines) {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => return Err(e),
+            };
+            line_number += 1;
+
+            // skip empty lines
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let sign = chars.next();
+            let sequence = String::from(chars.as_str().trim());
+            match sign {
+                Some(POSITIVE) => samples.positive.push(sequence),
+                Some(NEGATIVE) => samples.negative.push(sequence),
+                _ => {
+                    let error = format!(
+                        "invalid sample on line {}: '{}', expected: {} or {} followed by a sequence",
+                        line_number, line, POSITIVE, NEGATIVE
+                    );
+                    return Err(error);
+                }
+            }
+        }
+
+        Ok(samples)
+    }
+
+    pub fn get_positive(&self) -> &Vec<String> {
+        &self.positive
+    }
+
+    pub fn get_negative(&self) -> &Vec<String> {
+        &self.negative
+    }
+}
+
+// infers an automaton that accepts every positive and rejects every negative sample
+// with RPNI: build the prefix tree of the positive samples, then merge its states
+// in shortlex order whenever the result still rejects every negative sample
+pub fn learn_rpni(samples: &Samples) -> Result<Automaton<State>, String> {
+    for sequence in &samples.positive {
+        if samples.negative.contains(sequence) {
+            let error = format!(
+                "sequence '{}' is both a positive and a negative sample",
+                sequence
+            );
+            return Err(error);
+        }
+    }
+
+    let mut alphabet: Vec<char> = samples
+        .positive
+        .iter()
+        .chain(&samples.negative)
+        .flat_map(|sequence| sequence.chars())
+        .collect::<Set<char>>()
+        .into_iter()
+        .collect();
+    alphabet.sort();
+
+    let mut tree = PrefixTree::new(&alphabet, &samples.positive);
+
+    // the red states are final decisions, the blue states are their successors
+    let mut red = vec![0];
+    while let Some(blue) = tree.first_blue(&red) {
+        let mut merged = false;
+        for &red_state in &red {
+            let candidate = tree.merge(red_state, blue);
+            if !samples
+                .negative
+                .iter()
+                .any(|sequence| candidate.accepts(sequence))
+            {
+                tree = candidate;
+                merged = true;
+                break;
+            }
+        }
+
+        // it cannot be merged with any red state, so it becomes one
+        if !merged {
+            red.push(blue);
+        }
+    }
+
+    Ok(tree.to_automaton())
+}
+
+// infers the minimal automaton of the teacher's language with L*: fill a table of
+// membership queries until it describes an automaton, then ask the teacher for a
+// counterexample and add its suffixes to the table, until there is none
+pub fn learn_lstar<T>(alphabet: &Set<char>, teacher: &T) -> Automaton<State>
+where
+    T: Teacher,
+{
+    let mut alphabet: Vec<char> = alphabet.iter().cloned().collect();
+    alphabet.sort();
+
+    let mut table = ObservationTable::new(alphabet);
+    loop {
+        table.close(teacher);
+
+        let hypothesis = table.to_automaton();
+        let counterexample = match teacher.find_counterexample(&hypothesis) {
+            Some(counterexample) => counterexample,
+            None => return hypothesis,
+        };
+
+        // every suffix of the counterexample becomes a column (Maler and Pnueli),
+        // which keeps the table consistent
+        let chars: Vec<char> = counterexample.chars().collect();
+        for start in 0..chars.len() {
+            let suffix: String = chars[start..].iter().collect();
+            if !table.suffixes.contains(&suffix) {
+                table.suffixes.push(suffix);
+            }
+        }
+    }
+}
+
+// a partial automaton over the indices of a sorted alphabet, in which the states
+// of the prefix tree are numbered in shortlex order
+#[derive(Clone)]
+struct PrefixTree {
+    next_states: Vec<Vec<Option<usize>>>,
+    final_states: Vec<bool>,
+    alphabet: Vec<char>,
+}
+
+impl PrefixTree {
+    fn new(alphabet: &[char], positive: &[String]) -> Self {
+        let mut prefixes: Vec<String> = vec![String::new()];
+        for sequence in positive {
+            let chars: Vec<char> = sequence.chars().collect();
+            for end in 1..=chars.len() {
+                prefixes.push(chars[..end].iter().collect());
+            }
+        }
+        // every prefix is a state, numbered in shortlex order
+        prefixes.sort_by(|a, b| (a.chars().count(), a).cmp(&(b.chars().count(), b)));
+        prefixes.dedup();
+
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for (index, prefix) in prefixes.iter().enumerate() {
+            indices.insert(prefix.clone(), index);
+        }
+
+        let mut tree = Self {
+            next_states: vec![vec![None; alphabet.len()]; prefixes.len()],
+            final_states: vec![false; prefixes.len()],
+            alphabet: alphabet.to_vec(),
+        };
+
+        for (index, prefix) in prefixes.iter().enumerate() {
+            if let Some(symbol) = prefix.chars().last() {
+                let parent: String = prefix.chars().take(prefix.chars().count() - 1).collect();
+                let parent = indices.get(&parent).cloned().unwrap_or(0);
+                let column = tree.column(symbol).unwrap_or(0);
+                tree.next_states[parent][column] = Some(index);
+            }
+        }
+        for sequence in positive {
+            if let Some(&index) = indices.get(sequence) {
+                tree.final_states[index] = true;
+            }
+        }
+
+        tree
+    }
+
+    fn column(&self, symbol: char) -> Option<usize> {
+        self.alphabet.binary_search(&symbol).ok()
+    }
+
+    fn accepts(&self, sequence: &str) -> bool {
+        let mut state = 0;
+        for symbol in sequence.chars() {
+            state = match self
+                .column(symbol)
+                .and_then(|column| self.next_states[state][column])
+            {
+                Some(next_state) => next_state,
+                None => return false,
+            };
+        }
+
+        self.final_states[state]
+    }
+
+    // returns the smallest successor of a red state that is not red itself
+    fn first_blue(&self, red: &[usize]) -> Option<usize> {
+        red.iter()
+            .flat_map(|&state| self.next_states[state].iter().flatten())
+            .filter(|state| !red.contains(state))
+            .min()
+            .cloned()
+    }
+
+    // redirects the transition into the blue state to the red state,
+    // then folds the subtree of the blue state into the red state
+    fn merge(&self, red: usize, blue: usize) -> Self {
+        let mut tree = self.clone();
+        for next_states in tree.next_states.iter_mut() {
+            for next_state in next_states.iter_mut() {
+                if *next_state == Some(blue) {
+                    *next_state = Some(red);
+                }
+            }
+        }
+
+        tree.fold(red, blue);
+        tree
+    }
+
+    fn fold(&mut self, state: usize, other: usize) {
+        if self.final_states[other] {
+            self.final_states[state] = true;
+        }
+
+        for column in 0..self.alphabet.len() {
+            let other_next = match self.next_states[other][column] {
+                Some(other_next) => other_next,
+                None => continue,
+            };
+
+            match self.next_states[state][column] {
+                Some(next_state) => self.fold(next_state, other_next),
+                None => self.next_states[state][column] = Some(other_next),
+            }
+        }
+    }
+
+    // keeps the states that are still reachable, numbered in order
+    fn to_automaton(&self) -> Automaton<State> {
+        let mut numbers: HashMap<usize, State> = HashMap::new();
+        let mut order = vec![0];
+        numbers.insert(0, 0);
+
+        let mut index = 0;
+        while index < order.len() {
+            for next_state in self.next_states[order[index]].iter().flatten() {
+                if !numbers.contains_key(next_state) {
+                    numbers.insert(*next_state, order.len());
+                    order.push(*next_state);
+                }
+            }
+            index += 1;
+        }
+
+        let mut final_states = Set::new();
+        let mut transitions = HashMap::new();
+        for (number, state) in order.iter().enumerate() {
+            if self.final_states[*state] {
+                final_states.insert(number);
+            }
+            for (column, next_state) in self.next_states[*state].iter().enumerate() {
+                if let Some(next_state) = next_state {
+                    let next_number = numbers.get(next_state).cloned().unwrap_or(0);
+                    transitions.insert((number, self.alphabet[column]), next_number);
+                }
+            }
+        }
+
+        // the parts are consistent by construction
+        Automaton::from_parts(
+            self.alphabet.iter().cloned().collect(),
+            (0..order.len()).collect(),
+            0,
+            final_states,
+            transitions,
+        )
+        .expect("the learned automaton is consistent")
+    }
+}
+
+// the rows are access sequences, the columns are suffixes, and every cell
+// says whether the teacher accepts the row followed by the column
+struct ObservationTable {
+    alphabet: Vec<char>,
+    prefixes: Vec<String>,
+    suffixes: Vec<String>,
+    memberships: HashMap<String, bool>,
+}
+
+impl ObservationTable {
+    fn new(alphabet: Vec<char>) -> Self {
+        Self {
+            alphabet,
+            prefixes: vec![String::new()],
+            suffixes: vec![String::new()],
+            memberships: HashMap::new(),
+        }
+    }
+
+    fn row<T: Teacher>(&mut self, prefix: &str, teacher: &T) -> Vec<bool> {
+        let mut row = vec![];
+        for index in 0..self.suffixes.len() {
+            let sequence = format!("{}{}", prefix, self.suffixes[index]);
+            let member = match self.memberships.get(&sequence) {
+                Some(&member) => member,
+                None => {
+                    let member = teacher.is_member(&sequence);
+                    self.memberships.insert(sequence, member);
+                    member
+                }
+            };
+            row.push(member);
+        }
+
+        row
+    }
+
+    // adds rows until every successor of a row looks like an existing row
+    fn close<T: Teacher>(&mut self, teacher: &T) {
+        loop {
+            let prefixes = self.prefixes.clone();
+            let rows: Vec<Vec<bool>> = prefixes
+                .iter()
+                .map(|prefix| self.row(prefix, teacher))
+                .collect();
+
+            let mut missing = None;
+            'search: for prefix in &prefixes {
+                for symbol in self.alphabet.clone() {
+                    let successor = format!("{}{}", prefix, symbol);
+                    if !rows.contains(&self.row(&successor, teacher)) {
+                        missing = Some(successor);
+                        break 'search;
+                    }
+                }
+            }
+
+            match missing {
+                Some(successor) => self.prefixes.push(successor),
+                None => break,
+            }
+        }
+    }
+
+    // every distinct row is a state, reached by the first prefix that has it
+    fn to_automaton(&self) -> Automaton<State> {
+        let mut states: Vec<Vec<bool>> = vec![];
+        let mut numbers: HashMap<String, State> = HashMap::new();
+        for prefix in &self.prefixes {
+            let row = self.cached_row(prefix);
+            let number = match states.iter().position(|other| *other == row) {
+                Some(number) => number,
+                None => {
+                    states.push(row);
+                    states.len() - 1
+                }
+            };
+            numbers.insert(prefix.clone(), number);
+        }
+
+        let mut final_states = Set::new();
+        let mut transitions = HashMap::new();
+        for prefix in &self.prefixes {
+            let number = numbers.get(prefix).cloned().unwrap_or(0);
+            // the first column is the empty suffix
+            if states[number][0] {
+                final_states.insert(number);
+            }
+
+            for symbol in &self.alphabet {
+                let row = self.cached_row(&format!("{}{}", prefix, symbol));
+                if let Some(next_number) = states.iter().position(|other| *other == row) {
+                    transitions.insert((number, *symbol), next_number);
+                }
+            }
+        }
+
+        // the parts are consistent by construction
+        Automaton::from_parts(
+            self.alphabet.iter().cloned().collect(),
+            (0..states.len()).collect(),
+            0,
+            final_states,
+            transitions,
+        )
+        .expect("the hypothesis is consistent")
+    }
+
+    // the table is closed, so every row it needs was already asked for
+    fn cached_row(&self, prefix: &str) -> Vec<bool> {
+        self.suffixes
+            .iter()
+            .map(|suffix| {
+                let sequence = format!("{}{}", prefix, suffix);
+                self.memberships.get(&sequence).cloned().unwrap_or(false)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,209 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use super::automaton::Automaton;
+use super::state::{State, StateLabel};
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // returns the automaton with the fewest states that accepts the same language;
+    // states are numbered in breadth-first order from the initial state, which is 0,
+    // so equivalent automata minimize to identical ones
+    pub fn minimize(&self) -> Automaton<State> {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+
+        // only the reachable states matter; the last index is a dead state
+        // that completes the transition function
+        let mut states: Vec<S> = self.reachable_states().into_iter().collect();
+        states.sort();
+        let dead_state = states.len();
+
+        let mut indices: HashMap<S, usize> = HashMap::new();
+        for (index, state) in states.iter().enumerate() {
+            indices.insert(state.clone(), index);
+        }
+
+        let mut next_states = vec![vec![dead_state; alphabet.len()]; states.len() + 1];
+        for (index, state) in states.iter().enumerate() {
+            for (column, symbol) in alphabet.iter().enumerate() {
+                if let Some(next_state) = self.get_transitions().get(&(state.clone(), *symbol)) {
+                    next_states[index][column] =
+                        indices.get(next_state).cloned().unwrap_or(dead_state);
+                }
+            }
+        }
+
+        let mut is_final = vec![false; states.len() + 1];
+        for (index, state) in states.iter().enumerate() {
+            is_final[index] = self.get_final_states().contains(state);
+        }
+
+        // Moore's partition refinement: split the blocks until the states of every block
+        // go to the same blocks on every symbol
+        let mut blocks: Vec<usize> = is_final.iter().map(|&is_final| is_final as usize).collect();
+        let mut block_count = 0;
+        loop {
+            let mut signatures: HashMap<Vec<usize>, usize> = HashMap::new();
+            let mut next_blocks = vec![0; blocks.len()];
+
+            for state in 0..blocks.len() {
+                let mut signature = vec![blocks[state]];
+                signature.extend(
+                    next_states[state]
+                        .iter()
+                        .map(|&next_state| blocks[next_state]),
+                );
+
+                next_blocks[state] = match signatures.get(&signature) {
+                    Some(&block) => block,
+                    None => {
+                        let block = signatures.len();
+                        signatures.insert(signature, block);
+                        block
+                    }
+                };
+            }
+
+            blocks = next_blocks;
+            if signatures.len() == block_count {
+                break;
+            }
+            block_count = signatures.len();
+        }
+
+        // number the blocks in breadth-first order, leaving out the block of the dead state
+        let dead_block = blocks[dead_state];
+        let initial_block = match indices.get(self.get_initial_state()) {
+            Some(&index) => blocks[index],
+            None => dead_block,
+        };
+
+        let mut numbers: HashMap<usize, State> = HashMap::new();
+        numbers.insert(initial_block, 0);
+        let mut queue = VecDeque::from([initial_block]);
+
+        let mut minimal_states = Set::from([0]);
+        let mut final_states = Set::new();
+        let mut transitions = HashMap::new();
+
+        while let Some(block) = queue.pop_front() {
+            let number = numbers.get(&block).cloned().unwrap_or(0);
+            if block == dead_block {
+                continue;
+            }
+
+            // any state of the block will do, they are equivalent
+            let state = match blocks.iter().position(|&other| other == block) {
+                Some(state) => state,
+                None => continue,
+            };
+            if is_final[state] {
+                final_states.insert(number);
+            }
+
+            for (column, symbol) in alphabet.iter().enumerate() {
+                let next_block = blocks[next_states[state][column]];
+                if next_block == dead_block {
+                    continue;
+                }
+
+                let next_number = match numbers.get(&next_block) {
+                    Some(&next_number) => next_number,
+                    None => {
+                        let next_number = numbers.len();
+                        numbers.insert(next_block, next_number);
+                        minimal_states.insert(next_number);
+                        queue.push_back(next_block);
+                        next_number
+                    }
+                };
+
+                transitions.insert((number, *symbol), next_number);
+            }
+        }
+
+        // the parts are consistent by construction
+        Automaton::from_parts(
+            self.get_alphabet().clone(),
+            minimal_states,
+            0,
+            final_states,
+            transitions,
+        )
+        .expect("the minimal automaton is consistent")
+    }
+
+    // returns the shortest sequence, in shortlex order, that exactly one of the automata accepts
+    pub fn distinguishing_sequence<T>(&self, other: &Automaton<T>) -> Option<String>
+    where
+        T: StateLabel,
+    {
+        let mut alphabet: Vec<char> = self
+            .get_alphabet()
+            .union(other.get_alphabet())
+            .cloned()
+            .collect();
+        alphabet.sort();
+
+        // a pair holds the current state of both automata, or None once it rejected
+        let initial = (
+            Some(self.get_initial_state().clone()),
+            Some(other.get_initial_state().clone()),
+        );
+        let mut visited = Set::from([initial.clone()]);
+        let mut queue = VecDeque::from([(initial, String::new())]);
+
+        while let Some(((state, other_state), sequence)) = queue.pop_front() {
+            let accepted = match &state {
+                Some(state) => self.get_final_states().contains(state),
+                None => false,
+            };
+            let other_accepted = match &other_state {
+                Some(other_state) => other.get_final_states().contains(other_state),
+                None => false,
+            };
+            if accepted != other_accepted {
+                return Some(sequence);
+            }
+
+            for symbol in &alphabet {
+                let next_state = match &state {
+                    Some(state) => self.step(state, *symbol).cloned(),
+                    None => None,
+                };
+                let other_next_state = match &other_state {
+                    Some(other_state) => other.step(other_state, *symbol).cloned(),
+                    None => None,
+                };
+
+                // both rejected, so nothing after this can tell them apart
+                if next_state.is_none() && other_next_state.is_none() {
+                    continue;
+                }
+
+                let next = (next_state, other_next_state);
+                if visited.insert(next.clone()) {
+                    let mut next_sequence = sequence.clone();
+                    next_sequence.push(*symbol);
+                    queue.push_back((next, next_sequence));
+                }
+            }
+        }
+
+        None
+    }
+
+    // returns true if both automata accept the same language
+    // (library api, the menu reports the distinguishing sequence instead)
+    #[allow(dead_code)]
+    pub fn is_equivalent<T>(&self, other: &Automaton<T>) -> bool
+    where
+        T: StateLabel,
+    {
+        self.distinguishing_sequence(other).is_none()
+    }
+}
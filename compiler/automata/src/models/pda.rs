@@ -0,0 +1,641 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines, Read};
+
+use super::automaton::{parse_state, read_lines, tokens, Source};
+use super::error::{AutomatonError, UNNAMED_SOURCE};
+use super::state::{State, StateLabel};
+
+// the token for "nothing": a transition that reads no input, or pushes no symbols
+pub const EMPTY: &str = r"\e";
+
+// the number of header lines (input alphabet, stack alphabet, states,
+// initial state, initial stack symbol, final states)
+pub const PDA_HEADER_LINES: usize = 6;
+
+// how many configurations a run may visit before giving up, since
+// ε-transitions that keep pushing can go on forever
+const MAX_CONFIGURATIONS: usize = 100_000;
+
+// when a pushdown automaton accepts its input
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Acceptance {
+    // the whole input was read and the automaton is in a final state
+    FinalState,
+    // the whole input was read and the stack is empty
+    EmptyStack,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PdaTransition<S = State> {
+    pub from: S,
+    // the input symbol that is read, None for an ε-transition
+    pub input: Option<String>,
+    // the symbol popped from the top of the stack
+    pub pop: String,
+    pub to: S,
+    // the symbols pushed in its place, the first one ends up on top
+    pub push: Vec<String>,
+}
+
+// a nondeterministic pushdown automaton; the input symbols are whole tokens
+// (i.e. "while" or "Identifier"), not characters, so it can run on the output of the scanner
+pub struct Pda<S = State> {
+    input_alphabet: Set<String>,
+    stack_alphabet: Set<String>,
+    states: Set<S>,
+    initial_state: S,
+    initial_stack_symbol: String,
+    final_states: Set<S>,
+    transitions: Vec<PdaTransition<S>>,
+
+    // the indices of the transitions for every (state, top of the stack)
+    moves: HashMap<(S, String), Vec<usize>>,
+}
+
+impl<S> Pda<S>
+where
+    S: StateLabel,
+{
+    pub fn new(file_path: &str) -> Result<Self, AutomatonError> {
+        let file = match File::open(file_path) {
+            Ok(file) => file,
+            Err(e) => {
+                let error = AutomatonError::Io {
+                    path: String::from(file_path),
+                    message: format!("could not open file: {}", e),
+                };
+                return Err(error);
+            }
+        };
+
+        match Self::from_reader(file) {
+            Ok(pda) => Ok(pda),
+            Err(e) => Err(e.with_path(file_path)),
+        }
+    }
+
+    // reads the pushdown automaton from any source in the .pda format
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, AutomatonError> {
+        let mut lines = BufReader::new(reader).lines();
+        Self::from_lines(UNNAMED_SOURCE, &mut lines)
+    }
+
+    // builds a pushdown automaton directly from its components
+    pub fn from_parts(
+        input_alphabet: Set<String>,
+        stack_alphabet: Set<String>,
+        states: Set<S>,
+        initial_state: S,
+        initial_stack_symbol: String,
+        final_states: Set<S>,
+        transitions: Vec<PdaTransition<S>>,
+    ) -> Result<Self, AutomatonError> {
+        // check if the components are consistent with each other
+        if !states.contains(&initial_state) {
+            let message = format!("initial state '{}' not in set of states", initial_state);
+            return Err(AutomatonError::InconsistentParts { message });
+        }
+        if !stack_alphabet.contains(&initial_stack_symbol) {
+            let message = format!(
+                "initial stack symbol '{}' missing from stack alphabet",
+                initial_stack_symbol
+            );
+            return Err(AutomatonError::InconsistentParts { message });
+        }
+        for final_state in &final_states {
+            if !states.contains(final_state) {
+                let message = format!("final state '{}' not in set of states", final_state);
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+        }
+
+        let mut moves: HashMap<(S, String), Vec<usize>> = HashMap::new();
+        for (index, transition) in transitions.iter().enumerate() {
+            if !states.contains(&transition.from) || !states.contains(&transition.to) {
+                let message = format!(
+                    "transition from '{}' to '{}' uses a state that is not in the set of states",
+                    transition.from, transition.to
+                );
+                return Err(AutomatonError::InconsistentParts { message });
+            }
+            if let Some(symbol) = &transition.input {
+                if !input_alphabet.contains(symbol) {
+                    let message = format!("symbol '{}' missing from input alphabet", symbol);
+                    return Err(AutomatonError::InconsistentParts { message });
+                }
+            }
+            for symbol in std::iter::once(&transition.pop).chain(&transition.push) {
+                if !stack_alphabet.contains(symbol) {
+                    let message = format!("symbol '{}' missing from stack alphabet", symbol);
+                    return Err(AutomatonError::InconsistentParts { message });
+                }
+            }
+
+            let key = (transition.from.clone(), transition.pop.clone());
+            let mut indices = moves.get(&key).cloned().unwrap_or_default();
+            indices.push(index);
+            moves.insert(key, indices);
+        }
+
+        Ok(Self {
+            input_alphabet,
+            stack_alphabet,
+            states,
+            initial_state,
+            initial_stack_symbol,
+            final_states,
+            transitions,
+            moves,
+        })
+    }
+
+    fn from_lines<B: BufRead>(path: &str, lines: &mut Lines<B>) -> Result<Self, AutomatonError> {
+        // read every line first, so that errors can quote them
+        let source_lines = match read_lines(path, lines) {
+            Ok(source_lines) => source_lines,
+            Err(e) => return Err(e),
+        };
+
+        let source = Source {
+            path,
+            lines: &source_lines,
+        };
+
+        let mut parser = PdaParser {
+            source: &source,
+            input_alphabet: Set::new(),
+            stack_alphabet: Set::new(),
+            states: Set::new(),
+        };
+        parser.parse()
+    }
+
+    pub fn get_input_alphabet(&self) -> &Set<String> {
+        &self.input_alphabet
+    }
+
+    pub fn get_stack_alphabet(&self) -> &Set<String> {
+        &self.stack_alphabet
+    }
+
+    pub fn get_states(&self) -> &Set<S> {
+        &self.states
+    }
+
+    pub fn get_initial_state(&self) -> &S {
+        &self.initial_state
+    }
+
+    pub fn get_initial_stack_symbol(&self) -> &String {
+        &self.initial_stack_symbol
+    }
+
+    pub fn get_final_states(&self) -> &Set<S> {
+        &self.final_states
+    }
+
+    pub fn get_transitions(&self) -> &Vec<PdaTransition<S>> {
+        &self.transitions
+    }
+
+    // explores every run on the input breadth-first; a configuration is the current state,
+    // the number of symbols read and the stack, and every configuration is visited once,
+    // so ε-cycles that leave the stack unchanged end, but ε-transitions that keep pushing
+    // (i.e. a left-recursive grammar) only stop at the configuration limit
+    pub fn accepts(&self, input: &[&str], acceptance: Acceptance) -> Result<bool, String> {
+        let mut stacks = Stacks::new();
+        let initial_stack = stacks.push(EMPTY_STACK, &self.initial_stack_symbol);
+
+        let initial = (self.initial_state.clone(), 0, initial_stack);
+        let mut visited = Set::from([initial.clone()]);
+        let mut queue = VecDeque::from([initial]);
+
+        while let Some((state, position, stack)) = queue.pop_front() {
+            let accepted = match acceptance {
+                Acceptance::FinalState => self.final_states.contains(&state),
+                Acceptance::EmptyStack => stack == EMPTY_STACK,
+            };
+            if position == input.len() && accepted {
+                return Ok(true);
+            }
+
+            // every transition pops, so nothing can happen on an empty stack
+            let (top, rest) = match stacks.pop(stack) {
+                Some(top) => top,
+                None => continue,
+            };
+            let moves = match self.moves.get(&(state.clone(), top)) {
+                Some(moves) => moves,
+                None => continue,
+            };
+
+            for &index in moves {
+                let transition = &self.transitions[index];
+                let next_position = match &transition.input {
+                    None => position,
+                    Some(symbol) if input.get(position).copied() == Some(symbol.as_str()) => {
+                        position + 1
+                    }
+                    Some(_) => continue,
+                };
+
+                // the first pushed symbol ends up on top, so push them in reverse
+                let mut next_stack = rest;
+                for symbol in transition.push.iter().rev() {
+                    next_stack = stacks.push(next_stack, symbol);
+                }
+
+                let next = (transition.to.clone(), next_position, next_stack);
+                if visited.insert(next.clone()) {
+                    if visited.len() > MAX_CONFIGURATIONS {
+                        let error = format!(
+                            "gave up after {} configurations, the automaton may loop on ε-transitions",
+                            MAX_CONFIGURATIONS
+                        );
+                        return Err(error);
+                    }
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+// the stacks of a run, shared between configurations: every stack is a node
+// that holds its top symbol and the stack below it, and equal stacks get the
+// same node, so a configuration only keeps the index of its stack
+struct Stacks {
+    nodes: Vec<(String, usize)>,
+    indices: HashMap<(String, usize), usize>,
+}
+
+// the index of the empty stack
+const EMPTY_STACK: usize = 0;
+
+impl Stacks {
+    fn new() -> Self {
+        Self {
+            nodes: vec![(String::new(), EMPTY_STACK)],
+            indices: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, stack: usize, symbol: &str) -> usize {
+        let node = (String::from(symbol), stack);
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.indices.insert(node, index);
+        index
+    }
+
+    // returns the top symbol and the stack below it
+    fn pop(&self, stack: usize) -> Option<(String, usize)> {
+        match stack {
+            EMPTY_STACK => None,
+            _ => self.nodes.get(stack).cloned(),
+        }
+    }
+}
+
+// reads the lines of a .pda file into the parts of a pushdown automaton
+struct PdaParser<'a, S> {
+    source: &'a Source<'a>,
+    input_alphabet: Set<String>,
+    stack_alphabet: Set<String>,
+    states: Set<S>,
+}
+
+impl<'a, S> PdaParser<'a, S>
+where
+    S: StateLabel,
+{
+    fn parse(&mut self) -> Result<Pda<S>, AutomatonError> {
+        // parse input alphabet
+        self.input_alphabet = match self.parse_alphabet(0) {
+            Ok(input_alphabet) => input_alphabet,
+            Err(e) => return Err(e),
+        };
+
+        // parse stack alphabet
+        self.stack_alphabet = match self.parse_alphabet(1) {
+            Ok(stack_alphabet) => stack_alphabet,
+            Err(e) => return Err(e),
+        };
+
+        // parse states
+        self.states = match self.parse_states(2) {
+            Ok(states) => states,
+            Err(e) => return Err(e),
+        };
+
+        // parse initial state
+        let (column, token) = match self.single_token(3) {
+            Ok(token) => token,
+            Err(e) => return Err(e),
+        };
+        let initial_state = match self.parse_defined_state(3, column, token) {
+            Ok(initial_state) => initial_state,
+            Err(e) => return Err(e),
+        };
+
+        // parse initial stack symbol
+        let (column, token) = match self.single_token(4) {
+            Ok(token) => token,
+            Err(e) => return Err(e),
+        };
+        let initial_stack_symbol = match self.parse_stack_symbol(4, column, token) {
+            Ok(initial_stack_symbol) => initial_stack_symbol,
+            Err(e) => return Err(e),
+        };
+
+        // parse final states, there may be none when accepting by empty stack
+        let final_states = match self.parse_final_states(5) {
+            Ok(final_states) => final_states,
+            Err(e) => return Err(e),
+        };
+
+        // parse transitions
+        let transitions = match self.parse_transitions(PDA_HEADER_LINES) {
+            Ok(transitions) => transitions,
+            Err(e) => return Err(e),
+        };
+
+        Pda::from_parts(
+            self.input_alphabet.clone(),
+            self.stack_alphabet.clone(),
+            self.states.clone(),
+            initial_state,
+            initial_stack_symbol,
+            final_states,
+            transitions,
+        )
+    }
+
+    fn parse_alphabet(&self, index: usize) -> Result<Set<String>, AutomatonError> {
+        // check if alphabet is missing
+        let alphabet = match self.source.line(index) {
+            Some(alphabet) => alphabet,
+            None => {
+                let location = self.source.at(index, 1);
+                return Err(match index {
+                    0 => AutomatonError::MissingAlphabet { location },
+                    _ => AutomatonError::MissingStackAlphabet { location },
+                });
+            }
+        };
+
+        let mut symbols = Set::new();
+        for (column, token) in tokens(alphabet) {
+            // the empty token cannot be a symbol
+            if token == EMPTY {
+                let error = AutomatonError::InvalidSymbol {
+                    location: self.source.at(index, column),
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+
+            // insert symbol if it doesn't already exist
+            if !symbols.insert(String::from(token)) {
+                let error = AutomatonError::DuplicateSymbol {
+                    location: self.source.at(index, column),
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+        }
+
+        Ok(symbols)
+    }
+
+    fn parse_states(&self, index: usize) -> Result<Set<S>, AutomatonError> {
+        // check if set of states is missing
+        let states = match self.source.line(index) {
+            Some(states) => states,
+            None => {
+                let location = self.source.at(index, 1);
+                return Err(AutomatonError::MissingStates { location });
+            }
+        };
+
+        let mut result = Set::new();
+        for (column, token) in tokens(states) {
+            let state = match parse_state::<S>(self.source, index, column, token) {
+                Ok(state) => state,
+                Err(e) => return Err(e),
+            };
+
+            // insert state if it doesn't already exist
+            if !result.insert(state) {
+                let error = AutomatonError::DuplicateState {
+                    location: self.source.at(index, column),
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_final_states(&self, index: usize) -> Result<Set<S>, AutomatonError> {
+        // check if final states are missing
+        let final_states = match self.source.line(index) {
+            Some(final_states) => final_states,
+            None => {
+                let location = self.source.at(index, 1);
+                return Err(AutomatonError::MissingFinalStates { location });
+            }
+        };
+
+        let mut result = Set::new();
+        for (column, token) in tokens(final_states) {
+            let final_state = match self.parse_defined_state(index, column, token) {
+                Ok(final_state) => final_state,
+                Err(e) => return Err(e),
+            };
+
+            // insert final state if it doesn't already exist
+            if !result.insert(final_state) {
+                let error = AutomatonError::DuplicateFinalState {
+                    location: self.source.at(index, column),
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+        }
+
+        Ok(result)
+    }
+
+    // returns the only token of a header line, i.e. the initial state
+    fn single_token(&self, index: usize) -> Result<(usize, &'a str), AutomatonError> {
+        let line = match self.source.line(index) {
+            Some(line) => tokens(line),
+            None => vec![],
+        };
+
+        match line.as_slice() {
+            [token] => Ok(*token),
+            [] => {
+                let location = self.source.at(index, 1);
+                Err(match index {
+                    3 => AutomatonError::MissingInitialState { location },
+                    _ => AutomatonError::MissingInitialStackSymbol { location },
+                })
+            }
+            [_, (column, token), ..] => {
+                let error = AutomatonError::InvalidState {
+                    location: self.source.at(index, *column),
+                    token: String::from(*token),
+                    reason: String::from("expected a single token on this line"),
+                };
+                Err(error)
+            }
+        }
+    }
+
+    fn parse_transitions(&self, start: usize) -> Result<Vec<PdaTransition<S>>, AutomatonError> {
+        let mut transitions: Vec<PdaTransition<S>> = vec![];
+
+        for index in start..self.source.lines.len() {
+            let line = &self.source.lines[index];
+
+            // skip empty lines
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // check if transition is invalid: state input pop state push...
+            let parts = tokens(line);
+            if parts.len() < 5 {
+                let error = AutomatonError::MalformedPdaTransition {
+                    location: self.source.at(index, parts[0].0),
+                    token: String::from(line.trim()),
+                };
+                return Err(error);
+            }
+
+            let (column, token) = parts[0];
+            let from = match self.parse_defined_state(index, column, token) {
+                Ok(from) => from,
+                Err(e) => return Err(e),
+            };
+
+            // parse input symbol, \e reads nothing
+            let (column, token) = parts[1];
+            let input = match token {
+                EMPTY => None,
+                _ if self.input_alphabet.contains(token) => Some(String::from(token)),
+                _ => {
+                    let error = AutomatonError::SymbolNotInAlphabet {
+                        location: self.source.at(index, column),
+                        token: String::from(token),
+                    };
+                    return Err(error);
+                }
+            };
+
+            let (column, token) = parts[2];
+            let pop = match self.parse_stack_symbol(index, column, token) {
+                Ok(pop) => pop,
+                Err(e) => return Err(e),
+            };
+
+            let (column, token) = parts[3];
+            let to = match self.parse_defined_state(index, column, token) {
+                Ok(to) => to,
+                Err(e) => return Err(e),
+            };
+
+            // parse pushed symbols, a single \e pushes nothing
+            let mut push = vec![];
+            if !matches!(&parts[4..], [(_, EMPTY)]) {
+                for &(column, token) in &parts[4..] {
+                    match self.parse_stack_symbol(index, column, token) {
+                        Ok(symbol) => push.push(symbol),
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+
+            let transition = PdaTransition {
+                from,
+                input,
+                pop,
+                to,
+                push,
+            };
+
+            // a nondeterministic automaton may have several transitions for the same
+            // state, input and top of the stack, but not the same one twice
+            if transitions.contains(&transition) {
+                let error = AutomatonError::DuplicateTransition {
+                    location: self.source.at(index, parts[0].0),
+                    token: String::from(line.trim()),
+                };
+                return Err(error);
+            }
+
+            transitions.push(transition);
+        }
+
+        // check if there are no transitions
+        if transitions.is_empty() {
+            let location = self.source.at(self.source.lines.len(), 1);
+            return Err(AutomatonError::MissingTransitions { location });
+        }
+
+        Ok(transitions)
+    }
+
+    // parses a state and checks that it is in the set of states
+    fn parse_defined_state(
+        &self,
+        index: usize,
+        column: usize,
+        token: &str,
+    ) -> Result<S, AutomatonError> {
+        let state = match parse_state::<S>(self.source, index, column, token) {
+            Ok(state) => state,
+            Err(e) => return Err(e),
+        };
+
+        if !self.states.contains(&state) {
+            let error = AutomatonError::UndefinedState {
+                location: self.source.at(index, column),
+                token: String::from(token),
+            };
+            return Err(error);
+        }
+
+        Ok(state)
+    }
+
+    fn parse_stack_symbol(
+        &self,
+        index: usize,
+        column: usize,
+        token: &str,
+    ) -> Result<String, AutomatonError> {
+        if !self.stack_alphabet.contains(token) {
+            let error = AutomatonError::SymbolNotInStackAlphabet {
+                location: self.source.at(index, column),
+                token: String::from(token),
+            };
+            return Err(error);
+        }
+
+        Ok(String::from(token))
+    }
+}
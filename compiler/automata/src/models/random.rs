@@ -0,0 +1,203 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use super::automaton::Automaton;
+use super::state::{State, StateLabel};
+
+// the symbols of random alphabets, in order; it includes whitespace and
+// non-ASCII characters, which the .dfa format has to escape
+pub const SYMBOLS: &str = "ab0 1\t\u{a0}xyz+-_'\"\\éλ€23456789cdefghijklmnopqrstuvw";
+
+// a small, seedable pseudo-random number generator (xorshift64*), so that
+// every generated case can be reproduced from its seed
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        // the state must never be zero
+        Self {
+            state: (seed ^ 0x9e37_79b9_7f4a_7c15) | 1,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // returns a number in 0..bound, or 0 if the bound is 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            return 0;
+        }
+
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    // returns true with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let fraction = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        fraction < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        match items.is_empty() {
+            true => None,
+            false => items.get(self.below(items.len())),
+        }
+    }
+}
+
+// the shape of the random automata
+pub struct RandomConfig {
+    pub state_count: usize,
+    pub alphabet_size: usize,
+    // the probability that a state has a transition on a symbol
+    pub transition_density: f64,
+    // the probability that a state is final
+    pub final_density: f64,
+}
+
+impl RandomConfig {
+    pub fn new(state_count: usize, alphabet_size: usize) -> Self {
+        Self {
+            state_count,
+            alphabet_size,
+            transition_density: 0.7,
+            final_density: 0.3,
+        }
+    }
+}
+
+// returns a valid automaton with states 0..state_count, 0 being the initial state;
+// it always has at least one transition, since the .dfa format requires it
+pub fn random_automaton(config: &RandomConfig, random: &mut Random) -> Automaton<State> {
+    let state_count = config.state_count.max(1);
+    let alphabet: Vec<char> = SYMBOLS.chars().take(config.alphabet_size.max(1)).collect();
+
+    let states: Set<State> = (0..state_count).collect();
+    let final_states: Set<State> = (0..state_count)
+        .filter(|_| random.chance(config.final_density))
+        .collect();
+
+    let mut transitions = HashMap::new();
+    for state in 0..state_count {
+        for symbol in &alphabet {
+            if random.chance(config.transition_density) {
+                transitions.insert((state, *symbol), random.below(state_count));
+            }
+        }
+    }
+    if transitions.len() == 0 {
+        transitions.insert((0, alphabet[0]), random.below(state_count));
+    }
+
+    // the parts are consistent by construction
+    Automaton::from_parts(
+        alphabet.into_iter().collect(),
+        states,
+        0,
+        final_states,
+        transitions,
+    )
+    .expect("the random automaton is consistent")
+}
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // returns a random accepted sequence: a random walk of at most max_length symbols
+    // through states that can still reach a final state, followed by the shortest way
+    // to a final state; returns None if the automaton accepts nothing
+    pub fn random_accepted(&self, max_length: usize, random: &mut Random) -> Option<String> {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+
+        // the symbol to take from every coreachable state to get closer to a final state
+        let mut toward_final: HashMap<S, Option<char>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for state in self.get_final_states() {
+            toward_final.insert(state.clone(), None);
+            queue.push_back(state.clone());
+        }
+        while let Some(state) = queue.pop_front() {
+            for ((start_state, symbol), end_state) in self.get_transitions() {
+                if *end_state == state && !toward_final.contains_key(start_state) {
+                    toward_final.insert(start_state.clone(), Some(*symbol));
+                    queue.push_back(start_state.clone());
+                }
+            }
+        }
+
+        let mut current_state = self.get_initial_state();
+        if !toward_final.contains_key(current_state) {
+            return None;
+        }
+
+        let mut sequence = String::new();
+        let length = random.below(max_length + 1);
+        for _ in 0..length {
+            let choices: Vec<(char, &S)> = alphabet
+                .iter()
+                .filter_map(|symbol| match self.step(current_state, *symbol) {
+                    Some(next_state) if toward_final.contains_key(next_state) => {
+                        Some((*symbol, next_state))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            let (symbol, next_state) = match random.choose(&choices) {
+                Some(choice) => *choice,
+                None => break,
+            };
+            sequence.push(symbol);
+            current_state = next_state;
+        }
+
+        // finish on the shortest path to a final state
+        while let Some(Some(symbol)) = toward_final.get(current_state) {
+            sequence.push(*symbol);
+            current_state = match self.step(current_state, *symbol) {
+                Some(next_state) => next_state,
+                None => break,
+            };
+        }
+
+        Some(sequence)
+    }
+
+    // returns a random rejected sequence of at most max_length symbols, mostly from
+    // the alphabet; returns None if none was found after a few attempts
+    pub fn random_rejected(&self, max_length: usize, random: &mut Random) -> Option<String> {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        // a symbol outside of the alphabet, which is always rejected
+        alphabet.push('\u{0}');
+
+        for _ in 0..100 {
+            let length = random.below(max_length + 1);
+            let mut sequence = String::new();
+            for _ in 0..length {
+                // keep unknown symbols rare, otherwise most sequences are trivially rejected
+                let symbol = match random.chance(0.05) {
+                    true => alphabet[alphabet.len() - 1],
+                    false => alphabet[random.below(alphabet.len() - 1)],
+                };
+                sequence.push(symbol);
+            }
+
+            if !self.validate(&sequence) {
+                return Some(sequence);
+            }
+        }
+
+        None
+    }
+}
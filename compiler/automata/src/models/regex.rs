@@ -0,0 +1,400 @@
+use hash_map::HashMap;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use super::automaton::Automaton;
+use super::error::AutomatonError;
+use super::state::State;
+
+// the characters that "." and negated classes stand for: printable ASCII,
+// tab, newline and carriage return ("." leaves out the newline)
+const PRINTABLE: std::ops::RangeInclusive<char> = ' '..='~';
+const CONTROL: [char; 3] = ['\t', '\n', '\r'];
+
+// a regular expression is turned into an automaton in two steps: it is parsed straight
+// into a nondeterministic automaton with ε-transitions (Thompson's construction),
+// which is then made deterministic (subset construction) and minimized
+//
+// the syntax: "ab" concatenation, "a|b" alternation, "a*", "a+" and "a?" repetition,
+// "(a)" grouping, "." any character but a newline, "[a-z_]" and "[^']" classes,
+// "\n", "\t", "\r" and "\s" (space) escapes, and "\" before any other character
+// to take it literally, i.e. "\+" or "\["
+impl Automaton<State> {
+    pub fn from_regex(pattern: &str) -> Result<Self, AutomatonError> {
+        let mut parser = RegexParser {
+            pattern,
+            chars: pattern.chars().collect(),
+            position: 0,
+            nfa: Nfa { states: vec![] },
+        };
+
+        let (start, end) = match parser.parse_alternation() {
+            Ok(fragment) => fragment,
+            Err(e) => return Err(e),
+        };
+
+        // an alternation only stops early on an unmatched parenthesis
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+
+        Ok(parser.nfa.to_automaton(start, end).minimize())
+    }
+}
+
+// the nondeterministic automaton, every state lists its transitions,
+// None being an ε-transition
+struct Nfa {
+    states: Vec<Vec<(Option<char>, usize)>>,
+}
+
+// the start and end state of a part of the expression
+type Fragment = (usize, usize);
+
+impl Nfa {
+    fn add_state(&mut self) -> usize {
+        self.states.push(vec![]);
+        self.states.len() - 1
+    }
+
+    fn add_transition(&mut self, start: usize, symbol: Option<char>, end: usize) {
+        self.states[start].push((symbol, end));
+    }
+
+    // a fragment that reads any one of the characters
+    fn symbols(&mut self, symbols: &[char]) -> Fragment {
+        let (start, end) = (self.add_state(), self.add_state());
+        for symbol in symbols {
+            self.add_transition(start, Some(*symbol), end);
+        }
+
+        (start, end)
+    }
+
+    // every state reachable through ε-transitions, sorted
+    fn closure(&self, states: &[usize]) -> Vec<usize> {
+        let mut closure: Set<usize> = states.iter().cloned().collect();
+        let mut stack: Vec<usize> = states.to_vec();
+        while let Some(state) = stack.pop() {
+            for (symbol, next_state) in &self.states[state] {
+                if symbol.is_none() && closure.insert(*next_state) {
+                    stack.push(*next_state);
+                }
+            }
+        }
+
+        let mut closure: Vec<usize> = closure.into_iter().collect();
+        closure.sort();
+        closure
+    }
+
+    // the subset construction: every state of the automaton is the set of states
+    // the nondeterministic automaton can be in, numbered in breadth-first order
+    fn to_automaton(&self, start: usize, end: usize) -> Automaton<State> {
+        let initial = self.closure(&[start]);
+
+        let mut numbers: HashMap<Vec<usize>, State> = HashMap::new();
+        numbers.insert(initial.clone(), 0);
+        let mut queue = VecDeque::from([initial]);
+
+        let mut alphabet = Set::new();
+        let mut states = Set::new();
+        let mut final_states = Set::new();
+        let mut transitions = HashMap::new();
+
+        while let Some(subset) = queue.pop_front() {
+            let number = numbers.get(&subset).cloned().unwrap_or(0);
+            states.insert(number);
+            if subset.contains(&end) {
+                final_states.insert(number);
+            }
+
+            // where every symbol leads from any state of the subset
+            let mut symbols: Vec<char> = vec![];
+            let mut targets: HashMap<char, Vec<usize>> = HashMap::new();
+            for state in &subset {
+                for (symbol, next_state) in &self.states[*state] {
+                    let symbol = match symbol {
+                        Some(symbol) => *symbol,
+                        None => continue,
+                    };
+
+                    let mut next_states = match targets.get(&symbol) {
+                        Some(next_states) => next_states.clone(),
+                        None => {
+                            symbols.push(symbol);
+                            vec![]
+                        }
+                    };
+                    next_states.push(*next_state);
+                    targets.insert(symbol, next_states);
+                }
+            }
+            symbols.sort();
+
+            for symbol in symbols {
+                let next_states = targets.get(&symbol).cloned().unwrap_or_default();
+                let next_subset = self.closure(&next_states);
+
+                let next_number = match numbers.get(&next_subset) {
+                    Some(&next_number) => next_number,
+                    None => {
+                        let next_number = numbers.len();
+                        numbers.insert(next_subset.clone(), next_number);
+                        queue.push_back(next_subset);
+                        next_number
+                    }
+                };
+
+                alphabet.insert(symbol);
+                transitions.insert((number, symbol), next_number);
+            }
+        }
+
+        // the parts are consistent by construction
+        Automaton::from_parts(alphabet, states, 0, final_states, transitions)
+            .expect("the automaton of the regex is consistent")
+    }
+}
+
+struct RegexParser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    position: usize,
+    nfa: Nfa,
+}
+
+impl<'a> RegexParser<'a> {
+    fn current(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn error(&self, message: &str) -> AutomatonError {
+        AutomatonError::InvalidRegex {
+            pattern: String::from(self.pattern),
+            column: self.position + 1,
+            message: String::from(message),
+        }
+    }
+
+    // alternation ::= concatenation {"|" concatenation}
+    fn parse_alternation(&mut self) -> Result<Fragment, AutomatonError> {
+        let mut alternatives = vec![];
+        loop {
+            match self.parse_concatenation() {
+                Ok(fragment) => alternatives.push(fragment),
+                Err(e) => return Err(e),
+            }
+
+            match self.current() {
+                Some('|') => self.position += 1,
+                _ => break,
+            }
+        }
+
+        if alternatives.len() == 1 {
+            return Ok(alternatives[0]);
+        }
+
+        let (start, end) = (self.nfa.add_state(), self.nfa.add_state());
+        for (alternative_start, alternative_end) in alternatives {
+            self.nfa.add_transition(start, None, alternative_start);
+            self.nfa.add_transition(alternative_end, None, end);
+        }
+
+        Ok((start, end))
+    }
+
+    // concatenation ::= {repetition}
+    fn parse_concatenation(&mut self) -> Result<Fragment, AutomatonError> {
+        let start = self.nfa.add_state();
+        let mut end = start;
+
+        while let Some(current) = self.current() {
+            if current == '|' || current == ')' {
+                break;
+            }
+
+            let (next_start, next_end) = match self.parse_repetition() {
+                Ok(fragment) => fragment,
+                Err(e) => return Err(e),
+            };
+            self.nfa.add_transition(end, None, next_start);
+            end = next_end;
+        }
+
+        Ok((start, end))
+    }
+
+    // repetition ::= atom {"*" | "+" | "?"}
+    fn parse_repetition(&mut self) -> Result<Fragment, AutomatonError> {
+        let (mut start, mut end) = match self.parse_atom() {
+            Ok(fragment) => fragment,
+            Err(e) => return Err(e),
+        };
+
+        while let Some(operator) = self.current() {
+            if operator != '*' && operator != '+' && operator != '?' {
+                break;
+            }
+            self.position += 1;
+
+            let (new_start, new_end) = (self.nfa.add_state(), self.nfa.add_state());
+            self.nfa.add_transition(new_start, None, start);
+            self.nfa.add_transition(end, None, new_end);
+            // repeat
+            if operator != '?' {
+                self.nfa.add_transition(end, None, start);
+            }
+            // skip
+            if operator != '+' {
+                self.nfa.add_transition(new_start, None, new_end);
+            }
+
+            start = new_start;
+            end = new_end;
+        }
+
+        Ok((start, end))
+    }
+
+    // atom ::= "(" alternation ")" | "[" class "]" | "." | symbol
+    fn parse_atom(&mut self) -> Result<Fragment, AutomatonError> {
+        let current = match self.current() {
+            Some(current) => current,
+            None => return Err(self.error("unexpected end of pattern")),
+        };
+
+        match current {
+            '(' => {
+                self.position += 1;
+                let fragment = match self.parse_alternation() {
+                    Ok(fragment) => fragment,
+                    Err(e) => return Err(e),
+                };
+
+                if self.current() != Some(')') {
+                    return Err(self.error("missing ')'"));
+                }
+                self.position += 1;
+
+                Ok(fragment)
+            }
+            '[' => {
+                self.position += 1;
+                let symbols = match self.parse_class() {
+                    Ok(symbols) => symbols,
+                    Err(e) => return Err(e),
+                };
+
+                Ok(self.nfa.symbols(&symbols))
+            }
+            '.' => {
+                self.position += 1;
+                let symbols: Vec<char> = universe().into_iter().filter(|c| *c != '\n').collect();
+
+                Ok(self.nfa.symbols(&symbols))
+            }
+            '*' | '+' | '?' => Err(self.error("nothing to repeat")),
+            _ => {
+                let symbol = match self.parse_symbol() {
+                    Ok(symbol) => symbol,
+                    Err(e) => return Err(e),
+                };
+
+                Ok(self.nfa.symbols(&[symbol]))
+            }
+        }
+    }
+
+    // class ::= ["^"] {symbol ["-" symbol]} "]", after the opening bracket
+    fn parse_class(&mut self) -> Result<Vec<char>, AutomatonError> {
+        let negated = self.current() == Some('^');
+        if negated {
+            self.position += 1;
+        }
+
+        let mut symbols = Set::new();
+        loop {
+            match self.current() {
+                Some(']') => {
+                    self.position += 1;
+                    break;
+                }
+                Some(_) => (),
+                None => return Err(self.error("missing ']'")),
+            }
+
+            let first = match self.parse_symbol() {
+                Ok(first) => first,
+                Err(e) => return Err(e),
+            };
+
+            // a range, unless the "-" is the last character of the class
+            let is_range = self.current() == Some('-')
+                && self.chars.get(self.position + 1).is_some_and(|c| *c != ']');
+            if !is_range {
+                symbols.insert(first);
+                continue;
+            }
+            self.position += 1;
+
+            let last = match self.parse_symbol() {
+                Ok(last) => last,
+                Err(e) => return Err(e),
+            };
+            if last < first {
+                return Err(self.error("range out of order"));
+            }
+            symbols.extend(first..=last);
+        }
+
+        let mut symbols: Vec<char> = match negated {
+            true => universe()
+                .into_iter()
+                .filter(|c| !symbols.contains(c))
+                .collect(),
+            false => symbols.into_iter().collect(),
+        };
+        symbols.sort();
+
+        Ok(symbols)
+    }
+
+    // a single character, which may be escaped
+    fn parse_symbol(&mut self) -> Result<char, AutomatonError> {
+        let current = match self.current() {
+            Some(current) => current,
+            None => return Err(self.error("unexpected end of pattern")),
+        };
+        self.position += 1;
+
+        if current != '\\' {
+            return Ok(current);
+        }
+
+        let escaped = match self.current() {
+            Some(escaped) => escaped,
+            None => return Err(self.error("unfinished escape")),
+        };
+        self.position += 1;
+
+        let symbol = match escaped {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            's' => ' ',
+            _ if escaped.is_alphanumeric() => {
+                self.position -= 1;
+                return Err(self.error("unknown escape"));
+            }
+            _ => escaped,
+        };
+
+        Ok(symbol)
+    }
+}
+
+fn universe() -> Vec<char> {
+    CONTROL.iter().cloned().chain(PRINTABLE).collect()
+}
@@ -0,0 +1,269 @@
+use hash_map::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet as Set;
+use std::io::Write;
+
+use super::automaton::{format_symbol, Automaton};
+use super::error::AutomatonError;
+use super::state::StateLabel;
+
+// the version of the JSON schema, bumped on every incompatible change
+pub const JSON_VERSION: u32 = 1;
+const JSON_FORMAT: &str = "automaton";
+
+// the JSON representation of an automaton; states are written with their labels,
+// symbols as one-character strings, and every list is sorted, i.e.
+//
+// {
+//   "format": "automaton",
+//   "version": 1,
+//   "alphabet": ["a", "b"],
+//   "states": ["0", "1"],
+//   "initial_state": "0",
+//   "final_states": ["1"],
+//   "transitions": [{ "from": "0", "symbol": "a", "to": "1" }]
+// }
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AutomatonJson {
+    format: String,
+    version: u32,
+    alphabet: Vec<String>,
+    states: Vec<String>,
+    initial_state: String,
+    final_states: Vec<String>,
+    transitions: Vec<TransitionJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransitionJson {
+    from: String,
+    symbol: String,
+    to: String,
+}
+
+impl<S> Automaton<S>
+where
+    S: StateLabel,
+{
+    // writes the automaton in the canonical .dfa format: everything is sorted,
+    // symbols are escaped, and the transitions of every state are grouped together
+    pub fn write_dfa(&self, writer: &mut impl Write) -> Result<(), String> {
+        match writer.write_all(self.to_dfa_string().as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let error = format!("could not write automaton: {}", e);
+                Err(error)
+            }
+        }
+    }
+
+    pub fn to_dfa_string(&self) -> String {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+        let alphabet: Vec<String> = alphabet.into_iter().map(format_symbol).collect();
+
+        let states = sorted_labels(self.get_states());
+        let final_states = sorted_labels(self.get_final_states());
+
+        let mut output = String::new();
+        output.push_str(&format!("{}\n", alphabet.join(" ")));
+        output.push_str(&format!("{}\n", states.join(" ")));
+        output.push_str(&format!("{}\n", self.get_initial_state()));
+        output.push_str(&format!("{}\n", final_states.join(" ")));
+
+        // one group per start state, separated by empty lines
+        let mut previous_state: Option<&S> = None;
+        for ((start_state, symbol), end_state) in self.sorted_transitions() {
+            if previous_state.is_some() && previous_state != Some(start_state) {
+                output.push('\n');
+            }
+            previous_state = Some(start_state);
+
+            output.push_str(&format!(
+                "{} {} {}\n",
+                start_state,
+                format_symbol(*symbol),
+                end_state
+            ));
+        }
+
+        output
+    }
+
+    pub fn write_json(&self, writer: &mut impl Write) -> Result<(), String> {
+        match writer.write_all(self.to_json_string().as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let error = format!("could not write automaton: {}", e);
+                Err(error)
+            }
+        }
+    }
+
+    pub fn to_json_string(&self) -> String {
+        let mut alphabet: Vec<char> = self.get_alphabet().iter().cloned().collect();
+        alphabet.sort();
+
+        let json = AutomatonJson {
+            format: String::from(JSON_FORMAT),
+            version: JSON_VERSION,
+            alphabet: alphabet.iter().map(|symbol| symbol.to_string()).collect(),
+            states: sorted_labels(self.get_states()),
+            initial_state: self.get_initial_state().to_string(),
+            final_states: sorted_labels(self.get_final_states()),
+            transitions: self
+                .sorted_transitions()
+                .into_iter()
+                .map(|((start_state, symbol), end_state)| TransitionJson {
+                    from: start_state.to_string(),
+                    symbol: symbol.to_string(),
+                    to: end_state.to_string(),
+                })
+                .collect(),
+        };
+
+        // the schema only has strings, numbers and lists, so this cannot fail
+        let mut output = serde_json::to_string_pretty(&json).unwrap_or_default();
+        output.push('\n');
+        output
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, AutomatonError> {
+        let json: AutomatonJson = match serde_json::from_str(source) {
+            Ok(json) => json,
+            Err(e) => return Err(invalid_json(e.to_string())),
+        };
+
+        if json.format != JSON_FORMAT {
+            return Err(invalid_json(format!("unknown format '{}'", json.format)));
+        }
+        if json.version != JSON_VERSION {
+            return Err(invalid_json(format!(
+                "unsupported version {}, expected: {}",
+                json.version, JSON_VERSION
+            )));
+        }
+
+        let mut alphabet = Set::new();
+        for symbol in &json.alphabet {
+            let symbol = match json_symbol(symbol) {
+                Ok(symbol) => symbol,
+                Err(e) => return Err(e),
+            };
+            if !alphabet.insert(symbol) {
+                return Err(invalid_json(format!(
+                    "duplicate symbol '{}' in alphabet",
+                    symbol
+                )));
+            }
+        }
+
+        let mut states = Set::new();
+        for state in &json.states {
+            let state = match json_state::<S>(state) {
+                Ok(state) => state,
+                Err(e) => return Err(e),
+            };
+            if let Some(duplicate) = states.replace(state) {
+                return Err(invalid_json(format!("duplicate state '{}'", duplicate)));
+            }
+        }
+
+        let initial_state = match json_state::<S>(&json.initial_state) {
+            Ok(initial_state) => initial_state,
+            Err(e) => return Err(e),
+        };
+
+        let mut final_states = Set::new();
+        for state in &json.final_states {
+            let state = match json_state::<S>(state) {
+                Ok(state) => state,
+                Err(e) => return Err(e),
+            };
+            if let Some(duplicate) = final_states.replace(state) {
+                return Err(invalid_json(format!(
+                    "duplicate final state '{}'",
+                    duplicate
+                )));
+            }
+        }
+
+        let mut transitions = HashMap::new();
+        for transition in &json.transitions {
+            let (start_state, symbol, end_state) = match (
+                json_state::<S>(&transition.from),
+                json_symbol(&transition.symbol),
+                json_state::<S>(&transition.to),
+            ) {
+                (Ok(start_state), Ok(symbol), Ok(end_state)) => (start_state, symbol, end_state),
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return Err(e),
+            };
+
+            let key = (start_state, symbol);
+            if transitions.contains_key(&key) {
+                return Err(invalid_json(format!(
+                    "duplicate transition from state '{}' on '{}'",
+                    key.0, key.1
+                )));
+            }
+            transitions.insert(key, end_state);
+        }
+
+        // the same rule as in .dfa files, so every automaton read from JSON can be written as one
+        if transitions.len() == 0 {
+            return Err(invalid_json(String::from("missing transitions")));
+        }
+
+        Automaton::from_parts(alphabet, states, initial_state, final_states, transitions)
+    }
+
+    fn sorted_transitions(&self) -> Vec<(&(S, char), &S)> {
+        let mut transitions: Vec<(&(S, char), &S)> = self.get_transitions().iter().collect();
+        transitions.sort();
+        transitions
+    }
+}
+
+fn sorted_labels<S>(states: &Set<S>) -> Vec<String>
+where
+    S: StateLabel,
+{
+    let mut states: Vec<&S> = states.iter().collect();
+    states.sort();
+    states.iter().map(|state| state.to_string()).collect()
+}
+
+fn json_symbol(symbol: &str) -> Result<char, AutomatonError> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(value), None) => Ok(value),
+        _ => Err(invalid_json(format!(
+            "symbol '{}' is not a single character",
+            symbol
+        ))),
+    }
+}
+
+fn json_state<S>(state: &str) -> Result<S, AutomatonError>
+where
+    S: StateLabel,
+{
+    // the label has to fit in a .dfa file as well
+    if state.is_empty() || state.chars().any(|c| c.is_whitespace()) {
+        return Err(invalid_json(format!(
+            "invalid state '{}': labels cannot be empty or contain whitespace",
+            state
+        )));
+    }
+
+    match state.parse::<S>() {
+        Ok(value) => Ok(value),
+        Err(e) => Err(invalid_json(format!("invalid state '{}': {}", state, e))),
+    }
+}
+
+fn invalid_json(message: String) -> AutomatonError {
+    AutomatonError::InvalidJson { message }
+}
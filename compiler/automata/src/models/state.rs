@@ -1,3 +1,7 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+
 pub type State = usize;
 
 pub trait NewState {
@@ -21,3 +25,12 @@ impl NewState for char {
         '0'
     }
 }
+
+// any type that can label the states of an automaton
+// i.e. it can be parsed from a .dfa file, printed, hashed and sorted
+pub trait StateLabel: NewState + Clone + Eq + Hash + Ord + Display + FromStr<Err: Display> {}
+
+impl<T> StateLabel for T where
+    T: NewState + Clone + Eq + Hash + Ord + Display + FromStr<Err: Display>
+{
+}
@@ -0,0 +1,355 @@
+use hash_map::HashMap;
+use lazy_static::lazy_static;
+use std::collections::HashSet as Set;
+use std::collections::VecDeque;
+
+use utils::{get_next_line, open_file};
+
+use super::automaton::{parse_symbol, tokens, Automaton, HEADER_LINES};
+use super::error::{AutomatonError, SourceLocation};
+use super::state::{State, StateLabel};
+
+// separates a state from its output in a .moore file, i.e. "state => output"
+const MOORE_OUTPUT: &str = "=>";
+
+lazy_static! {
+    static ref OUTPUT_ESCAPES: HashMap<&'static str, &'static str> = HashMap::from([
+        (r"\e", ""),   // empty output
+        (r"\s", " "),  // space
+        (r"\n", "\n"), // newline
+        (r"\t", "\t"), // tab
+    ]);
+}
+
+// an automaton that produces an output for every state it visits
+pub struct Moore<S = State, O = String> {
+    automaton: Automaton<S>,
+    outputs: HashMap<S, O>,
+}
+
+// an automaton that produces an output for every transition it takes
+pub struct Mealy<S = State> {
+    automaton: Automaton<S>,
+    outputs: HashMap<(S, char), String>,
+}
+
+impl<S> Moore<S, String>
+where
+    S: StateLabel,
+{
+    // reads a .dfa file extended with "state => output" lines after the header
+    pub fn new(file_path: &str) -> Result<Self, AutomatonError> {
+        let (automaton_lines, output_lines) = match split_file(file_path, |parts| {
+            parts.len() == 3 && parts[1] == MOORE_OUTPUT
+        }) {
+            Ok(lines) => lines,
+            Err(e) => return Err(e),
+        };
+
+        // the output lines were left blank, so the line numbers still match the file
+        let automaton = match Automaton::<S>::parse(automaton_lines.as_bytes()) {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.with_path(file_path)),
+        };
+
+        let mut outputs = HashMap::new();
+        for (index, line) in output_lines {
+            let (column, token) = tokens(&line)[0];
+            let location = SourceLocation::new(file_path, index + 1, column, &line);
+
+            // parse state
+            let state = match token.parse::<S>() {
+                Ok(state) => state,
+                Err(e) => {
+                    let error = AutomatonError::InvalidState {
+                        location,
+                        token: String::from(token),
+                        reason: e.to_string(),
+                    };
+                    return Err(error);
+                }
+            };
+            // check if state is in set of states
+            if !automaton.get_states().contains(&state) {
+                let error = AutomatonError::UndefinedState {
+                    location,
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+
+            // check if the state already has an output
+            if outputs.contains_key(&state) {
+                let error = AutomatonError::DuplicateOutput {
+                    location,
+                    token: String::from(token),
+                };
+                return Err(error);
+            }
+
+            outputs.insert(state, parse_output(tokens(&line)[2].1));
+        }
+
+        Ok(Self { automaton, outputs })
+    }
+}
+
+impl<S, O> Moore<S, O>
+where
+    S: StateLabel,
+{
+    pub fn get_automaton(&self) -> &Automaton<S> {
+        &self.automaton
+    }
+
+    pub fn get_output(&self, state: &S) -> Option<&O> {
+        self.outputs.get(state)
+    }
+
+    // returns the output of the state reached by an accepted sequence
+    pub fn classify(&self, sequence: &str) -> Option<&O> {
+        let mut current_state = self.automaton.get_initial_state();
+        for symbol in sequence.chars() {
+            current_state = match self.automaton.step(current_state, symbol) {
+                Some(next_state) => next_state,
+                None => return None,
+            };
+        }
+
+        if !self.automaton.get_final_states().contains(current_state) {
+            return None;
+        }
+
+        self.outputs.get(current_state)
+    }
+
+    // returns the outputs of every state visited by an accepted sequence,
+    // starting with the initial state and skipping the states without an output
+    pub fn translate(&self, sequence: &str) -> Option<Vec<&O>> {
+        let mut current_state = self.automaton.get_initial_state();
+        let mut result: Vec<&O> = self.outputs.get(current_state).into_iter().collect();
+
+        for symbol in sequence.chars() {
+            current_state = match self.automaton.step(current_state, symbol) {
+                Some(next_state) => next_state,
+                None => return None,
+            };
+
+            if let Some(output) = self.outputs.get(current_state) {
+                result.push(output);
+            }
+        }
+
+        if !self.automaton.get_final_states().contains(current_state) {
+            return None;
+        }
+
+        Some(result)
+    }
+}
+
+impl<O> Moore<State, O>
+where
+    O: Clone,
+{
+    // runs the given automata side by side as a single machine (product construction);
+    // a state is final if any of the automata accepts, and its output is the output
+    // of the first accepting automaton in the list, so the order sets the priority
+    pub fn combine<S>(machines: &[(O, &Automaton<S>)]) -> Result<Self, AutomatonError>
+    where
+        S: StateLabel,
+    {
+        let mut alphabet = Set::new();
+        for (_, automaton) in machines {
+            alphabet.extend(automaton.get_alphabet().iter().cloned());
+        }
+
+        let mut sorted_alphabet: Vec<char> = alphabet.iter().cloned().collect();
+        sorted_alphabet.sort();
+
+        // a product state holds the current state of every automaton, or None once it rejected
+        let initial: Vec<Option<S>> = machines
+            .iter()
+            .map(|(_, automaton)| Some(automaton.get_initial_state().clone()))
+            .collect();
+
+        let mut indices: HashMap<Vec<Option<S>>, State> = HashMap::new();
+        indices.insert(initial.clone(), 0);
+        let mut queue = VecDeque::from([(initial, 0)]);
+
+        let mut states = Set::new();
+        let mut final_states = Set::new();
+        let mut transitions = HashMap::new();
+        let mut outputs = HashMap::new();
+
+        while let Some((product, index)) = queue.pop_front() {
+            states.insert(index);
+
+            // the first accepting automaton decides the output
+            for ((output, automaton), state) in machines.iter().zip(&product) {
+                if let Some(state) = state {
+                    if automaton.get_final_states().contains(state) {
+                        final_states.insert(index);
+                        outputs.insert(index, output.clone());
+                        break;
+                    }
+                }
+            }
+
+            for symbol in &sorted_alphabet {
+                let next: Vec<Option<S>> = machines
+                    .iter()
+                    .zip(&product)
+                    .map(|((_, automaton), state)| match state {
+                        Some(state) => automaton.step(state, *symbol).cloned(),
+                        None => None,
+                    })
+                    .collect();
+
+                // every automaton rejected, so there is no transition
+                if next.iter().all(|state| state.is_none()) {
+                    continue;
+                }
+
+                let next_index = match indices.get(&next) {
+                    Some(&next_index) => next_index,
+                    None => {
+                        let next_index = indices.len();
+                        indices.insert(next.clone(), next_index);
+                        queue.push_back((next, next_index));
+                        next_index
+                    }
+                };
+
+                transitions.insert((index, *symbol), next_index);
+            }
+        }
+
+        let automaton = match Automaton::from_parts(alphabet, states, 0, final_states, transitions)
+        {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self { automaton, outputs })
+    }
+}
+
+impl<S> Mealy<S>
+where
+    S: StateLabel,
+{
+    // reads a .dfa file in which every transition can have an output as its fourth part
+    pub fn new(file_path: &str) -> Result<Self, AutomatonError> {
+        let (mut automaton_lines, output_lines) =
+            match split_file(file_path, |parts| parts.len() == 4) {
+                Ok(lines) => lines,
+                Err(e) => return Err(e),
+            };
+
+        // the transitions with outputs are also transitions of the automaton,
+        // so they are added back on the lines they came from
+        let mut lines: Vec<String> = automaton_lines.lines().map(String::from).collect();
+        for (index, line) in &output_lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            lines[*index] = parts[..3].join(" ");
+        }
+        automaton_lines = lines.join("\n");
+
+        let automaton = match Automaton::<S>::parse(automaton_lines.as_bytes()) {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.with_path(file_path)),
+        };
+
+        let mut outputs = HashMap::new();
+        for (_, line) in output_lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            // the automaton already checked the state and the symbol
+            let (start_state, symbol) = match (parts[0].parse::<S>(), parse_symbol(parts[1])) {
+                (Ok(start_state), Ok(symbol)) => (start_state, symbol),
+                _ => continue,
+            };
+
+            outputs.insert((start_state, symbol), parse_output(parts[3]));
+        }
+
+        Ok(Self { automaton, outputs })
+    }
+
+    // returns the concatenated outputs of the transitions taken by an accepted sequence
+    pub fn translate(&self, sequence: &str) -> Option<String> {
+        let mut result = String::new();
+        let mut current_state = self.automaton.get_initial_state();
+
+        for symbol in sequence.chars() {
+            if let Some(output) = self.outputs.get(&(current_state.clone(), symbol)) {
+                result.push_str(output);
+            }
+
+            current_state = match self.automaton.step(current_state, symbol) {
+                Some(next_state) => next_state,
+                None => return None,
+            };
+        }
+
+        if !self.automaton.get_final_states().contains(current_state) {
+            return None;
+        }
+
+        Some(result)
+    }
+}
+
+// an output as written in a transducer file, either an escape or the text itself
+fn parse_output(token: &str) -> String {
+    match OUTPUT_ESCAPES.get(&token) {
+        Some(&escape) => String::from(escape),
+        None => String::from(token),
+    }
+}
+
+// splits a transducer file into the lines of the underlying automaton
+// and the lines selected by `is_output`, which may only appear after the header;
+// the selected lines are left blank in the automaton, and kept with their index
+fn split_file<F>(
+    file_path: &str,
+    is_output: F,
+) -> Result<(String, Vec<(usize, String)>), AutomatonError>
+where
+    F: Fn(&Vec<&str>) -> bool,
+{
+    let io_error = |message: String| AutomatonError::Io {
+        path: String::from(file_path),
+        message,
+    };
+
+    let mut lines = match open_file(file_path) {
+        Ok(lines) => lines,
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let mut automaton_lines = String::new();
+    let mut output_lines = vec![];
+    let mut index = 0;
+
+    loop {
+        let line = match get_next_line(&mut lines) {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => return Err(io_error(e)),
+        };
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if index >= HEADER_LINES && is_output(&parts) {
+            output_lines.push((index, line));
+        } else {
+            automaton_lines.push_str(&line);
+        }
+        automaton_lines.push('\n');
+
+        index += 1;
+    }
+
+    Ok((automaton_lines, output_lines))
+}
@@ -1 +1,18 @@
+mod analysis;
 mod automaton;
+mod builder;
+mod codegen;
+mod compiled;
+mod error;
+mod helpers;
+mod learning;
+mod minimize;
+mod pda;
+mod random;
+mod regex;
+mod serialize;
+mod transducer;
+
+// generated by `cargo run codegen`, only used by the tests
+#[allow(dead_code)]
+mod generated;
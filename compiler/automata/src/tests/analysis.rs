@@ -0,0 +1,65 @@
+#[allow(unused_imports)]
+use std::collections::HashSet as Set;
+
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+
+#[test]
+fn test_finite() {
+    let finite = Automaton::new("input/finite.dfa").unwrap();
+
+    assert!(!finite.is_empty());
+    assert!(finite.is_finite());
+
+    assert_eq!(finite.count_accepted(0), 0);
+    assert_eq!(finite.count_accepted(1), 2);
+    assert_eq!(finite.count_accepted(10), 3);
+
+    assert_eq!(finite.enumerate(10), vec!["a", "b", "ab"]);
+    assert_eq!(finite.enumerate(2), vec!["a", "b"]);
+
+    assert_eq!(finite.shortest_accepted(), Some(String::from("a")));
+    assert_eq!(finite.shortest_rejected(), Some(String::from("")));
+}
+
+#[test]
+fn test_reachability() {
+    let finite = Automaton::new("input/finite.dfa").unwrap();
+
+    assert_eq!(finite.unreachable_states(), Set::from([4]));
+    assert_eq!(finite.dead_states(), Set::from([3]));
+    assert_eq!(finite.warnings().len(), 2);
+
+    for file in [
+        "identifier",
+        "number",
+        "hex_number",
+        "octal_number",
+        "binary_number",
+        "decimal_number",
+        "char",
+        "string",
+    ] {
+        let automaton = Automaton::new(&format!("input/{}.dfa", file)).unwrap();
+        assert!(automaton.warnings().is_empty());
+    }
+}
+
+#[test]
+fn test_infinite() {
+    let number = Automaton::new("input/number.dfa").unwrap();
+
+    assert!(!number.is_empty());
+    assert!(!number.is_finite());
+
+    assert_eq!(number.count_accepted(1), 10);
+    assert_eq!(number.enumerate(4), vec!["0", "1", "2", "3"]);
+    assert_eq!(number.enumerate(12)[10..], ["10", "11"]);
+
+    assert_eq!(number.shortest_accepted(), Some(String::from("0")));
+    assert_eq!(number.shortest_rejected(), Some(String::from("")));
+
+    let string = Automaton::new("input/string.dfa").unwrap();
+    assert_eq!(string.shortest_accepted(), Some(String::from(r#""""#)));
+    assert_eq!(string.enumerate(1), vec![r#""""#]);
+}
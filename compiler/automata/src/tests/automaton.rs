@@ -19,8 +19,8 @@ fn test_identifier() {
 
 #[test]
 fn test_number() {
-    let good_numbers = vec!["1", "10", "+12", "-12", "0"];
-    let bad_numbers = vec!["01", "+-10", "-+10", "+0", "-0", "+01", "-01", ""];
+    let good_numbers = vec!["1", "10", "0", "1_000", "1_2_3"];
+    let bad_numbers = vec!["01", "+12", "-12", "1_", "1__0", "_1", "0_1", ""];
 
     let number = Automaton::new("input/number.dfa").unwrap();
 
@@ -33,6 +33,67 @@ fn test_number() {
     }
 }
 
+#[test]
+fn test_prefixed_numbers() {
+    let numbers = vec![
+        (
+            "input/hex_number.dfa",
+            vec!["0x0", "0xff", "0xFF_FF", "0x00aB"],
+            vec!["0x", "0xg", "0x_f", "0xf_", "0Xf", "x1", "1x1", "0b1"],
+        ),
+        (
+            "input/octal_number.dfa",
+            vec!["0o0", "0o17", "0o7_7"],
+            vec!["0o", "0o8", "0o_1", "017", "0O1"],
+        ),
+        (
+            "input/binary_number.dfa",
+            vec!["0b0", "0b1010", "0b1111_0000"],
+            vec!["0b", "0b2", "0b1__0", "0b1_", "1b1", "0B1"],
+        ),
+    ];
+
+    for (file, good_numbers, bad_numbers) in numbers {
+        let number = Automaton::new(file).unwrap();
+
+        for value in good_numbers {
+            assert!(number.validate(value), "{} rejects {}", file, value);
+        }
+
+        for value in bad_numbers {
+            assert!(!number.validate(value), "{} accepts {}", file, value);
+        }
+    }
+}
+
+#[test]
+fn test_decimal_number() {
+    let good_numbers = vec![
+        "0.5",
+        "1.25",
+        "10.0",
+        "1_000.000_1",
+        "1e10",
+        "1E10",
+        "2.5e-3",
+        "0e+0",
+        "6.02e2_3",
+    ];
+    let bad_numbers = vec![
+        "1", "0", "1.", ".5", "01.5", "1._5", "1.5_", "1e", "1e+", "1.5e", "1e_1", "-1.5", "1.5.2",
+    ];
+
+    let decimal = Automaton::new("input/decimal_number.dfa").unwrap();
+
+    for value in good_numbers {
+        assert!(decimal.validate(value), "rejects {}", value);
+    }
+
+    for value in bad_numbers {
+        assert!(!decimal.validate(value), "accepts {}", value);
+    }
+}
+
 #[test]
 fn test_string() {
     let good_strings = vec![
@@ -40,6 +101,7 @@ fn test_string() {
         r#""a""#,
         r#""a \"b\" c""#,
         r#""a \\ b""#,
+        r#""it\'s\n\tok""#,
         r#""""#,
     ];
     let bad_strings = vec![
@@ -48,6 +110,7 @@ fn test_string() {
         r#""a \ b""#,
         r#""abc"#,
         r#"abc""#,
+        r#""a \q b""#,
     ];
 
     let string = Automaton::new("input/string.dfa").unwrap();
@@ -63,8 +126,10 @@ fn test_string() {
 
 #[test]
 fn test_char() {
-    let good_chars = vec!["'a'", "' '", r#"'\''"#, r#"'\\'"#];
-    let bad_chars = vec!["'ab'", "''", r#"'''"#, r#"'\'"#];
+    let good_chars = vec![
+        "'a'", "' '", r#"'\''"#, r#"'\\'"#, r#"'\"'"#, r#"'\n'"#, r#"'\t'"#,
+    ];
+    let bad_chars = vec!["'ab'", "''", r#"'''"#, r#"'\'"#, r#"'\q'"#];
 
     let char = Automaton::new("input/char.dfa").unwrap();
 
@@ -76,3 +141,23 @@ fn test_char() {
         assert!(!char.validate(value));
     }
 }
+
+#[test]
+fn test_named_states() {
+    let good_numbers = vec!["0", "1", "101", "+1", "-10"];
+    let bad_numbers = vec!["01", "+0", "-", "2", ""];
+
+    let binary = Automaton::<String>::open("input/binary.dfa").unwrap();
+    assert_eq!(binary.get_initial_state(), "q_start");
+
+    for value in good_numbers {
+        assert!(binary.validate(value));
+    }
+
+    for value in bad_numbers {
+        assert!(!binary.validate(value));
+    }
+
+    // named states cannot be parsed as numbers
+    assert!(Automaton::<usize>::open("input/binary.dfa").is_err());
+}
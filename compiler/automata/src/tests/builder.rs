@@ -0,0 +1,84 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::builder::AutomatonBuilder;
+
+#[test]
+fn test_build() {
+    let mut builder: AutomatonBuilder<String> = AutomatonBuilder::new();
+    let commands = vec![
+        "symbol a b",
+        "state even odd",
+        "initial even",
+        "final even",
+        "transition even a odd",
+        "transition odd a even",
+        "transition even b even",
+    ];
+    for command in commands {
+        builder.run_command(command).unwrap();
+    }
+
+    assert!(!builder.is_complete());
+    assert_eq!(
+        builder.missing_transitions(),
+        vec![(String::from("odd"), 'b')]
+    );
+
+    builder.run_command("transition odd b odd").unwrap();
+    assert!(builder.is_complete());
+
+    let automaton = builder.build().unwrap();
+    assert!(automaton.validate("abab"));
+    assert!(!automaton.validate("ab"));
+
+    // invalid commands do not change the draft
+    assert!(builder.run_command("state odd other").is_err());
+    assert!(!builder.get_states().contains("other"));
+    assert!(builder.run_command("transition even c odd").is_err());
+    assert!(builder.run_command("initial").is_err());
+}
+
+#[test]
+fn test_undo() {
+    let automaton = Automaton::new("input/finite.dfa").unwrap();
+    let mut builder = AutomatonBuilder::from_automaton(&automaton);
+    let transitions = automaton.get_transitions().len();
+
+    // removing a state also removes its transitions and marks, in a single step
+    let initial_state = *automaton.get_initial_state();
+    builder.remove_state(&initial_state).unwrap();
+    assert_eq!(builder.get_initial_state(), None);
+    assert!(builder.get_transitions().len() < transitions);
+    assert!(builder.build().is_err());
+
+    assert!(builder.undo());
+    assert_eq!(builder.get_initial_state(), Some(&initial_state));
+    assert_eq!(builder.get_transitions().len(), transitions);
+    assert!(!builder.undo());
+
+    let rebuilt = builder.build().unwrap();
+    assert_eq!(rebuilt.enumerate(100), automaton.enumerate(100));
+}
+
+#[test]
+fn test_save() {
+    let automaton = Automaton::<String>::open("input/binary.dfa").unwrap();
+    let builder = AutomatonBuilder::from_automaton(&automaton);
+
+    // a name of its own, so parallel test runs do not write over each other
+    let file_name = format!("automata-builder-save-{}.dfa", std::process::id());
+    let file_path = std::env::temp_dir().join(file_name);
+    let file_path = file_path.to_str().unwrap();
+    builder.save(file_path).unwrap();
+
+    let saved = Automaton::<String>::open(file_path).unwrap();
+    assert_eq!(saved.get_initial_state(), automaton.get_initial_state());
+    assert_eq!(saved.enumerate(50), automaton.enumerate(50));
+
+    // an empty draft cannot be saved
+    let empty: AutomatonBuilder = AutomatonBuilder::new();
+    assert!(empty.save(file_path).is_err());
+
+    std::fs::remove_file(file_path).unwrap();
+}
@@ -0,0 +1,59 @@
+#[allow(unused_imports)]
+use super::generated;
+#[allow(unused_imports)]
+use super::helpers::all_sequences;
+
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::codegen::generate_rust_module;
+
+#[allow(dead_code)]
+const AUTOMATA: [(&str, &str); 5] = [
+    ("input/identifier.dfa", "is_identifier"),
+    ("input/number.dfa", "is_number"),
+    ("input/char.dfa", "is_char"),
+    ("input/string.dfa", "is_string"),
+    ("input/binary.dfa", "is_binary"),
+];
+
+#[test]
+fn test_up_to_date() {
+    // regenerate with: cargo run codegen -o src/tests/generated.rs (<file.dfa> <function name>)...
+    let code = generate_rust_module(&AUTOMATA).unwrap();
+    assert_eq!(code, include_str!("generated.rs"));
+}
+
+// a generated function, and the length of the words it is checked up to
+#[allow(dead_code)]
+type Generated = (fn(&str) -> bool, usize);
+
+#[test]
+fn test_equivalence() {
+    let generated: [Generated; 5] = [
+        (generated::is_identifier, 3),
+        (generated::is_number, 3),
+        (generated::is_char, 3),
+        (generated::is_string, 2),
+        (generated::is_binary, 4),
+    ];
+
+    for ((file, _), (function, max_length)) in AUTOMATA.iter().zip(generated) {
+        let automaton = Automaton::<String>::open(file).unwrap();
+
+        // include characters from outside of the alphabet
+        let mut alphabet: Vec<char> = automaton.get_alphabet().iter().cloned().collect();
+        alphabet.push('\t');
+        alphabet.push('é');
+
+        for sequence in all_sequences(&alphabet, max_length) {
+            assert_eq!(
+                automaton.validate(&sequence),
+                function(&sequence),
+                "{} disagrees on {:?}",
+                file,
+                sequence
+            );
+        }
+    }
+}
@@ -0,0 +1,62 @@
+#[allow(unused_imports)]
+use super::helpers::all_sequences;
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+
+#[test]
+fn test_equivalence() {
+    let files = [("identifier", 3), ("number", 3), ("char", 3), ("string", 2)];
+
+    for (file, max_length) in files {
+        let automaton = Automaton::new(&format!("input/{}.dfa", file)).unwrap();
+        let compiled = automaton.compile();
+
+        // include characters from outside of the alphabet
+        let mut alphabet: Vec<char> = automaton.get_alphabet().iter().cloned().collect();
+        alphabet.push('\t');
+        alphabet.push('é');
+
+        for sequence in all_sequences(&alphabet, max_length) {
+            assert_eq!(
+                automaton.validate(&sequence),
+                compiled.validate(&sequence),
+                "{} disagrees on {:?}",
+                file,
+                sequence
+            );
+        }
+    }
+}
+
+#[test]
+fn test_classes() {
+    let identifier = Automaton::new("input/identifier.dfa").unwrap();
+    let compiled = identifier.compile();
+
+    // all letters behave the same, so they share a single class
+    assert_eq!(compiled.class_count(), 2);
+    assert_eq!(compiled.get_classes()[0].len(), 52);
+    assert_eq!(compiled.state_count(), 3);
+
+    let number = Automaton::new("input/number.dfa").unwrap();
+    let compiled = number.compile();
+
+    // unknown, zero, nonzero, separator
+    assert_eq!(compiled.class_count(), 4);
+    assert_eq!(compiled.get_classes()[0], vec!['0']);
+    assert_eq!(compiled.get_classes()[2], vec!['_']);
+}
+
+#[test]
+fn test_named_states() {
+    let binary = Automaton::<String>::open("input/binary.dfa").unwrap();
+    let compiled = binary.compile();
+
+    for value in ["0", "1", "101", "+1", "-10"] {
+        assert!(compiled.validate(value));
+    }
+
+    for value in ["01", "+0", "-", "2", "", "1é"] {
+        assert!(!compiled.validate(value));
+    }
+}
@@ -0,0 +1,91 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::error::AutomatonError;
+
+#[allow(dead_code)]
+fn parse(source: &str) -> AutomatonError {
+    match Automaton::<usize>::parse(source.as_bytes()) {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn test_header_errors() {
+    assert!(matches!(parse(""), AutomatonError::MissingAlphabet { .. }));
+    assert!(matches!(
+        parse("a b\n0 1\n0\n1\n"),
+        AutomatonError::MissingTransitions { .. }
+    ));
+    assert!(matches!(
+        parse("a ab\n"),
+        AutomatonError::InvalidSymbol { .. }
+    ));
+    assert!(matches!(
+        parse("a b a\n"),
+        AutomatonError::DuplicateSymbol { .. }
+    ));
+    assert!(matches!(
+        parse("a\n0 x\n"),
+        AutomatonError::InvalidState { .. }
+    ));
+    assert!(matches!(
+        parse("a\n0\n"),
+        AutomatonError::MissingInitialState { .. }
+    ));
+    assert!(matches!(
+        parse("a\n0\n1\n"),
+        AutomatonError::UndefinedState { .. }
+    ));
+    assert!(matches!(
+        parse("a\n0\n0\n0 0\n"),
+        AutomatonError::DuplicateFinalState { .. }
+    ));
+}
+
+#[test]
+fn test_locations() {
+    let error = parse("a b\n0 1 1\n0\n1\n0 a 1\n");
+    assert!(matches!(error, AutomatonError::DuplicateState { .. }));
+
+    let location = error.get_location().unwrap();
+    assert_eq!((location.line, location.column), (2, 5));
+    assert_eq!(location.path, "<input>");
+    assert_eq!(error.get_token(), Some("1"));
+
+    let error = parse("a b\n0 1\n0\n1\n0 a 1\n\n1   c 0\n");
+    assert!(matches!(error, AutomatonError::SymbolNotInAlphabet { .. }));
+
+    let location = error.get_location().unwrap();
+    assert_eq!((location.line, location.column), (7, 5));
+    assert_eq!(
+        error.to_string(),
+        "<input>:7:5: character 'c' missing from alphabet"
+    );
+    assert!(error.diagnostic().ends_with("7 | 1   c 0\n  |     ^"));
+
+    let error = parse("a b\n0 1\n0\n1\n0 a 1\n0 a 0\n");
+    assert!(matches!(error, AutomatonError::DuplicateTransition { .. }));
+    assert_eq!(error.get_location().unwrap().line, 6);
+
+    let error = parse("a b\n0 1\n0\n1\n0 a\n");
+    assert!(matches!(error, AutomatonError::MalformedTransition { .. }));
+}
+
+#[test]
+fn test_file_errors() {
+    let error = match Automaton::<usize>::open("input/missing.dfa") {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e,
+    };
+    assert!(matches!(error, AutomatonError::Io { .. }));
+
+    // named states cannot be parsed as numbers, the path comes from the file
+    let error = match Automaton::<usize>::open("input/binary.dfa") {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e,
+    };
+    assert!(matches!(error, AutomatonError::InvalidState { .. }));
+    assert_eq!(error.get_location().unwrap().path, "input/binary.dfa");
+}
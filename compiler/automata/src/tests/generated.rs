@@ -0,0 +1,78 @@
+// generated from .dfa files by the automata crate, do not edit by hand
+
+pub fn is_identifier(sequence: &str) -> bool {
+    let mut state: usize = 0;
+    for symbol in sequence.chars() {
+        state = match (state, symbol) {
+            (0, 'A'..='Z' | 'a'..='z') => 1,
+            (1, 'A'..='Z' | 'a'..='z') => 1,
+            _ => return false,
+        };
+    }
+
+    matches!(state, 1)
+}
+
+pub fn is_number(sequence: &str) -> bool {
+    let mut state: usize = 0;
+    for symbol in sequence.chars() {
+        state = match (state, symbol) {
+            (0, '0') => 1,
+            (0, '1'..='9') => 2,
+            (2, '0'..='9') => 2,
+            (2, '_') => 3,
+            (3, '0'..='9') => 2,
+            _ => return false,
+        };
+    }
+
+    matches!(state, 1 | 2)
+}
+
+pub fn is_char(sequence: &str) -> bool {
+    let mut state: usize = 0;
+    for symbol in sequence.chars() {
+        state = match (state, symbol) {
+            (0, '\'') => 1,
+            (1, ' '..='&' | '('..='[' | ']'..='~') => 3,
+            (1, '\\') => 2,
+            (2, '"' | '\'' | '\\' | 'n' | 't') => 3,
+            (3, '\'') => 4,
+            _ => return false,
+        };
+    }
+
+    matches!(state, 4)
+}
+
+pub fn is_string(sequence: &str) -> bool {
+    let mut state: usize = 0;
+    for symbol in sequence.chars() {
+        state = match (state, symbol) {
+            (0, '"') => 1,
+            (1, ' ' | '!' | '#'..='[' | ']'..='~') => 1,
+            (1, '"') => 3,
+            (1, '\\') => 2,
+            (2, '"' | '\'' | '\\' | 'n' | 't') => 1,
+            _ => return false,
+        };
+    }
+
+    matches!(state, 3)
+}
+
+pub fn is_binary(sequence: &str) -> bool {
+    let mut state: usize = 2;
+    for symbol in sequence.chars() {
+        state = match (state, symbol) {
+            (0, '1') => 1,
+            (1, '0' | '1') => 1,
+            (2, '+' | '-') => 0,
+            (2, '0') => 3,
+            (2, '1') => 1,
+            _ => return false,
+        };
+    }
+
+    matches!(state, 1 | 3)
+}
@@ -0,0 +1,22 @@
+// every sequence over the alphabet up to the given length, the empty one included
+#[allow(dead_code)]
+pub fn all_sequences(alphabet: &[char], max_length: usize) -> Vec<String> {
+    let mut sequences = vec![String::new()];
+    let mut previous = vec![String::new()];
+
+    for _ in 0..max_length {
+        let mut current = vec![];
+        for sequence in &previous {
+            for symbol in alphabet {
+                let mut next = sequence.clone();
+                next.push(*symbol);
+                current.push(next);
+            }
+        }
+
+        sequences.extend(current.iter().cloned());
+        previous = current;
+    }
+
+    sequences
+}
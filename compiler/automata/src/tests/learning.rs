@@ -0,0 +1,45 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::learning::{learn_lstar, learn_rpni, Samples, Teacher};
+
+#[test]
+fn test_rpni() {
+    let samples = Samples::new("input/even_a.samples").unwrap();
+    let learned = learn_rpni(&samples).unwrap();
+
+    for sequence in samples.get_positive() {
+        assert!(learned.validate(sequence));
+    }
+    for sequence in samples.get_negative() {
+        assert!(!learned.validate(sequence));
+    }
+
+    // the samples are enough to find the sequences with an even number of a's
+    assert!(learned.validate("bbaabb"));
+    assert!(!learned.validate("bbabb"));
+    assert_eq!(learned.minimize().get_states().len(), 2);
+}
+
+#[test]
+fn test_lstar() {
+    let files = vec![
+        "input/identifier.dfa",
+        "input/number.dfa",
+        "input/char.dfa",
+        "input/string.dfa",
+        "input/finite.dfa",
+    ];
+
+    for file in files {
+        let teacher = Automaton::new(file).unwrap();
+        let learned = learn_lstar(teacher.get_alphabet(), &teacher);
+
+        assert!(learned.is_equivalent(&teacher));
+        assert_eq!(teacher.find_counterexample(&learned), None);
+
+        // the learned automaton is minimal, apart from the dead state
+        let minimal = teacher.minimize();
+        assert!(learned.get_states().len() <= minimal.get_states().len() + 1);
+    }
+}
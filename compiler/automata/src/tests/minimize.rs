@@ -0,0 +1,49 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+
+#[test]
+fn test_minimize() {
+    let files = vec![
+        "input/identifier.dfa",
+        "input/number.dfa",
+        "input/char.dfa",
+        "input/string.dfa",
+        "input/finite.dfa",
+    ];
+
+    for file in files {
+        let automaton = Automaton::new(file).unwrap();
+        let minimal = automaton.minimize();
+
+        assert!(minimal.get_states().len() <= automaton.get_states().len());
+        assert!(minimal.is_equivalent(&automaton));
+        assert_eq!(minimal.enumerate(50), automaton.enumerate(50));
+
+        // minimizing again changes nothing
+        let again = minimal.minimize();
+        assert_eq!(again.get_states(), minimal.get_states());
+        assert_eq!(again.get_final_states(), minimal.get_final_states());
+    }
+
+    // the unreachable state 4 and the dead state 3 go away
+    let finite = Automaton::new("input/finite.dfa").unwrap();
+    assert_eq!(finite.minimize().get_states().len(), 3);
+}
+
+#[test]
+fn test_equivalence() {
+    let binary = Automaton::<String>::open("input/binary.dfa").unwrap();
+    let named = binary.map_states(|state| format!("renamed_{}", state));
+    assert!(binary.is_equivalent(&named));
+    assert!(binary.is_equivalent(&binary.minimize()));
+
+    let identifier = Automaton::new("input/identifier.dfa").unwrap();
+    let number = Automaton::new("input/number.dfa").unwrap();
+    assert!(!identifier.is_equivalent(&number));
+
+    // the shortest sequence that only one of them accepts
+    assert_eq!(
+        identifier.distinguishing_sequence(&number),
+        Some(String::from("0"))
+    );
+}
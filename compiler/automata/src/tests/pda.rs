@@ -0,0 +1,148 @@
+#[allow(unused_imports)]
+use crate::models::error::AutomatonError;
+#[allow(unused_imports)]
+use crate::models::pda::{Acceptance, Pda, PdaTransition};
+
+#[test]
+fn test_balanced() {
+    let pda: Pda<String> = Pda::new("input/balanced.pda").unwrap();
+
+    let accepted = ["", "( )", "( ( ) )", "( ) ( )", "( ( ) ( ) ) ( )"];
+    let rejected = ["(", ")", ") (", "( ( )", "( ) )", "( x )"];
+
+    // the automaton empties its stack exactly when it reaches the final state
+    for acceptance in [Acceptance::FinalState, Acceptance::EmptyStack] {
+        for sequence in accepted {
+            let input: Vec<&str> = sequence.split_whitespace().collect();
+            assert_eq!(pda.accepts(&input, acceptance), Ok(true), "{:?}", sequence);
+        }
+        for sequence in rejected {
+            let input: Vec<&str> = sequence.split_whitespace().collect();
+            assert_eq!(pda.accepts(&input, acceptance), Ok(false), "{:?}", sequence);
+        }
+    }
+}
+
+#[test]
+fn test_nondeterminism() {
+    // even-length palindromes over a and b: guess the middle with an ε-transition
+    let source = "\
+a b
+Z a b
+push pop done
+push
+Z
+done
+push a Z push a Z
+push b Z push b Z
+push a a push a a
+push a b push a b
+push b a push b a
+push b b push b b
+push \\e Z pop Z
+push \\e a pop a
+push \\e b pop b
+pop a a pop \\e
+pop b b pop \\e
+pop \\e Z done Z
+";
+    let pda: Pda<String> = Pda::from_reader(source.as_bytes()).unwrap();
+    assert_eq!(pda.get_transitions().len(), 12);
+
+    for sequence in [
+        vec![],
+        vec!["a", "a"],
+        vec!["a", "b", "b", "a"],
+        vec!["b", "a", "a", "b"],
+    ] {
+        assert_eq!(pda.accepts(&sequence, Acceptance::FinalState), Ok(true));
+    }
+    for sequence in [
+        vec!["a"],
+        vec!["a", "b"],
+        vec!["a", "b", "a"],
+        vec!["a", "a", "b", "b"],
+    ] {
+        assert_eq!(pda.accepts(&sequence, Acceptance::FinalState), Ok(false));
+    }
+
+    // the stack always keeps Z
+    assert_eq!(pda.accepts(&["a", "a"], Acceptance::EmptyStack), Ok(false));
+}
+
+#[test]
+fn test_endless_pushing() {
+    // an ε-loop that grows the stack, like a left-recursive grammar
+    let source = "a\nS a\n0\n0\nS\n\n0 \\e S 0 S a\n0 a a 0 \\e\n";
+    let pda: Pda = Pda::from_reader(source.as_bytes()).unwrap();
+
+    assert!(pda.accepts(&["b"], Acceptance::EmptyStack).is_err());
+}
+
+#[test]
+fn test_invalid_pda() {
+    let header = "a\nZ\n0 1\n0\nZ\n1\n";
+    let cases = [
+        ("a\n", "missing stack alphabet"),
+        ("a\nZ\n0\n0\n", "missing initial stack symbol"),
+        ("a\nZ\n0\n0\nZ\n", "missing final states"),
+        ("a \\e\nZ\n0\n0\nZ\n0\n0 a Z 0 Z\n", "invalid symbol '\\e'"),
+        (
+            "a\nZ\n0\n0\nY\n0\n0 a Z 0 Z\n",
+            "symbol 'Y' missing from stack alphabet",
+        ),
+        ("a\nZ\n0\n0\nZ\n\n", "missing transitions"),
+    ];
+    for (source, message) in cases {
+        let error = Pda::<usize>::from_reader(source.as_bytes()).err().unwrap();
+        assert_eq!(error.message(), message);
+    }
+
+    let transitions = [
+        (
+            "0 a Z 1",
+            "invalid transition '0 a Z 1', expected: state input pop state push...",
+        ),
+        ("0 b Z 1 Z", "character 'b' missing from alphabet"),
+        ("0 a Y 1 Z", "symbol 'Y' missing from stack alphabet"),
+        ("0 a Z 2 Z", "state '2' not in set of states"),
+        ("0 a Z 1 Z Y", "symbol 'Y' missing from stack alphabet"),
+        (
+            "0 a Z 1 \\e\n0 a Z 1 \\e",
+            "duplicate transition key for transition '0 a Z 1 \\e'",
+        ),
+    ];
+    for (transition, message) in transitions {
+        let source = format!("{}{}\n", header, transition);
+        let error = Pda::<usize>::from_reader(source.as_bytes()).err().unwrap();
+        assert_eq!(error.message(), message);
+        assert_eq!(
+            error.get_location().unwrap().line,
+            7 + transition.matches('\n').count()
+        );
+    }
+
+    // several transitions for the same state, input and top of the stack are fine
+    let source = format!("{}0 a Z 1 \\e\n0 a Z 0 Z\n", header);
+    assert!(Pda::<usize>::from_reader(source.as_bytes()).is_ok());
+
+    let transition = PdaTransition {
+        from: 0,
+        input: Some(String::from("b")),
+        pop: String::from("Z"),
+        to: 0,
+        push: vec![],
+    };
+    let error = Pda::from_parts(
+        ["a"].iter().map(|symbol| symbol.to_string()).collect(),
+        ["Z"].iter().map(|symbol| symbol.to_string()).collect(),
+        [0].into_iter().collect(),
+        0,
+        String::from("Z"),
+        [].into_iter().collect(),
+        vec![transition],
+    )
+    .err()
+    .unwrap();
+    assert!(matches!(error, AutomatonError::InconsistentParts { .. }));
+}
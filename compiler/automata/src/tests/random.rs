@@ -0,0 +1,56 @@
+#[allow(unused_imports)]
+use crate::models::builder::AutomatonBuilder;
+#[allow(unused_imports)]
+use crate::models::fuzz::fuzz;
+#[allow(unused_imports)]
+use crate::models::random::{random_automaton, Random, RandomConfig};
+
+#[test]
+fn test_random_automaton() {
+    let config = RandomConfig::new(6, 3);
+
+    // the same seed gives the same automaton
+    let first = random_automaton(&config, &mut Random::new(7));
+    let second = random_automaton(&config, &mut Random::new(7));
+    assert_eq!(
+        AutomatonBuilder::from_automaton(&first).to_dfa(),
+        AutomatonBuilder::from_automaton(&second).to_dfa()
+    );
+
+    assert_eq!(first.get_states().len(), 6);
+    assert_eq!(first.get_alphabet().len(), 3);
+    assert!(first.get_transitions().len() > 0);
+}
+
+#[test]
+fn test_random_sequences() {
+    let mut random = Random::new(42);
+    for _ in 0..50 {
+        let automaton = random_automaton(&RandomConfig::new(5, 2), &mut random);
+
+        for _ in 0..10 {
+            if let Some(sequence) = automaton.random_accepted(8, &mut random) {
+                assert!(automaton.validate(&sequence));
+            }
+            if let Some(sequence) = automaton.random_rejected(8, &mut random) {
+                assert!(!automaton.validate(&sequence));
+            }
+        }
+
+        // there is an accepted sequence exactly when the language is not empty
+        let accepted = automaton.random_accepted(8, &mut random);
+        assert_eq!(accepted.is_some(), !automaton.is_empty());
+    }
+}
+
+#[test]
+fn test_fuzz() {
+    for seed in 0..4 {
+        let report = match fuzz(250, seed) {
+            Ok(report) => report,
+            Err(e) => panic!("{}", e),
+        };
+        assert_eq!(report.automata, 250);
+        assert!(report.invalid_mutations > 0);
+    }
+}
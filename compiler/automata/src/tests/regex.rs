@@ -0,0 +1,132 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::error::AutomatonError;
+#[allow(unused_imports)]
+use crate::models::transducer::Moore;
+
+#[test]
+fn test_regex() {
+    let cases = [
+        ("abc", vec!["abc"], vec!["", "ab", "abcd"]),
+        ("a|bc|", vec!["a", "bc", ""], vec!["b", "abc"]),
+        (
+            "a(b|c)*d",
+            vec!["ad", "abd", "acbbd"],
+            vec!["a", "abc", "aed"],
+        ),
+        ("a+b?", vec!["a", "aab"], vec!["", "b", "abb"]),
+        ("[a-cx_]+", vec!["a", "cab_x"], vec!["", "d", "A"]),
+        (
+            "[+-]?[1-9][0-9]*|0",
+            vec!["0", "-10", "+7"],
+            vec!["-0", "01", "+"],
+        ),
+        (
+            "'[^'\\n]'",
+            vec!["'a'", "' '", "'\"'"],
+            vec!["'''", "'\n'", "'ab'"],
+        ),
+        ("\\(\\*\\s\\.", vec!["(* ."], vec!["(*x."]),
+        ("a.c", vec!["abc", "a c"], vec!["a\nc", "ac"]),
+        ("(a*)*b", vec!["b", "aab"], vec!["a"]),
+    ];
+
+    for (pattern, accepted, rejected) in cases {
+        let automaton = Automaton::from_regex(pattern).unwrap();
+        for sequence in accepted {
+            assert!(automaton.validate(sequence), "{:?} {:?}", pattern, sequence);
+        }
+        for sequence in rejected {
+            assert!(
+                !automaton.validate(sequence),
+                "{:?} {:?}",
+                pattern,
+                sequence
+            );
+        }
+    }
+
+    // the automaton is minimal, so equivalent patterns give the same automaton
+    let automaton = Automaton::from_regex("(a|b)*abb").unwrap();
+    let other = Automaton::from_regex("(b*a+b)*b*a+bb").unwrap();
+    assert_eq!(automaton.get_states().len(), 4);
+    assert_eq!(automaton.to_dfa_string(), other.to_dfa_string());
+
+    // the same language as the hand-written automaton
+    let identifier = Automaton::new("input/identifier.dfa").unwrap();
+    assert!(Automaton::from_regex("[A-Za-z]+")
+        .unwrap()
+        .is_equivalent(&identifier));
+}
+
+#[test]
+fn test_invalid_regex() {
+    let cases = [
+        ("(ab", 4, "missing ')'"),
+        ("ab)", 3, "unmatched ')'"),
+        ("[ab", 4, "missing ']'"),
+        ("[z-a]", 5, "range out of order"),
+        ("*a", 1, "nothing to repeat"),
+        ("a|+", 3, "nothing to repeat"),
+        ("ab\\", 4, "unfinished escape"),
+        ("\\d", 2, "unknown escape"),
+    ];
+
+    for (pattern, expected_column, expected_message) in cases {
+        match Automaton::from_regex(pattern) {
+            Err(AutomatonError::InvalidRegex {
+                column, message, ..
+            }) => {
+                assert_eq!(column, expected_column, "{:?}", pattern);
+                assert_eq!(message, expected_message, "{:?}", pattern);
+            }
+            _ => panic!("{:?} should be invalid", pattern),
+        }
+    }
+}
+
+#[test]
+fn test_longest_match() {
+    let patterns = [("if", "if"), ("[a-z]+", "identifier"), ("=|==", "operator")];
+    let automata: Vec<(&str, Automaton)> = patterns
+        .iter()
+        .map(|(pattern, tag)| (*tag, Automaton::from_regex(pattern).unwrap()))
+        .collect();
+    let machines: Vec<(&str, &Automaton)> = automata
+        .iter()
+        .map(|(tag, automaton)| (*tag, automaton))
+        .collect();
+    let lexer = Moore::combine(&machines).unwrap().compile();
+
+    let cases = [
+        ("if(", Some((2, "if"))),
+        ("iffy ", Some((4, "identifier"))),
+        ("==x", Some((2, "operator"))),
+        ("=x", Some((1, "operator"))),
+        ("(", None),
+        ("", None),
+    ];
+    for (input, expected) in cases {
+        let symbols: Vec<char> = input.chars().collect();
+        let result = lexer
+            .longest_match(&symbols)
+            .map(|(length, tag)| (length, *tag));
+        assert_eq!(result, expected, "{:?}", input);
+    }
+
+    // a match that reaches the end of the input may grow with more input
+    let cases = [
+        ("if", Some((2, "if")), false),
+        ("if(", Some((2, "if")), true),
+        ("=", Some((1, "operator")), false),
+        ("==", Some((2, "operator")), false),
+        ("==x", Some((2, "operator")), true),
+        ("", None, false),
+    ];
+    for (input, expected, stuck) in cases {
+        let (result, result_stuck) = lexer.longest_match_partial(input.chars());
+        let result = result.map(|(length, tag)| (length, *tag));
+        assert_eq!((result, result_stuck), (expected, stuck), "{:?}", input);
+    }
+}
@@ -0,0 +1,116 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::error::AutomatonError;
+
+#[test]
+fn test_write_dfa() {
+    let binary = Automaton::<String>::open("input/binary.dfa").unwrap();
+
+    let expected = "\
++ - 0 1
+after_sign digits q_start zero
+q_start
+digits zero
+after_sign 1 digits
+
+digits 0 digits
+digits 1 digits
+
+q_start + after_sign
+q_start - after_sign
+q_start 0 zero
+q_start 1 digits
+";
+    let mut output: Vec<u8> = vec![];
+    binary.write_dfa(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+    // escaped symbols survive the round trip
+    let source = "\\s \\t \\u{a0} a\n0\n0\n0\n0 \\s 0\n0 \\u{a0} 0\n";
+    let automaton = Automaton::from_reader(source.as_bytes()).unwrap();
+    assert!(automaton.validate(" \u{a0}"));
+
+    let written = automaton.to_dfa_string();
+    assert!(written.starts_with("\\t \\s a \\u{a0}\n"));
+    let reparsed = Automaton::from_reader(written.as_bytes()).unwrap();
+    assert_eq!(reparsed.to_dfa_string(), written);
+}
+
+#[test]
+fn test_json() {
+    let files = vec![
+        "input/identifier.dfa",
+        "input/number.dfa",
+        "input/char.dfa",
+        "input/string.dfa",
+        "input/binary.dfa",
+    ];
+
+    for file in files {
+        let automaton = Automaton::<String>::open(file).unwrap();
+        let json = automaton.to_json_string();
+
+        let from_json: Automaton<String> = Automaton::from_json(&json).unwrap();
+        assert_eq!(from_json.to_json_string(), json);
+        assert_eq!(from_json.to_dfa_string(), automaton.to_dfa_string());
+        assert!(from_json.is_equivalent(&automaton));
+    }
+
+    let json = r#"{
+  "format": "automaton",
+  "version": 1,
+  "alphabet": ["a"],
+  "states": ["0", "1"],
+  "initial_state": "0",
+  "final_states": ["1"],
+  "transitions": [{ "from": "0", "symbol": "a", "to": "1" }]
+}"#;
+    let automaton: Automaton = Automaton::from_json(json).unwrap();
+    assert!(automaton.validate("a"));
+    assert!(!automaton.validate("aa"));
+
+    // both formats reject an automaton without transitions, which the other could not read
+    let json = r#"{"format": "automaton", "version": 1, "alphabet": ["a"], "states": ["0"],
+        "initial_state": "0", "final_states": ["0"], "transitions": []}"#;
+    let error = Automaton::<String>::from_json(json).err().unwrap();
+    assert!(matches!(error, AutomatonError::InvalidJson { .. }));
+    let error = Automaton::<String>::parse("a\n0\n0\n0\n".as_bytes())
+        .err()
+        .unwrap();
+    assert!(matches!(error, AutomatonError::MissingTransitions { .. }));
+}
+
+#[test]
+fn test_invalid_json() {
+    let invalid = vec![
+        // not JSON at all
+        "0 1",
+        // a newer version
+        r#"{"format": "automaton", "version": 2, "alphabet": [], "states": ["0"],
+            "initial_state": "0", "final_states": [], "transitions": []}"#,
+        // a symbol with more than one character
+        r#"{"format": "automaton", "version": 1, "alphabet": ["ab"], "states": ["0"],
+            "initial_state": "0", "final_states": [], "transitions": []}"#,
+        // a duplicate transition
+        r#"{"format": "automaton", "version": 1, "alphabet": ["a"], "states": ["0"],
+            "initial_state": "0", "final_states": [], "transitions": [
+                {"from": "0", "symbol": "a", "to": "0"},
+                {"from": "0", "symbol": "a", "to": "0"}]}"#,
+        // a label that does not fit in a .dfa file
+        r#"{"format": "automaton", "version": 1, "alphabet": [], "states": ["q 0"],
+            "initial_state": "q 0", "final_states": [], "transitions": []}"#,
+    ];
+
+    for json in invalid {
+        let error = Automaton::<String>::from_json(json).err().unwrap();
+        assert!(matches!(error, AutomatonError::InvalidJson { .. }));
+    }
+
+    // valid JSON, but the initial state is not a state
+    let json = r#"{"format": "automaton", "version": 1, "alphabet": ["a"], "states": ["0"],
+        "initial_state": "1", "final_states": [], "transitions": [
+            {"from": "0", "symbol": "a", "to": "0"}]}"#;
+    let error = Automaton::<usize>::from_json(json).err().unwrap();
+    assert!(matches!(error, AutomatonError::InconsistentParts { .. }));
+}
@@ -0,0 +1,78 @@
+#[allow(unused_imports)]
+use crate::models::automaton::Automaton;
+#[allow(unused_imports)]
+use crate::models::transducer::{Mealy, Moore};
+
+#[test]
+fn test_moore() {
+    let parity: Moore<String> = Moore::new("input/parity.moore").unwrap();
+
+    assert_eq!(parity.classify(""), Some(&String::from("Even")));
+    assert_eq!(parity.classify("1101"), Some(&String::from("Odd")));
+    assert_eq!(parity.classify("12"), None);
+
+    let outputs = parity.translate("10").unwrap();
+    assert_eq!(outputs, vec!["Even", "Odd", "Odd"]);
+
+    let compiled = parity.compile();
+    assert_eq!(compiled.classify("11"), Some(&String::from("Even")));
+    assert_eq!(compiled.classify("a"), None);
+}
+
+#[test]
+fn test_moore_escapes() {
+    let spacing: Moore<usize> = Moore::new("input/spacing.moore").unwrap();
+
+    assert_eq!(spacing.classify(""), Some(&String::new()));
+    assert_eq!(spacing.classify("ba"), Some(&String::from(" ")));
+    assert_eq!(spacing.classify("ab"), Some(&String::from("\t")));
+    assert_eq!(spacing.translate("ab").unwrap(), vec!["", " ", "\t"]);
+}
+
+#[test]
+fn test_mealy() {
+    let escape: Mealy<usize> = Mealy::new("input/escape.mealy").unwrap();
+
+    assert_eq!(escape.translate(r#""abc""#), Some(String::from("abc")));
+    assert_eq!(escape.translate(r#""a b""#), Some(String::from("a b")));
+    assert_eq!(
+        escape.translate(r#""a\"b\\""#),
+        Some(String::from(r#"a"b\"#))
+    );
+    assert_eq!(escape.translate(r#""abc"#), None);
+}
+
+#[test]
+fn test_combine() {
+    let names = ["identifier", "number", "char", "string"];
+    let automata: Vec<Automaton> = names
+        .iter()
+        .map(|name| Automaton::new(&format!("input/{}.dfa", name)).unwrap())
+        .collect();
+
+    let machines: Vec<(&str, &Automaton)> = names.iter().cloned().zip(automata.iter()).collect();
+    let classifier = Moore::combine(&machines).unwrap();
+    let compiled = classifier.compile();
+
+    let cases = [
+        ("abc", Some("identifier")),
+        ("1_200", Some("number")),
+        ("-12", None),
+        ("0", Some("number")),
+        ("'a'", Some("char")),
+        (r#""a b""#, Some("string")),
+        ("1a", None),
+        ("'ab'", None),
+        ("", None),
+    ];
+
+    for (lexeme, expected) in cases {
+        assert_eq!(
+            classifier.classify(lexeme).cloned(),
+            expected,
+            "{:?}",
+            lexeme
+        );
+        assert_eq!(compiled.classify(lexeme).cloned(), expected, "{:?}", lexeme);
+    }
+}
@@ -15,6 +15,13 @@ pub fn get_dfa_path(file_name: &str) -> String {
     dfa_path
 }
 
+pub fn get_input_path(file_name: &str) -> String {
+    let mut input_path = get_dfa_folder();
+    input_path.push_str(file_name);
+
+    input_path
+}
+
 pub fn read_usize(prompt: &str) -> usize {
     match print_prompt(prompt) {
         Ok(_) => (),
@@ -0,0 +1,329 @@
+use std::fs::{self, File};
+
+use crate::models::automaton::Automaton;
+use crate::models::builder::AutomatonBuilder;
+use crate::models::codegen::{generate_rust_module, write_rust_module};
+use crate::models::fuzz::fuzz;
+use crate::models::learning::{learn_rpni, Samples};
+use crate::models::random::{random_automaton, Random, RandomConfig};
+
+const JSON_EXTENSION: &str = ".json";
+
+const USAGE: &str = "Usage:
+  cargo run                                      open the interactive menu
+  cargo run codegen [-o <output.rs>] (<file.dfa> <function name>)...
+                                                 generate Rust functions from automata
+  cargo run build [-f <input.dfa>] <output.dfa> (<command>)...
+                                                 build an automaton from commands such as
+                                                 \"state q0 q1\" \"symbol a\" \"initial q0\" \"final q1\"
+                                                 \"transition q0 a q1\" \"remove-state q1\" \"undo\"
+  cargo run learn <samples file> <output.dfa>   learn an automaton from \"+ sequence\" and
+                                                 \"- sequence\" lines with RPNI
+  cargo run random [-s <seed>] [-n <states>] [-k <alphabet size>] <output.dfa>
+                                                 write a random automaton and some sequences
+  cargo run fuzz [<iterations>] [<seed>]         check random automata against a reference
+  cargo run convert <input> <output>             convert between .dfa and .json files,
+                                                 writing .dfa files in canonical form
+  cargo run regex <pattern> [<output.dfa>]       build an automaton from a regular expression";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    match command {
+        "codegen" => codegen(args),
+        "build" => build(args),
+        "learn" => learn(args),
+        "random" => random(args),
+        "fuzz" => run_fuzz(args),
+        "convert" => convert(args),
+        "regex" => regex(args),
+        _ => Err(format!("unknown command '{}'\n\n{}", command, USAGE)),
+    }
+}
+
+fn codegen(args: &[String]) -> Result<(), String> {
+    // an optional output file, otherwise the code is printed
+    let (output_path, args) = match args.first().map(|arg| arg.as_str()) {
+        Some("-o") | Some("--output") => match args.get(1) {
+            Some(output_path) => (Some(output_path), &args[2..]),
+            None => return Err(String::from("missing output file")),
+        },
+        _ => (None, args),
+    };
+
+    if args.is_empty() || args.len() % 2 != 0 {
+        let error = format!("expected pairs of <file.dfa> <function name>\n\n{}", USAGE);
+        return Err(error);
+    }
+
+    let automata: Vec<(&str, &str)> = args
+        .chunks(2)
+        .map(|pair| (pair[0].as_str(), pair[1].as_str()))
+        .collect();
+
+    match output_path {
+        Some(output_path) => {
+            match write_rust_module(&automata, output_path) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+            println!("INFO: Code written to {}", output_path);
+        }
+        None => {
+            let code = match generate_rust_module(&automata) {
+                Ok(code) => code,
+                Err(e) => return Err(e),
+            };
+            print!("{}", code);
+        }
+    }
+
+    Ok(())
+}
+
+fn build(args: &[String]) -> Result<(), String> {
+    // an optional automaton to start from
+    let (mut builder, args) = match args.first().map(|arg| arg.as_str()) {
+        Some("-f") | Some("--from") => match args.get(1) {
+            Some(input_path) => match Automaton::<String>::open(input_path) {
+                Ok(automaton) => (AutomatonBuilder::from_automaton(&automaton), &args[2..]),
+                Err(e) => return Err(e.diagnostic()),
+            },
+            None => return Err(String::from("missing input file")),
+        },
+        _ => (AutomatonBuilder::new(), args),
+    };
+
+    let (output_path, commands) = match args.split_first() {
+        Some((output_path, commands)) => (output_path, commands),
+        None => {
+            let error = format!("missing output file\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    for command in commands {
+        match builder.run_command(command) {
+            Ok(_) => (),
+            Err(e) => {
+                let error = format!("[{}] {}", command, e);
+                return Err(error);
+            }
+        }
+    }
+
+    // report what is left to do, the file is saved anyway
+    for (state, symbol) in builder.missing_transitions() {
+        println!("WARNING: Missing transition: ({:?}, {:?})", state, symbol);
+    }
+
+    match builder.save(output_path) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    println!("INFO: Automaton written to {}", output_path);
+
+    Ok(())
+}
+
+fn learn(args: &[String]) -> Result<(), String> {
+    let (samples_path, output_path) = match args {
+        [samples_path, output_path] => (samples_path, output_path),
+        _ => {
+            let error = format!("expected <samples file> <output.dfa>\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    let samples = match Samples::new(samples_path) {
+        Ok(samples) => samples,
+        Err(e) => return Err(e),
+    };
+    let automaton = match learn_rpni(&samples) {
+        Ok(automaton) => automaton,
+        Err(e) => return Err(e),
+    };
+
+    match AutomatonBuilder::from_automaton(&automaton).save(output_path) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    println!(
+        "INFO: Automaton with {} states written to {}",
+        automaton.get_states().len(),
+        output_path
+    );
+
+    Ok(())
+}
+
+fn random(args: &[String]) -> Result<(), String> {
+    let mut seed = 0;
+    let mut config = RandomConfig::new(4, 2);
+    let mut output_path = None;
+
+    let mut index = 0;
+    while index < args.len() {
+        let option = args[index].as_str();
+        if !option.starts_with('-') {
+            output_path = Some(&args[index]);
+            index += 1;
+            continue;
+        }
+
+        let value = match args.get(index + 1).map(|value| value.parse::<u64>()) {
+            Some(Ok(value)) => value,
+            _ => {
+                let error = format!("expected a number after '{}'", option);
+                return Err(error);
+            }
+        };
+        match option {
+            "-s" | "--seed" => seed = value,
+            "-n" | "--states" => config.state_count = value as usize,
+            "-k" | "--alphabet" => config.alphabet_size = value as usize,
+            _ => {
+                let error = format!("unknown option '{}'\n\n{}", option, USAGE);
+                return Err(error);
+            }
+        }
+        index += 2;
+    }
+
+    let output_path = match output_path {
+        Some(output_path) => output_path,
+        None => {
+            let error = format!("missing output file\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    let mut random = Random::new(seed);
+    let automaton = random_automaton(&config, &mut random);
+    match AutomatonBuilder::from_automaton(&automaton).save(output_path) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    println!("INFO: Automaton written to {}", output_path);
+
+    for _ in 0..5 {
+        if let Some(sequence) = automaton.random_accepted(10, &mut random) {
+            println!("Accepted: {:?}", sequence);
+        }
+    }
+    for _ in 0..5 {
+        if let Some(sequence) = automaton.random_rejected(10, &mut random) {
+            println!("Rejected: {:?}", sequence);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_fuzz(args: &[String]) -> Result<(), String> {
+    let numbers: Vec<Result<u64, _>> = args.iter().map(|arg| arg.parse::<u64>()).collect();
+    let (iterations, seed) = match numbers.as_slice() {
+        [] => (1000, 0),
+        [Ok(iterations)] => (*iterations, 0),
+        [Ok(iterations), Ok(seed)] => (*iterations, *seed),
+        _ => {
+            let error = format!("expected [<iterations>] [<seed>]\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    let report = match fuzz(iterations as usize, seed) {
+        Ok(report) => report,
+        Err(e) => return Err(e),
+    };
+    println!(
+        "INFO: {} automata, {} sequences and {} mutated files ({} rejected by the parser) checked",
+        report.automata, report.sequences, report.mutations, report.invalid_mutations
+    );
+
+    Ok(())
+}
+
+fn convert(args: &[String]) -> Result<(), String> {
+    let (input_path, output_path) = match args {
+        [input_path, output_path] => (input_path, output_path),
+        _ => {
+            let error = format!("expected <input> <output>\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    // the states keep their labels, whatever they are
+    let automaton: Automaton<String> = if input_path.ends_with(JSON_EXTENSION) {
+        let source = match fs::read_to_string(input_path) {
+            Ok(source) => source,
+            Err(e) => {
+                let error = format!("could not read input file: {}", e);
+                return Err(error);
+            }
+        };
+        match Automaton::from_json(&source) {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.with_path(input_path).to_string()),
+        }
+    } else {
+        match Automaton::open(input_path) {
+            Ok(automaton) => automaton,
+            Err(e) => return Err(e.diagnostic()),
+        }
+    };
+
+    let mut file = match File::create(output_path) {
+        Ok(file) => file,
+        Err(e) => {
+            let error = format!("could not create output file: {}", e);
+            return Err(error);
+        }
+    };
+    let result = match output_path.ends_with(JSON_EXTENSION) {
+        true => automaton.write_json(&mut file),
+        false => automaton.write_dfa(&mut file),
+    };
+    match result {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    println!("INFO: Automaton written to {}", output_path);
+
+    Ok(())
+}
+
+fn regex(args: &[String]) -> Result<(), String> {
+    let (pattern, output_path) = match args {
+        [pattern] => (pattern, None),
+        [pattern, output_path] => (pattern, Some(output_path)),
+        _ => {
+            let error = format!("expected <pattern> [<output.dfa>]\n\n{}", USAGE);
+            return Err(error);
+        }
+    };
+
+    let automaton = match Automaton::from_regex(pattern) {
+        Ok(automaton) => automaton,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    match output_path {
+        Some(output_path) => {
+            match AutomatonBuilder::from_automaton(&automaton).save(output_path) {
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+            println!("INFO: Automaton written to {}", output_path);
+        }
+        None => print!("{}", automaton.to_dfa_string()),
+    }
+
+    Ok(())
+}
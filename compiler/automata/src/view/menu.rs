@@ -1,6 +1,26 @@
+use std::collections::HashSet;
+
 use crate::models::automaton::Automaton;
-use crate::models::state::State;
-use crate::utils::input::{get_dfa_folder, get_dfa_path, read_string, read_usize};
+use crate::models::builder::AutomatonBuilder;
+use crate::models::learning::{learn_lstar, learn_rpni, Samples};
+use crate::models::pda::{Acceptance, Pda, EMPTY};
+use crate::models::transducer::{Mealy, Moore};
+use crate::utils::input::{get_dfa_folder, get_dfa_path, get_input_path, read_string, read_usize};
+
+// the menu accepts descriptive state names, not just numbers
+type State = String;
+
+// the lexical automata and the tags of the sequences they accept, by priority
+const LEXICAL_AUTOMATA: [(&str, &str); 4] = [
+    ("identifier", "Identifier"),
+    ("number", "Number"),
+    ("char", "Char"),
+    ("string", "String"),
+];
+
+const MOORE_EXTENSION: &str = ".moore";
+const MEALY_EXTENSION: &str = ".mealy";
+const PDA_EXTENSION: &str = ".pda";
 
 pub struct Menu {}
 
@@ -10,12 +30,18 @@ impl Menu {
     }
 
     pub fn show(&self) {
-        let mut automaton: Option<Automaton> = None;
+        let mut automaton: Option<Automaton<State>> = None;
         let menu_items = vec![
             "Exit",
             "Load automaton",
             "Display details",
             "Verify sequence",
+            "Analyze language",
+            "Classify lexeme",
+            "Run transducer",
+            "Build automaton",
+            "Learn automaton",
+            "Run pushdown automaton",
         ];
 
         self.display_menu(&menu_items, "Main menu");
@@ -42,6 +68,36 @@ impl Menu {
                 3 => {
                     self.verify_sequence(&automaton);
                 }
+                4 => {
+                    match self.analysis_submenu(&automaton) {
+                        Ok(_) => {
+                            // display main menu again
+                            self.display_menu(&menu_items, "Main menu");
+                        }
+                        Err(_) => (),
+                    };
+                }
+                5 => {
+                    self.classify_lexeme();
+                }
+                6 => {
+                    self.run_transducer();
+                }
+                7 => {
+                    self.builder_submenu(&mut automaton);
+
+                    // display main menu again
+                    self.display_menu(&menu_items, "Main menu");
+                }
+                8 => {
+                    self.learning_submenu(&mut automaton);
+
+                    // display main menu again
+                    self.display_menu(&menu_items, "Main menu");
+                }
+                9 => {
+                    self.run_pda();
+                }
                 _ => println!("ERROR: Invalid option!"),
             }
         }
@@ -54,7 +110,7 @@ impl Menu {
         }
     }
 
-    fn load_dfa(&self, automaton: &mut Option<Automaton>) {
+    fn load_dfa(&self, automaton: &mut Option<Automaton<State>>) {
         println!(
             "Enter the name of the file from the '{}' folder, without the extension.",
             get_dfa_folder().trim_end_matches('/')
@@ -62,18 +118,25 @@ impl Menu {
         let file_name = read_string("File name:");
         let file_path = get_dfa_path(&file_name);
 
-        *automaton = match Automaton::new(&file_path) {
+        *automaton = match Automaton::open(&file_path) {
             Ok(automaton) => Some(automaton),
-            Err(_) => {
+            Err(e) => {
                 println!("ERROR: Could not load automaton!");
+                println!("{}", e.diagnostic());
                 return;
             }
         };
 
         println!("INFO: Automaton loaded!");
+
+        if let Some(automaton) = automaton {
+            for warning in automaton.warnings() {
+                println!("WARNING: {}", warning);
+            }
+        }
     }
 
-    fn verify_sequence(&self, automaton: &Option<Automaton>) {
+    fn verify_sequence(&self, automaton: &Option<Automaton<State>>) {
         let automaton = match automaton {
             Some(automaton) => automaton,
             None => {
@@ -90,7 +153,148 @@ impl Menu {
         }
     }
 
-    fn dfa_submenu(&self, automaton: &Option<Automaton>) -> Result<(), ()> {
+    fn classify_lexeme(&self) {
+        // load the lexical automata and run them as a single machine
+        let mut automata = vec![];
+        for (file_name, tag) in LEXICAL_AUTOMATA {
+            let automaton = match Automaton::<State>::open(&get_dfa_path(file_name)) {
+                Ok(automaton) => automaton,
+                Err(e) => {
+                    println!("ERROR: Could not load automaton '{}'!", file_name);
+                    println!("{}", e.diagnostic());
+                    return;
+                }
+            };
+            automata.push((String::from(tag), automaton));
+        }
+
+        let machines: Vec<(String, &Automaton<State>)> = automata
+            .iter()
+            .map(|(tag, automaton)| (tag.clone(), automaton))
+            .collect();
+        let classifier = match Moore::combine(&machines) {
+            Ok(classifier) => classifier.compile(),
+            Err(e) => {
+                println!("ERROR: Could not combine automata: {}", e);
+                return;
+            }
+        };
+
+        let lexeme = read_string("Lexeme:");
+        match classifier.classify(&lexeme) {
+            Some(tag) => println!("INFO: {}", tag),
+            None => println!("INFO: Lexeme not accepted by any automaton!"),
+        }
+
+        // what a lexer would take as the next token
+        let symbols: Vec<char> = lexeme.chars().collect();
+        if let Some((length, tag)) = classifier.longest_match(&symbols) {
+            let prefix: String = symbols[..length].iter().collect();
+            println!("INFO: Longest match: {:?} ({})", prefix, tag);
+        }
+    }
+
+    fn run_transducer(&self) {
+        println!(
+            "Enter the name of the file from the '{}' folder, with the {} or {} extension.",
+            get_dfa_folder().trim_end_matches('/'),
+            MOORE_EXTENSION,
+            MEALY_EXTENSION
+        );
+        let file_name = read_string("File name:");
+        let file_path = get_input_path(&file_name);
+
+        if file_name.ends_with(MOORE_EXTENSION) {
+            let moore: Moore<State> = match Moore::new(&file_path) {
+                Ok(moore) => moore,
+                Err(e) => {
+                    println!("ERROR: Could not load transducer!");
+                    println!("{}", e.diagnostic());
+                    return;
+                }
+            };
+
+            let sequence = read_string("Sequence:");
+            match moore.translate(&sequence) {
+                Some(outputs) => println!("Outputs: {:?}", outputs),
+                None => println!("INFO: Sequence rejected!"),
+            }
+            if let Some(output) = moore.classify(&sequence) {
+                println!("Final output: {:?}", output);
+            }
+        } else if file_name.ends_with(MEALY_EXTENSION) {
+            let mealy: Mealy<State> = match Mealy::new(&file_path) {
+                Ok(mealy) => mealy,
+                Err(e) => {
+                    println!("ERROR: Could not load transducer!");
+                    println!("{}", e.diagnostic());
+                    return;
+                }
+            };
+
+            let sequence = read_string("Sequence:");
+            match mealy.translate(&sequence) {
+                Some(output) => println!("Output: {:?}", output),
+                None => println!("INFO: Sequence rejected!"),
+            }
+        } else {
+            println!("ERROR: Unknown transducer extension!");
+        }
+    }
+
+    fn run_pda(&self) {
+        println!(
+            "Enter the name of the file from the '{}' folder, with the {} extension.",
+            get_dfa_folder().trim_end_matches('/'),
+            PDA_EXTENSION
+        );
+        let file_name = read_string("File name:");
+        let pda: Pda<State> = match Pda::new(&get_input_path(&file_name)) {
+            Ok(pda) => pda,
+            Err(e) => {
+                println!("ERROR: Could not load pushdown automaton!");
+                println!("{}", e.diagnostic());
+                return;
+            }
+        };
+
+        println!("Input alphabet: {:?}", sorted(pda.get_input_alphabet()));
+        println!("Stack alphabet: {:?}", sorted(pda.get_stack_alphabet()));
+        println!("States: {:?}", sorted(pda.get_states()));
+        println!("Initial state: {:?}", pda.get_initial_state());
+        println!("Initial stack symbol: {:?}", pda.get_initial_stack_symbol());
+        println!("Final states: {:?}", sorted(pda.get_final_states()));
+        println!("Transitions:");
+        for transition in pda.get_transitions() {
+            let push = match transition.push.is_empty() {
+                true => String::from(EMPTY),
+                false => transition.push.join(" "),
+            };
+            println!(
+                "  ({}, {}, {}) -> ({}, {})",
+                transition.from,
+                transition.input.as_deref().unwrap_or(EMPTY),
+                transition.pop,
+                transition.to,
+                push
+            );
+        }
+
+        let acceptance = match read_usize("\nAccept by (1) final state or (2) empty stack:") {
+            2 => Acceptance::EmptyStack,
+            _ => Acceptance::FinalState,
+        };
+
+        let sequence = read_string("Tokens, separated by spaces:");
+        let input: Vec<&str> = sequence.split_whitespace().collect();
+        match pda.accepts(&input, acceptance) {
+            Ok(true) => println!("INFO: Sequence accepted!"),
+            Ok(false) => println!("INFO: Sequence rejected!"),
+            Err(e) => println!("ERROR: {}", e),
+        }
+    }
+
+    fn dfa_submenu(&self, automaton: &Option<Automaton<State>>) -> Result<(), ()> {
         let automaton = match automaton {
             Some(automaton) => automaton,
             None => {
@@ -106,6 +310,7 @@ impl Menu {
             "Transitions",
             "Initial state",
             "Set of final states",
+            "Character classes",
         ];
 
         self.display_menu(&menu_items, "Display details");
@@ -133,20 +338,7 @@ impl Menu {
                     println!("Alphabet: {:?}", &alphabet);
                 }
                 3 => {
-                    // get a copy of the transitions and sort them
-                    let mut transitions: Vec<((State, char), State)> = automaton
-                        .get_transitions()
-                        .iter()
-                        .map(|(key, value)| (*key, *value))
-                        .collect();
-
-                    // sort by key = (start_state, symbol)
-                    transitions.sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
-
-                    println!("Transitions:");
-                    for ((start_state, symbol), end_state) in &transitions {
-                        println!("({:?}, {:?}) -> {:?}", start_state, symbol, end_state);
-                    }
+                    self.display_transitions(automaton);
                 }
                 4 => {
                     // print the initial state
@@ -160,10 +352,331 @@ impl Menu {
 
                     println!("Set of final states: {:?}", &final_states);
                 }
+                6 => {
+                    // compile the automaton to group the alphabet into classes
+                    let compiled = automaton.compile();
+                    println!(
+                        "{} states (including the dead state), {} classes (including unknown characters)",
+                        compiled.state_count(),
+                        compiled.class_count()
+                    );
+
+                    println!("Character classes:");
+                    for class in compiled.get_classes() {
+                        println!("{:?}", class);
+                    }
+                }
+                _ => println!("ERROR: Invalid option!"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn analysis_submenu(&self, automaton: &Option<Automaton<State>>) -> Result<(), ()> {
+        let automaton = match automaton {
+            Some(automaton) => automaton,
+            None => {
+                println!("INFO: No automaton loaded!");
+                return Err(());
+            }
+        };
+
+        let menu_items = vec![
+            "Back",
+            "Emptiness and finiteness",
+            "Count accepted sequences",
+            "List accepted sequences",
+            "Shortest accepted and rejected sequences",
+            "Unreachable and dead states",
+            "Minimize",
+            "Compare with another automaton",
+        ];
+
+        self.display_menu(&menu_items, "Analyze language");
+
+        loop {
+            let choice = read_usize("\nOption:");
+            match choice {
+                0 => {
+                    println!("INFO: Returning to the previous menu.");
+                    break;
+                }
+                1 => {
+                    println!("Empty: {}", automaton.is_empty());
+                    println!("Finite: {}", automaton.is_finite());
+                }
+                2 => {
+                    let length = read_usize("Maximum length:");
+                    println!(
+                        "Accepted sequences of length at most {}: {}",
+                        length,
+                        automaton.count_accepted(length)
+                    );
+                }
+                3 => {
+                    let count = read_usize("Number of sequences:");
+                    for sequence in automaton.enumerate(count) {
+                        println!("{:?}", sequence);
+                    }
+                }
+                4 => {
+                    match automaton.shortest_accepted() {
+                        Some(sequence) => println!("Shortest accepted: {:?}", sequence),
+                        None => println!("Shortest accepted: none"),
+                    }
+                    match automaton.shortest_rejected() {
+                        Some(sequence) => println!("Shortest rejected: {:?}", sequence),
+                        None => println!("Shortest rejected: none"),
+                    }
+                }
+                5 => {
+                    // get a copy of the states and sort them
+                    let mut unreachable: Vec<State> =
+                        automaton.unreachable_states().into_iter().collect();
+                    unreachable.sort();
+
+                    let mut dead: Vec<State> = automaton.dead_states().into_iter().collect();
+                    dead.sort();
+
+                    println!("Unreachable states: {:?}", &unreachable);
+                    println!("Dead states: {:?}", &dead);
+                }
+                6 => {
+                    let minimal = automaton.minimize();
+                    println!(
+                        "States: {} -> {}",
+                        automaton.get_states().len(),
+                        minimal.get_states().len()
+                    );
+                    self.display_transitions(&minimal.map_states(|state| state.to_string()));
+                }
+                7 => {
+                    let file_name = read_string("File name:");
+                    let other = match Automaton::<State>::open(&get_dfa_path(&file_name)) {
+                        Ok(other) => other,
+                        Err(e) => {
+                            println!("ERROR: Could not load automaton!");
+                            println!("{}", e.diagnostic());
+                            continue;
+                        }
+                    };
+
+                    match automaton.distinguishing_sequence(&other) {
+                        Some(sequence) => println!(
+                            "INFO: Not equivalent, only one of them accepts {:?}",
+                            sequence
+                        ),
+                        None => println!("INFO: The automata are equivalent!"),
+                    }
+                }
                 _ => println!("ERROR: Invalid option!"),
             }
         }
 
         Ok(())
     }
+
+    fn builder_submenu(&self, automaton: &mut Option<Automaton<State>>) {
+        // edit the loaded automaton, or start from scratch
+        let mut builder = match automaton {
+            Some(automaton) => AutomatonBuilder::from_automaton(automaton),
+            None => AutomatonBuilder::new(),
+        };
+
+        let menu_items = vec![
+            "Back",
+            "Add symbols",
+            "Remove symbol",
+            "Add states",
+            "Remove state",
+            "Set initial state",
+            "Mark final states",
+            "Unmark final states",
+            "Add transition",
+            "Remove transition",
+            "Display draft",
+            "Check completeness",
+            "Undo",
+            "Save to file",
+            "Use as current automaton",
+        ];
+
+        // the command run by each editing option, and what it asks for
+        let commands = [
+            ("symbol", "Symbols:"),
+            ("remove-symbol", "Symbol:"),
+            ("state", "States:"),
+            ("remove-state", "State:"),
+            ("initial", "State:"),
+            ("final", "States:"),
+            ("not-final", "States:"),
+            ("transition", "Transition (state symbol state):"),
+            ("remove-transition", "Transition (state symbol):"),
+        ];
+
+        self.display_menu(&menu_items, "Build automaton");
+
+        loop {
+            let choice = read_usize("\nOption:");
+            match choice {
+                0 => {
+                    println!("INFO: Returning to the previous menu.");
+                    break;
+                }
+                1..=9 => {
+                    let (command, prompt) = commands[choice - 1];
+                    let args = read_string(prompt);
+                    match builder.run_command(&format!("{} {}", command, args)) {
+                        Ok(_) => println!("INFO: Done!"),
+                        Err(e) => println!("ERROR: {}", e),
+                    }
+                }
+                10 => {
+                    let mut alphabet: Vec<char> = builder.get_alphabet().iter().cloned().collect();
+                    alphabet.sort();
+                    let mut states: Vec<State> = builder.get_states().iter().cloned().collect();
+                    states.sort();
+                    let mut final_states: Vec<State> =
+                        builder.get_final_states().iter().cloned().collect();
+                    final_states.sort();
+                    let mut transitions: Vec<((State, char), State)> = builder
+                        .get_transitions()
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect();
+                    transitions.sort();
+
+                    println!("Alphabet: {:?}", &alphabet);
+                    println!("Set of states: {:?}", &states);
+                    println!("Initial state: {:?}", builder.get_initial_state());
+                    println!("Set of final states: {:?}", &final_states);
+                    println!("Transitions:");
+                    for ((start_state, symbol), end_state) in &transitions {
+                        println!("({:?}, {:?}) -> {:?}", start_state, symbol, end_state);
+                    }
+                }
+                11 => {
+                    if builder.get_initial_state().is_none() {
+                        println!("WARNING: No initial state!");
+                    }
+                    if builder.is_complete() {
+                        println!("INFO: Every state has a transition on every symbol!");
+                    }
+                    for (state, symbol) in builder.missing_transitions() {
+                        println!("Missing transition: ({:?}, {:?})", state, symbol);
+                    }
+                }
+                12 => match builder.undo() {
+                    true => println!("INFO: Undone!"),
+                    false => println!("INFO: Nothing to undo!"),
+                },
+                13 => {
+                    let file_name = read_string("File name:");
+                    match builder.save(&get_dfa_path(&file_name)) {
+                        Ok(_) => println!("INFO: Automaton saved!"),
+                        Err(e) => println!("ERROR: Could not save automaton: {}", e),
+                    }
+                }
+                14 => match builder.build() {
+                    Ok(built) => {
+                        *automaton = Some(built);
+                        println!("INFO: Automaton loaded!");
+                    }
+                    Err(e) => println!("ERROR: {}", e),
+                },
+                _ => println!("ERROR: Invalid option!"),
+            }
+        }
+    }
+
+    fn display_transitions(&self, automaton: &Automaton<State>) {
+        // get a copy of the transitions and sort them
+        let mut transitions: Vec<((State, char), State)> = automaton
+            .get_transitions()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        // sort by key = (start_state, symbol)
+        transitions.sort_by(|(key1, _), (key2, _)| key1.cmp(key2));
+
+        println!("Transitions:");
+        for ((start_state, symbol), end_state) in &transitions {
+            println!("({:?}, {:?}) -> {:?}", start_state, symbol, end_state);
+        }
+    }
+
+    fn learning_submenu(&self, automaton: &mut Option<Automaton<State>>) {
+        let menu_items = vec![
+            "Back",
+            "Learn from samples (RPNI)",
+            "Learn the loaded automaton (L*)",
+        ];
+
+        self.display_menu(&menu_items, "Learn automaton");
+
+        loop {
+            let choice = read_usize("\nOption:");
+            let learned = match choice {
+                0 => {
+                    println!("INFO: Returning to the previous menu.");
+                    break;
+                }
+                1 => {
+                    println!(
+                        "Enter the name of the file from the '{}' folder, with the extension.",
+                        get_dfa_folder().trim_end_matches('/')
+                    );
+                    let file_name = read_string("File name:");
+                    let samples = match Samples::new(&get_input_path(&file_name)) {
+                        Ok(samples) => samples,
+                        Err(e) => {
+                            println!("ERROR: Could not load samples: {}", e);
+                            continue;
+                        }
+                    };
+                    println!(
+                        "INFO: {} positive and {} negative samples",
+                        samples.get_positive().len(),
+                        samples.get_negative().len()
+                    );
+
+                    match learn_rpni(&samples) {
+                        Ok(learned) => learned,
+                        Err(e) => {
+                            println!("ERROR: {}", e);
+                            continue;
+                        }
+                    }
+                }
+                2 => match automaton {
+                    // the loaded automaton answers the queries
+                    Some(teacher) => learn_lstar(teacher.get_alphabet(), teacher),
+                    None => {
+                        println!("INFO: No automaton loaded!");
+                        continue;
+                    }
+                },
+                _ => {
+                    println!("ERROR: Invalid option!");
+                    continue;
+                }
+            };
+
+            println!(
+                "INFO: Learned an automaton with {} states!",
+                learned.get_states().len()
+            );
+            *automaton = Some(learned.map_states(|state| state.to_string()));
+            println!("INFO: Automaton loaded!");
+        }
+    }
+}
+
+// a sorted copy of a set, for display
+fn sorted<T: Clone + Ord>(items: &HashSet<T>) -> Vec<T> {
+    let mut items: Vec<T> = items.iter().cloned().collect();
+    items.sort();
+    items
 }
@@ -1,57 +1,124 @@
 use parser::{Grammar, LL1Parser};
 use scanner::Scanner;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
 
-fn main() {
-    let grammar_file = "../parser/input/ll_pass.in";
-    let grammar = match Grammar::new(grammar_file) {
+// the grammar is embedded in the binary, and the other paths are relative to the
+// current directory, so the compiler can be run from any directory
+const GRAMMAR: &str = include_str!("../../parser/input/grammar.in");
+const OUTPUT_FILE: &str = "output/parse_tree.out";
+// reads the program from stdin
+const STANDARD_STREAM: &str = "-";
+
+const USAGE: &str = "Usage: olive [-o <file>] <program>
+
+Parses the program, or stdin if it is -, and writes its parse tree to <file>,
+output/parse_tree.out in the current directory by default.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (program, output_file) = match parse_args(&args) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let grammar = match Grammar::from_reader(GRAMMAR.as_bytes()) {
         Ok(grammar) => grammar,
         Err(e) => {
-            println!("{}", e);
-            return;
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
     };
 
     let parser = LL1Parser::new(grammar);
 
-    let mut scanner = match Scanner::new() {
+    let scanner = match program.as_str() {
+        STANDARD_STREAM => Scanner::from_reader(io::stdin()),
+        _ => match File::open(&program) {
+            Ok(file) => Scanner::from_reader(file),
+            Err(e) => {
+                eprintln!("could not read program file: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let mut scanner = match scanner {
         Ok(scanner) => scanner,
         Err(e) => {
-            println!("{}", e);
-            return;
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
     };
 
-    let program = "../parser/input/program.test";
-    match scanner.scan(program) {
-        Ok(_) => {}
-        Err(e) => {
-            println!("{}", e);
-            return;
-        }
-    }
-
-    println!("Finished scanning input.");
+    // the program is scanned as the parser asks for tokens
     println!("\nParsing input...");
-
-    let tokens = scanner.get_token_list();
-    let identifiers = scanner.get_identifier_table();
-    let constants = scanner.get_constant_table();
-
-    let output = match parser.parse(&tokens, &identifiers, &constants) {
+    let output = match parser.parse_stream(scanner.tokens()) {
         Ok(output) => output,
         Err(e) => {
-            println!("{}", e);
-            return;
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
     };
 
-    match output.write_output("output/parse_tree.out") {
+    if let Some(directory) = Path::new(&output_file).parent() {
+        match fs::create_dir_all(directory) {
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("could not create output directory: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    match output.write_output(&output_file) {
         Ok(_) => {}
         Err(e) => {
-            println!("{}", e);
-            return;
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
     }
 
     println!("Finished parsing input.");
+    ExitCode::SUCCESS
+}
+
+// the program and the output file
+fn parse_args(args: &[String]) -> Result<(String, String), String> {
+    let mut program = None;
+    let mut output_file = String::from(OUTPUT_FILE);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                output_file = match args.next() {
+                    Some(output_file) => output_file.clone(),
+                    None => {
+                        let error = format!("{} needs a value", arg);
+                        return Err(error);
+                    }
+                };
+            }
+            option if option.starts_with('-') && option != STANDARD_STREAM => {
+                let error = format!("unknown option '{}'", option);
+                return Err(error);
+            }
+            path if program.is_none() => program = Some(String::from(path)),
+            path => {
+                let error = format!("unexpected argument '{}', only one program is parsed", path);
+                return Err(error);
+            }
+        }
+    }
+
+    match program {
+        Some(program) => Ok((program, output_file)),
+        None => Err(String::from("missing program")),
+    }
 }
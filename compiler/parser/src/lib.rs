@@ -2,4 +2,4 @@ mod models;
 mod utils;
 
 pub use models::grammar::Grammar;
-pub use models::parser::LL1Parser;
+pub use models::parser::{terminal_symbol, LL1Parser};
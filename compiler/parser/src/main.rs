@@ -2,9 +2,18 @@ mod models;
 mod tests;
 mod utils;
 
+use automata::Acceptance;
 use models::grammar::Grammar;
-use models::parser::LL1Parser;
+use models::parser::{terminal_symbol, LL1Parser};
 use scanner::Scanner;
+use std::env;
+use std::fs::File;
+
+// the paths are anchored to the crate, so the parser can be run from any directory
+const GRAMMAR_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/grammar.in");
+// parsed when no program is given on the command line
+const PROGRAM_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/p3.oli");
+const OUTPUT_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/output/parse_tree.out");
 
 #[allow(dead_code)]
 fn print_first_follow(grammar: &Grammar) {
@@ -74,7 +83,7 @@ fn print_first_follow(grammar: &Grammar) {
 }
 
 fn main() {
-    let grammar = match Grammar::new("input/grammar.in") {
+    let grammar = match Grammar::new(GRAMMAR_FILE) {
         Ok(grammar) => grammar,
         Err(e) => {
             println!("{}", e);
@@ -141,6 +150,13 @@ fn main() {
     print_first_follow(&grammar);
 
     println!("\ngrammar is context free: {}\n", grammar.is_context_free());
+    let pda = match grammar.to_pda() {
+        Ok(pda) => pda,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
     let parser = LL1Parser::new(grammar);
 
     let mut table: Vec<(&(String, String), &String)> = parser.get_parsing_table().iter().collect();
@@ -159,30 +175,50 @@ fn main() {
 
     println!("\nFinished building parsing table.");
 
-    let mut scanner = match Scanner::new() {
-        Ok(scanner) => scanner,
+    let program = match env::args().nth(1) {
+        Some(program) => program,
+        None => String::from(PROGRAM_FILE),
+    };
+    let file = match File::open(&program) {
+        Ok(file) => file,
         Err(e) => {
-            println!("{}", e);
+            println!("could not read program file: {}", e);
             return;
         }
     };
 
-    let program = "../../programs/p3.oli";
-    match scanner.scan(program) {
-        Ok(_) => {}
+    let mut scanner = match Scanner::from_reader(file) {
+        Ok(scanner) => scanner,
         Err(e) => {
             println!("{}", e);
             return;
         }
-    }
+    };
 
-    println!("Finished scanning input.\n");
-    let tokens = scanner.get_token_list();
+    // the program is scanned as the parser asks for tokens,
+    // which are kept for the pushdown automaton
+    let mut tokens = vec![];
+    let tokens_read = scanner.tokens().inspect(|result| {
+        if let Ok(token) = result {
+            tokens.push(token.clone());
+        }
+    });
 
-    let identifiers = scanner.get_identifier_table();
-    let constants = scanner.get_constant_table();
+    let result = parser.parse_stream(tokens_read);
+
+    // cross-check the parser with the pushdown automaton of the grammar
+    let symbols: Vec<&str> = tokens.iter().map(terminal_symbol).collect();
+    match pda.accepts(&symbols, Acceptance::FinalState) {
+        Ok(accepted) if accepted != result.is_ok() => eprintln!(
+            "ERROR: the pushdown automaton {} the input, but the parser {} it!",
+            if accepted { "accepts" } else { "rejects" },
+            if result.is_ok() { "accepts" } else { "rejects" }
+        ),
+        Ok(accepted) => println!("Pushdown automaton accepts input: {}\n", accepted),
+        Err(e) => println!("{}", e),
+    }
 
-    let output = match parser.parse(&tokens, &identifiers, &constants) {
+    let output = match result {
         Ok(output) => output,
         Err(e) => {
             println!("{}", e);
@@ -190,8 +226,10 @@ fn main() {
         }
     };
 
+    println!("Finished scanning and parsing input.\n");
+
     output.display();
-    match output.write_output("output/parse_tree.out") {
+    match output.write_output(OUTPUT_FILE) {
         Ok(_) => {}
         Err(e) => {
             println!("{}", e);
@@ -1,13 +1,18 @@
+use automata::{Pda, PdaTransition};
 use hash_map::HashMap;
 use lazy_static::lazy_static;
 use std::collections::HashSet as Set;
 
-use std::fs::File;
-use std::io::{BufReader, Lines};
+use std::io::{BufRead, BufReader, Lines, Read};
 use utils::{extract_line_data, get_next_line, open_file, InputLine};
 
 use crate::utils::constants::{EOF_TOKEN, EPSILON};
 
+// the states of the pushdown automaton built from a grammar
+const PDA_START: usize = 0;
+const PDA_LOOP: usize = 1;
+const PDA_ACCEPT: usize = 2;
+
 lazy_static! {
     static ref ESCAPES: HashMap<&'static str, &'static str> = HashMap::from([
         (r"\s", " "),   // space
@@ -38,6 +43,24 @@ impl Grammar {
         }
     }
 
+    // reads the grammar from any source in the same format as the files,
+    // i.e. a grammar embedded with `include_str!`; only the compiler uses it
+    #[allow(dead_code)]
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, String> {
+        let mut grammar = Self {
+            non_terminals: Set::new(),
+            terminals: Set::new(),
+            start_symbol: String::new(),
+            productions: HashMap::new(),
+        };
+
+        let mut lines = BufReader::new(reader).lines();
+        match grammar.parse_lines(&mut lines) {
+            Ok(_) => Ok(grammar),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn get_non_terminals(&self) -> &Set<String> {
         &self.non_terminals
     }
@@ -215,12 +238,99 @@ impl Grammar {
         true
     }
 
+    // builds a pushdown automaton that accepts the language of the grammar, both by
+    // final state and by empty stack; it predicts like the LL(1) parser, expanding the
+    // non-terminal on top of the stack, but tries every production instead of looking
+    // them up in the parsing table, so it works for any context free grammar
+    // (except left-recursive ones, which make it expand forever)
+    pub fn to_pda(&self) -> Result<Pda, String> {
+        // the bottom of the stack must not be a symbol of the grammar
+        let mut bottom = String::from(EOF_TOKEN);
+        while self.terminals.contains(&bottom) || self.non_terminals.contains(&bottom) {
+            bottom.push('\'');
+        }
+
+        let mut stack_alphabet: Set<String> =
+            self.terminals.union(&self.non_terminals).cloned().collect();
+        stack_alphabet.insert(bottom.clone());
+
+        // start by putting the start symbol on top of the bottom of the stack
+        let mut transitions = vec![PdaTransition {
+            from: PDA_START,
+            input: None,
+            pop: bottom.clone(),
+            to: PDA_LOOP,
+            push: vec![self.start_symbol.clone(), bottom.clone()],
+        }];
+
+        // expand a non-terminal into one of its productions
+        let mut productions: Vec<(&String, &Vec<String>)> = self.productions.iter().collect();
+        productions.sort();
+        for (non_terminal, productions) in productions {
+            for production in productions {
+                transitions.push(PdaTransition {
+                    from: PDA_LOOP,
+                    input: None,
+                    pop: non_terminal.clone(),
+                    to: PDA_LOOP,
+                    push: production
+                        .split_whitespace()
+                        .filter(|symbol| *symbol != EPSILON)
+                        .map(String::from)
+                        .collect(),
+                });
+            }
+        }
+
+        // match a terminal against the input
+        let mut terminals: Vec<&String> = self.terminals.iter().collect();
+        terminals.sort();
+        for terminal in terminals {
+            transitions.push(PdaTransition {
+                from: PDA_LOOP,
+                input: Some(terminal.clone()),
+                pop: terminal.clone(),
+                to: PDA_LOOP,
+                push: vec![],
+            });
+        }
+
+        // the stack is back to its bottom once the start symbol was derived
+        transitions.push(PdaTransition {
+            from: PDA_LOOP,
+            input: None,
+            pop: bottom.clone(),
+            to: PDA_ACCEPT,
+            push: vec![],
+        });
+
+        match Pda::from_parts(
+            self.terminals.clone(),
+            stack_alphabet,
+            Set::from([PDA_START, PDA_LOOP, PDA_ACCEPT]),
+            PDA_START,
+            bottom,
+            Set::from([PDA_ACCEPT]),
+            transitions,
+        ) {
+            Ok(pda) => Ok(pda),
+            Err(e) => {
+                let error = format!("could not build pushdown automaton: {}", e);
+                Err(error)
+            }
+        }
+    }
+
     fn parse_file(&mut self, file_path: &str) -> Result<(), String> {
         let mut lines = match open_file(file_path) {
             Ok(lines) => lines,
             Err(e) => return Err(e),
         };
 
+        self.parse_lines(&mut lines)
+    }
+
+    fn parse_lines<B: BufRead>(&mut self, lines: &mut Lines<B>) -> Result<(), String> {
         // parse non-terminals
         match self.parse_non_terminals(lines.next()) {
             Ok(_) => (),
@@ -240,7 +350,7 @@ impl Grammar {
         }
 
         // parse productions
-        match self.parse_productions(&mut lines) {
+        match self.parse_productions(lines) {
             Ok(_) => (),
             Err(e) => return Err(e),
         }
@@ -331,10 +441,7 @@ impl Grammar {
         Ok(())
     }
 
-    fn parse_productions(
-        &mut self,
-        productions: &mut Lines<BufReader<File>>,
-    ) -> Result<(), String> {
+    fn parse_productions<B: BufRead>(&mut self, productions: &mut Lines<B>) -> Result<(), String> {
         loop {
             // get the next line from the reader
             let line = match get_next_line(productions) {
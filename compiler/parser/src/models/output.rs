@@ -53,6 +53,16 @@ impl ParserOutput {
         self.nodes.push(node);
     }
 
+    // the symbols of the children of a node, in order; the binary only writes the tree
+    #[allow(dead_code)]
+    pub fn get_children(&self, node_index: usize) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .filter(|node| node.parent == Some(node_index))
+            .map(|node| node.symbol.as_str())
+            .collect()
+    }
+
     fn get_symbol(&self, node_index: Option<usize>) -> String {
         let node = match node_index {
             Some(index) => self.nodes.get(index),
@@ -1,13 +1,23 @@
 use super::grammar::Grammar;
 use super::output::ParserOutput;
 use hash_map::{HashMap, Table};
-use scanner::{Token, TokenKind};
+use scanner::{Constant, LexError, Span, Token, TokenKind};
 
 use crate::utils::constants::{EOF_TOKEN, EPSILON};
 
 const IDENTIFIER: &str = "Identifier";
 const CONSTANT: &str = "Constant";
 
+// the terminal of the grammar that matches a token: identifiers and constants
+// are matched by their kind, everything else by its representation
+pub fn terminal_symbol(token: &Token) -> &str {
+    match token.get_kind() {
+        TokenKind::Identifier => IDENTIFIER,
+        kind if kind.is_constant() => CONSTANT,
+        _ => token.get_inner(),
+    }
+}
+
 // maps a pair (non-terminal, terminal) to a production
 type ParsingTable = HashMap<(String, String), String>;
 
@@ -75,15 +85,46 @@ impl LL1Parser {
         }
     }
 
+    // parses tokens that were all scanned beforehand, the binary streams them instead
+    #[allow(dead_code)]
     pub fn parse(
         &self,
         tokens: &Vec<Token>,
         identifiers: &Table<String>,
-        constants: &Table<String>,
+        constants: &Table<Constant>,
     ) -> Result<ParserOutput, String> {
-        // TODO: find a way to break out of StatementList (&& on line 98)
-        let mut input = tokens.clone();
-        input.push(Token::new(TokenKind::EOF, EOF_TOKEN));
+        // get the value of the token from the identifier or constant table
+        let value = |token: &Token| match token.get_kind() {
+            TokenKind::Identifier => identifiers.get(&token.value()).cloned(),
+            kind if kind.is_constant() => constants.get(&token.value()).map(|c| c.text.clone()),
+            _ => None,
+        };
+
+        self.parse_tokens(tokens.iter().cloned().map(Ok), value)
+    }
+
+    // parses the tokens as the scanner produces them, i.e. `parse_stream(scanner.tokens())`,
+    // stopping at the first lexical error
+    pub fn parse_stream<I>(&self, tokens: I) -> Result<ParserOutput, String>
+    where
+        I: IntoIterator<Item = Result<Token, LexError>>,
+    {
+        self.parse_tokens(tokens, |_| None)
+    }
+
+    fn parse_tokens<I, F>(&self, tokens: I, value: F) -> Result<ParserOutput, String>
+    where
+        I: IntoIterator<Item = Result<Token, LexError>>,
+        F: Fn(&Token) -> Option<String>,
+    {
+        let mut input = TokenStream {
+            tokens: tokens.into_iter(),
+            last_span: None,
+        };
+        let mut current_token = match input.next() {
+            Ok(token) => token,
+            Err(e) => return Err(e),
+        };
 
         let mut stack = vec![
             String::from(EOF_TOKEN),
@@ -94,60 +135,60 @@ impl LL1Parser {
         let mut output = ParserOutput::new();
 
         while let Some(stack_top) = stack.last() {
-            if stack_top == EOF_TOKEN
-                || input
-                    .first()
-                    .map_or(false, |t| t.get_kind() == TokenKind::EOF)
-            {
-                // successfully parsed
+            if stack_top == EOF_TOKEN && current_token.get_kind() == TokenKind::EOF {
+                // successfully parsed, the grammar and the input ended together
                 return Ok(output);
             }
 
-            // get the current token
-            let current_token = match input.first() {
-                Some(token) => token,
-                None => {
-                    self.print_stack_trace(&stack);
-                    return Err(String::from("Parse error: could not get current token"));
-                }
-            };
-
             // get the terminal symbol value from the token
-            let token_symbol = match current_token.get_kind() {
-                TokenKind::Identifier => IDENTIFIER,
-                TokenKind::Constant => CONSTANT,
-                _ => current_token.get_inner(),
-            };
-
-            // get the value of the token from the identifier or constant table
-            let mut token_value = match current_token.get_kind() {
-                TokenKind::Identifier => identifiers.get(&current_token.value()).cloned(),
-                TokenKind::Constant => constants.get(&current_token.value()).cloned(),
-                _ => None,
-            };
+            let token_symbol = terminal_symbol(&current_token);
+
+            let mut token_value = value(&current_token);
             // if the token is not an identifier or constant, use the representation
             if token_value.is_none() {
                 token_value = Some(String::from(current_token.get_inner()));
             }
 
             match (stack_top.as_str(), &current_token.get_kind()) {
-                (_, TokenKind::EOF) | (EOF_TOKEN, _) => {
+                (EOF_TOKEN, _) => {
                     self.print_stack_trace(&stack);
-                    return Err(String::from("Parse error: unexpected end of input stream"));
+                    let error = format!(
+                        "Parse error on {}: expected end of input, found {:?}",
+                        current_token.get_span(),
+                        current_token.get_inner()
+                    );
+                    return Err(error);
+                }
+                (top, TokenKind::EOF) if self.grammar.get_terminals().contains(top) => {
+                    self.print_stack_trace(&stack);
+                    let error = format!(
+                        "Parse error on {}: unexpected end of input stream, expected {:?}",
+                        current_token.get_span(),
+                        top
+                    );
+                    return Err(error);
                 }
                 (top, _) if self.grammar.get_terminals().contains(top) => {
                     if top == token_symbol {
                         stack.pop(); // match terminal
-                        input.remove(0);
 
                         // add terminal node to output
                         let parent_index = parent_stack.last();
                         output.add_node(token_value, parent_index);
+
+                        current_token = match input.next() {
+                            Ok(token) => token,
+                            Err(e) => return Err(e),
+                        };
                     } else {
                         self.print_stack_trace(&stack);
 
-                        let error =
-                            format!("Parse error: expected {:?}, found {:?}", top, current_token);
+                        let error = format!(
+                            "Parse error on {}: expected {:?}, found {:?}",
+                            current_token.get_span(),
+                            top,
+                            current_token.get_inner()
+                        );
                         return Err(error);
                     }
                 }
@@ -180,8 +221,10 @@ impl LL1Parser {
                         self.print_stack_trace(&stack);
 
                         let error = format!(
-                            "Parse error: no rule for {:?} with {:?}",
-                            top, current_token
+                            "Parse error on {}: no rule for {:?} with {:?}",
+                            current_token.get_span(),
+                            top,
+                            current_token.get_inner()
                         );
                         return Err(error);
                     }
@@ -202,3 +245,42 @@ impl LL1Parser {
         }
     }
 }
+
+// the tokens to parse, followed by as many EOF tokens as asked for
+struct TokenStream<I> {
+    tokens: I,
+    last_span: Option<Span>,
+}
+
+impl<I> TokenStream<I>
+where
+    I: Iterator<Item = Result<Token, LexError>>,
+{
+    fn next(&mut self) -> Result<Token, String> {
+        loop {
+            match self.tokens.next() {
+                // comments kept by the scanner are not part of the grammar, and the end of the
+                // input is added below even if the scanner has an EOF token for its trivia
+                Some(Ok(token))
+                    if token.get_kind().is_trivia() || token.get_kind() == TokenKind::EOF =>
+                {
+                    continue
+                }
+                Some(Ok(token)) => {
+                    self.last_span = Some(token.get_span());
+                    return Ok(token);
+                }
+                Some(Err(e)) => return Err(e.to_string()),
+                None => {
+                    // the end of input is right after the last token
+                    let mut eof = Token::new(TokenKind::EOF, EOF_TOKEN);
+                    if let Some(span) = self.last_span {
+                        let end = Span::new(span.offset + span.length, 0, span.end, span.end);
+                        eof.set_span(end);
+                    }
+                    return Ok(eof);
+                }
+            }
+        }
+    }
+}
@@ -1 +1,2 @@
 mod grammar;
+mod pda;
@@ -20,6 +20,26 @@ fn test_cfg() {
     }
 }
 
+#[test]
+fn test_from_reader() {
+    // a grammar read from memory is the same as the one read from its file
+    let grammar = Grammar::new("input/ll_pass.in").unwrap();
+    let from_reader =
+        Grammar::from_reader(include_str!("../../input/ll_pass.in").as_bytes()).unwrap();
+
+    assert_eq!(from_reader.get_non_terminals(), grammar.get_non_terminals());
+    assert_eq!(from_reader.get_terminals(), grammar.get_terminals());
+    assert_eq!(from_reader.get_start_symbol(), grammar.get_start_symbol());
+    for non_terminal in grammar.get_non_terminals() {
+        assert_eq!(
+            from_reader.get_production(non_terminal),
+            grammar.get_production(non_terminal)
+        );
+    }
+
+    assert!(Grammar::from_reader("".as_bytes()).is_err());
+}
+
 #[test]
 fn test_pass_1() {
     let grammar = Grammar::new("input/pass_1.in").unwrap();
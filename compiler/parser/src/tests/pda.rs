@@ -0,0 +1,162 @@
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use automata::Acceptance;
+#[allow(unused_imports)]
+use hash_map::Table;
+#[allow(unused_imports)]
+use scanner::{Scanner, Token, TokenKind};
+
+#[allow(unused_imports)]
+use crate::models::grammar::Grammar;
+#[allow(unused_imports)]
+use crate::models::parser::{terminal_symbol, LL1Parser};
+
+#[test]
+fn test_to_pda() {
+    let grammar = Grammar::new("input/balanced.in").unwrap();
+    let pda = grammar.to_pda().unwrap();
+
+    // every sequence of parentheses up to 8 long
+    for length in 0..=8 {
+        for bits in 0..(1 << length) {
+            let input: Vec<&str> = (0..length)
+                .map(|index| if bits & (1 << index) == 0 { "(" } else { ")" })
+                .collect();
+
+            let mut depth: i32 = 0;
+            let mut balanced = true;
+            for symbol in &input {
+                depth += if *symbol == "(" { 1 } else { -1 };
+                balanced &= depth >= 0;
+            }
+            balanced &= depth == 0;
+
+            assert_eq!(pda.accepts(&input, Acceptance::FinalState), Ok(balanced));
+            assert_eq!(pda.accepts(&input, Acceptance::EmptyStack), Ok(balanced));
+        }
+    }
+}
+
+#[test]
+fn test_cross_check_parser() {
+    let grammar = Grammar::new("input/balanced.in").unwrap();
+    let pda = grammar.to_pda().unwrap();
+    let parser = LL1Parser::new(grammar);
+
+    let identifiers = Table::new();
+    let constants = Table::new();
+
+    for length in 0..=6 {
+        for bits in 0..(1 << length) {
+            let tokens: Vec<Token> = (0..length)
+                .map(|index| match bits & (1 << index) {
+                    0 => Token::new(TokenKind::ParenOpen, "("),
+                    _ => Token::new(TokenKind::ParenClose, ")"),
+                })
+                .collect();
+            let symbols: Vec<&str> = tokens.iter().map(terminal_symbol).collect();
+
+            let accepted = pda.accepts(&symbols, Acceptance::FinalState).unwrap();
+            let parsed = parser.parse(&tokens, &identifiers, &constants).is_ok();
+            assert_eq!(accepted, parsed, "{:?}", symbols);
+        }
+    }
+}
+
+#[test]
+fn test_cross_check_program() {
+    let grammar = Grammar::new("input/grammar.in").unwrap();
+    let pda = grammar.to_pda().unwrap();
+    let parser = LL1Parser::new(grammar);
+
+    for program in ["p1", "p2", "p3"] {
+        let mut scanner = Scanner::new().unwrap();
+        scanner
+            .scan(&format!("../../programs/{}.oli", program))
+            .unwrap();
+
+        let tokens = scanner.get_token_list();
+        let symbols: Vec<&str> = tokens.iter().map(terminal_symbol).collect();
+
+        assert_eq!(
+            pda.accepts(&symbols, Acceptance::FinalState),
+            Ok(true),
+            "{}",
+            program
+        );
+        assert!(parser
+            .parse(
+                tokens,
+                scanner.get_identifier_table(),
+                scanner.get_constant_table(),
+            )
+            .is_ok());
+
+        // without its last ';' the program is incomplete
+        let incomplete = &symbols[..symbols.len() - 1];
+        assert_eq!(
+            pda.accepts(incomplete, Acceptance::FinalState),
+            Ok(false),
+            "{}",
+            program
+        );
+        assert!(parser
+            .parse(
+                &tokens[..tokens.len() - 1].to_vec(),
+                scanner.get_identifier_table(),
+                scanner.get_constant_table(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn test_stream_with_comments() {
+    let grammar = Grammar::new("input/grammar.in").unwrap();
+    let parser = LL1Parser::new(grammar);
+
+    // the comments the scanner keeps are skipped by the parser, even inside a statement
+    let program = "--- the answer\na: number = 42; {- b: number -}\nc: number {- c -} = -- one\n1;";
+    let mut scanner = Scanner::from_str(program).unwrap();
+    scanner.set_keep_comments(true);
+    assert!(parser.parse_stream(scanner.tokens()).is_ok());
+
+    // but they are not a part of it either
+    let mut scanner = Scanner::from_str("a: number = {- 42 -};").unwrap();
+    scanner.set_keep_comments(true);
+    assert!(parser.parse_stream(scanner.tokens()).is_err());
+}
+
+#[test]
+fn test_unary_minus() {
+    let grammar = Grammar::new("input/grammar.in").unwrap();
+    let parser = LL1Parser::new(grammar);
+
+    // numbers have no sign, a minus before a factor negates it; the minus between
+    // two terms stays a subtraction
+    for (program, negations) in [
+        ("a: number = -5;", 1),
+        ("a = 1 - -2 * - -0x10;", 3),
+        ("output(-(b - -1.5e3));", 2),
+        ("a = 1 - 2;", 0),
+    ] {
+        let mut scanner = Scanner::from_str(program).unwrap();
+        let output = parser.parse_stream(scanner.tokens()).unwrap();
+
+        let negated = (0..output.len())
+            .filter(|&node| output.get_children(node) == ["-", "Factor"])
+            .count();
+        assert_eq!(negated, negations, "{}", program);
+    }
+
+    for program in ["a = 1 * -;", "a = - * 1;"] {
+        let mut scanner = Scanner::from_str(program).unwrap();
+        assert!(
+            parser.parse_stream(scanner.tokens()).is_err(),
+            "{}",
+            program
+        );
+    }
+}
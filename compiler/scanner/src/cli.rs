@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::models::unicode::CharacterSet;
+use crate::utils::writer::OutputFormat;
+
+// programs can be named instead of given by path, i.e. "p1" for "programs/p1.oli";
+// paths are relative to the crate, so the scanner can be run from any directory
+const PROGRAM_FILE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/");
+const PROGRAM_EXTENSION: &str = ".oli";
+const DEFAULT_PROGRAM: &str = "p3";
+const OUTPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/output/");
+
+// reads the program from stdin, or writes the output to stdout
+pub const STANDARD_STREAM: &str = "-";
+// the name stdin is written under in an output directory
+const STDIN_NAME: &str = "stdin";
+const GLOB_CHARACTERS: [char; 3] = ['*', '?', '['];
+
+pub const USAGE: &str = "Usage: cargo run -- [<options>] [<input>...]
+
+Scans every input and writes its result to <output>/<name>/<file>, where <name> is
+the name of the input without its extension and <file> depends on the format.
+An input is a path, a glob such as \"programs/*.oli\", the name of a program in
+programs/ such as \"p1\", or - for stdin. Without inputs, p3 is scanned.
+
+Stdin is printed token by token as it is read, unless a format or an output
+directory is given.
+
+Options:
+  -f, --format <format>    text, json, csv or pif, text by default
+  -o, --output <dir | ->   the output directory, output/ in the crate by default,
+                           or - for stdout
+  -q, --quiet              only print lexical errors and failures
+  -l, --error-limit <n>    stop a file after its first n lexical errors
+      --unicode            let identifiers use any Unicode letters
+      --comments           keep comments as tokens
+      --lossless           keep whitespace and comments as the trivia of the tokens,
+                           the source comes back unchanged only if it is UTF-8
+  -h, --help               print this message
+
+Exits with 0 if every input is lexically correct, 1 if some have lexical errors,
+and 2 if an input could not be scanned or its result could not be written.";
+
+pub struct Options {
+    pub inputs: Vec<String>,
+    pub output: Option<String>,
+    // stdin is printed token by token unless a format or an output directory is given
+    pub format: Option<OutputFormat>,
+    pub quiet: bool,
+    pub error_limit: Option<usize>,
+    pub character_set: CharacterSet,
+    pub keep_comments: bool,
+    pub lossless: bool,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            inputs: vec![],
+            output: None,
+            format: None,
+            quiet: false,
+            error_limit: None,
+            character_set: CharacterSet::Ascii,
+            keep_comments: false,
+            lossless: false,
+            help: false,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Input {
+    Stdin,
+    // the path of the program, and the name its output is written under
+    File { path: String, name: String },
+}
+
+impl Input {
+    pub fn name(&self) -> &str {
+        match self {
+            Input::Stdin => STDIN_NAME,
+            Input::File { name, .. } => name,
+        }
+    }
+
+    // how the input is referred to in messages
+    pub fn describe(&self) -> &str {
+        match self {
+            Input::Stdin => STDIN_NAME,
+            Input::File { path, .. } => path,
+        }
+    }
+}
+
+// the arguments without the name of the program
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => options.help = true,
+            "-q" | "--quiet" => options.quiet = true,
+            "--unicode" => options.character_set = CharacterSet::Unicode,
+            "--comments" => options.keep_comments = true,
+            "--lossless" => options.lossless = true,
+            "-f" | "--format" => {
+                let format = match value(arg, args.next()) {
+                    Ok(format) => format,
+                    Err(e) => return Err(e),
+                };
+                options.format = match OutputFormat::from_str(format) {
+                    Ok(format) => Some(format),
+                    Err(e) => return Err(e),
+                };
+            }
+            "-o" | "--output" => {
+                options.output = match value(arg, args.next()) {
+                    Ok(output) => Some(String::from(output)),
+                    Err(e) => return Err(e),
+                };
+            }
+            "-l" | "--error-limit" => {
+                let limit = match value(arg, args.next()) {
+                    Ok(limit) => limit,
+                    Err(e) => return Err(e),
+                };
+                options.error_limit = match limit.parse::<usize>() {
+                    Ok(0) | Err(_) => {
+                        let error =
+                            format!("the error limit must be a positive number, not '{}'", limit);
+                        return Err(error);
+                    }
+                    Ok(limit) => Some(limit),
+                };
+            }
+            // everything after -- is an input, even if it starts with a dash
+            "--" => options.inputs.extend(args.by_ref().cloned()),
+            option if option.starts_with('-') && option != STANDARD_STREAM => {
+                let error = format!("unknown option '{}'", option);
+                return Err(error);
+            }
+            input => options.inputs.push(String::from(input)),
+        }
+    }
+
+    if options.inputs.is_empty() {
+        options.inputs.push(String::from(DEFAULT_PROGRAM));
+    }
+
+    Ok(options)
+}
+
+fn value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, String> {
+    match value {
+        Some(value) => Ok(value),
+        None => {
+            let error = format!("{} needs a value", option);
+            Err(error)
+        }
+    }
+}
+
+// the programs the inputs stand for, in the order they were given; globs are
+// expanded in alphabetical order
+pub fn resolve_inputs(inputs: &[String]) -> Result<Vec<Input>, String> {
+    let mut resolved = vec![];
+
+    for input in inputs {
+        if input == STANDARD_STREAM {
+            resolved.push(Input::Stdin);
+            continue;
+        }
+
+        if !input.contains(GLOB_CHARACTERS) {
+            match resolve_path(input) {
+                Ok(input) => resolved.push(input),
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+
+        let paths = match glob::glob(input) {
+            Ok(paths) => paths,
+            Err(e) => {
+                let error = format!("invalid glob '{}': {}", input, e);
+                return Err(error);
+            }
+        };
+
+        let mut matches = vec![];
+        for path in paths {
+            match path {
+                Ok(path) if path.is_file() => matches.push(file_input(&path)),
+                Ok(_) => {}
+                Err(e) => {
+                    let error = format!("could not read '{}': {}", input, e);
+                    return Err(error);
+                }
+            }
+        }
+
+        if matches.is_empty() {
+            let error = format!("no files match '{}'", input);
+            return Err(error);
+        }
+        resolved.extend(matches);
+    }
+
+    Ok(resolved)
+}
+
+fn resolve_path(input: &str) -> Result<Input, String> {
+    let path = Path::new(input);
+    if path.is_file() {
+        return Ok(file_input(path));
+    }
+    if path.exists() {
+        let error = format!("'{}' is not a file", input);
+        return Err(error);
+    }
+
+    // the name of a program in programs/
+    let program = format!("{}{}{}", PROGRAM_FILE_PATH, input, PROGRAM_EXTENSION);
+    let program = Path::new(&program);
+    if !input.contains(['/', '\\']) && program.is_file() {
+        return Ok(Input::File {
+            path: String::from(program.to_string_lossy()),
+            name: String::from(input),
+        });
+    }
+
+    let error = format!("no such file '{}'", input);
+    Err(error)
+}
+
+fn file_input(path: &Path) -> Input {
+    let name = match path.file_stem() {
+        Some(name) => name.to_string_lossy(),
+        None => path.to_string_lossy(),
+    };
+
+    Input::File {
+        path: String::from(path.to_string_lossy()),
+        name: String::from(name),
+    }
+}
+
+// the file the output of every input is written to, None for stdout; inputs
+// with the same name would overwrite each other's output
+pub fn output_paths(
+    inputs: &[Input],
+    output: Option<&str>,
+    file_name: &str,
+) -> Result<Vec<Option<String>>, String> {
+    let mut paths = vec![];
+    let mut written: HashMap<String, &Input> = HashMap::new();
+
+    for input in inputs {
+        let directory = match (output, input) {
+            (Some(STANDARD_STREAM), _) | (None, Input::Stdin) => {
+                paths.push(None);
+                continue;
+            }
+            (Some(directory), _) => format!("{}/{}", directory.trim_end_matches('/'), input.name()),
+            (None, _) => format!("{}{}", OUTPUT_DIR, input.name()),
+        };
+
+        let path = format!("{}/{}", directory, file_name);
+        if let Some(other) = written.insert(path.clone(), input) {
+            let error = format!(
+                "'{}' and '{}' would both be written to {}",
+                other.describe(),
+                input.describe(),
+                path
+            );
+            return Err(error);
+        }
+        paths.push(Some(path));
+    }
+
+    Ok(paths)
+}
+
+// makes the directory of an output file
+pub fn create_output_dir(output_path: &str) -> Result<(), String> {
+    let directory = match Path::new(output_path).parent() {
+        Some(directory) => directory,
+        None => return Ok(()),
+    };
+
+    match fs::create_dir_all(directory) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            let error = format!("could not create output directory: {}", e);
+            Err(error)
+        }
+    }
+}
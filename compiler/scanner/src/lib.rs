@@ -1,6 +1,15 @@
 mod models;
 mod utils;
 
-pub use models::scanner::Scanner;
+pub use models::automata::{Automata, SEARCH_PATH_VARIABLE};
+pub use models::edit::Edit;
+pub use models::error::{LexError, LexErrorKind};
+pub use models::lexer::Lexer;
+pub use models::literal::{Constant, ConstantValue};
+pub use models::scanner::{Scanner, Tokens};
+pub use models::span::{Position, Span};
 pub use models::token::Token;
 pub use models::token::TokenKind;
+pub use models::trivia::{Trivia, TriviaKind};
+pub use models::unicode::CharacterSet;
+pub use utils::writer::{OutputFormat, ScanOutput, ScanWriter};
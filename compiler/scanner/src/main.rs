@@ -1,88 +1,187 @@
+mod cli;
 mod models;
+mod tests;
 mod utils;
 
+use cli::{Input, Options, USAGE};
 use models::scanner::Scanner;
-use std::{env, fs};
-
-const PROGRAM_FILE_PATH: &str = "../../programs/";
-const PROGRAM_EXTENSION: &str = ".oli";
-const DEFAULT_PROGRAM: &str = "p3";
-
-const OUTPUT_DIR: &str = "output/";
-const OUTPUT_FILE_PATH: &str = "/tokens";
-const OUTPUT_EXTENSION: &str = ".out";
-
-fn get_program_path(program: &str) -> String {
-    let mut program_path = String::from(PROGRAM_FILE_PATH);
-    program_path.push_str(program);
-    program_path.push_str(PROGRAM_EXTENSION);
-
-    program_path
-}
-
-fn get_output_path(program: &str) -> Result<String, String> {
-    let mut output_path = String::from(OUTPUT_DIR);
-    output_path.push_str(program);
-
-    match fs::create_dir_all(&output_path) {
-        Ok(_) => {}
+use std::env;
+use std::io;
+use std::process::ExitCode;
+
+// the exit codes, the worst outcome of any input wins
+const LEXICALLY_CORRECT: u8 = 0;
+const LEXICALLY_INCORRECT: u8 = 1;
+const FAILURE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match cli::parse_args(&args) {
+        Ok(options) => options,
         Err(e) => {
-            let error = format!("could not create output directory: {}", e);
-            return Err(error);
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(FAILURE);
         }
-    }
-
-    output_path.push_str(OUTPUT_FILE_PATH);
-    output_path.push_str(OUTPUT_EXTENSION);
-
-    Ok(output_path)
-}
+    };
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() > 2 {
-        println!("Usage: cargo run <program name>");
-        return;
+    if options.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
     }
 
-    let program = if args.len() == 1 {
-        DEFAULT_PROGRAM
-    } else {
-        &args[1]
+    let inputs = match cli::resolve_inputs(&options.inputs) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(FAILURE);
+        }
     };
 
-    let input_path = get_program_path(program);
-    let output_path = match get_output_path(program) {
-        Ok(output_path) => output_path,
+    let format = options.format.unwrap_or_default();
+    let output_paths = match cli::output_paths(
+        &inputs,
+        options.output.as_deref(),
+        format.writer().file_name(),
+    ) {
+        Ok(output_paths) => output_paths,
         Err(e) => {
-            println!("{}", e);
-            return;
+            eprintln!("{}", e);
+            return ExitCode::from(FAILURE);
         }
     };
 
+    let mut status = LEXICALLY_CORRECT;
+    for (input, output_path) in inputs.iter().zip(output_paths) {
+        // stdin is streamed only when its result goes to stdout in no particular format
+        let result = match (input, options.format, &output_path) {
+            (Input::Stdin, None, None) => scan_stdin(&options),
+            _ => scan_input(&options, input, output_path),
+        };
+
+        let input_status = match result {
+            Ok(true) => LEXICALLY_CORRECT,
+            Ok(false) => LEXICALLY_INCORRECT,
+            Err(e) => {
+                eprintln!("{}", e);
+                FAILURE
+            }
+        };
+        status = status.max(input_status);
+    }
+
+    ExitCode::from(status)
+}
+
+fn new_scanner(options: &Options) -> Result<Scanner, String> {
     let mut scanner = match Scanner::new() {
         Ok(scanner) => scanner,
-        Err(e) => {
-            println!("{}", e);
-            return;
-        }
+        Err(e) => return Err(e),
     };
 
-    match scanner.scan(input_path.as_str()) {
+    scanner.set_error_limit(options.error_limit);
+    scanner.set_character_set(options.character_set);
+    scanner.set_keep_comments(options.keep_comments);
+    scanner.set_lossless(options.lossless);
+
+    Ok(scanner)
+}
+
+// scans the input and writes its result, returns whether it is lexically correct
+fn scan_input(
+    options: &Options,
+    input: &Input,
+    output_path: Option<String>,
+) -> Result<bool, String> {
+    let mut scanner = match new_scanner(options) {
+        Ok(scanner) => scanner,
+        Err(e) => return Err(e),
+    };
+
+    // the scan is not announced if its result goes to stdout
+    if !options.quiet && output_path.is_some() {
+        println!("Scanning '{}'", input.describe());
+    }
+
+    let scanned = match input {
+        Input::Stdin => scanner.scan_reader(io::stdin()),
+        Input::File { path, .. } => scanner.scan(path),
+    };
+    match scanned {
         Ok(_) => {}
         Err(e) => {
-            println!("{}", e);
-            return;
+            let error = format!("{}: {}", input.describe(), e);
+            return Err(error);
         }
     }
 
-    match scanner.write_result(output_path.as_str()) {
-        Ok(_) => {}
-        Err(e) => {
-            println!("{}", e);
-            return;
+    for error in scanner.get_errors() {
+        eprintln!("{}: {}", input.describe(), error);
+    }
+
+    let format = options.format.unwrap_or_default();
+    match output_path {
+        Some(output_path) => {
+            match cli::create_output_dir(&output_path) {
+                Ok(_) => {}
+                Err(e) => return Err(e),
+            }
+            match scanner.write_result(&output_path, format) {
+                Ok(_) => {}
+                Err(e) => return Err(e),
+            }
+            if !options.quiet {
+                println!("Output written to {}", output_path);
+            }
+        }
+        None => match format.writer().format(&scanner.output()) {
+            Ok(text) => print!("{}", text),
+            Err(e) => return Err(e),
+        },
+    }
+
+    Ok(scanner.get_errors().is_empty())
+}
+
+// prints the tokens of stdin as they are read, returns whether it is lexically correct
+fn scan_stdin(options: &Options) -> Result<bool, String> {
+    let mut scanner = match new_scanner(options) {
+        Ok(scanner) => scanner,
+        Err(e) => return Err(e),
+    };
+    scanner.set_reader(io::stdin());
+
+    let mut error_count = 0;
+    let mut tokens = scanner.tokens();
+    while let Some(result) = tokens.next() {
+        match result {
+            Ok(token) => {
+                let kind = tokens.get_lexer().kind_name(token.get_kind());
+                // comments may hold anything, i.e. tabs
+                match token.get_kind().is_trivia() {
+                    true => println!("{} {} {:?}", token.get_span(), kind, token.get_inner()),
+                    false => println!("{} {} {}", token.get_span(), kind, token.get_inner()),
+                }
+
+                if let Some(documentation) = token.get_documentation() {
+                    println!("  documented by {:?}", documentation);
+                }
+                for trivia in token.get_leading_trivia() {
+                    println!("  leading {:?} {:?}", trivia.kind, trivia.text);
+                }
+                for trivia in token.get_trailing_trivia() {
+                    println!("  trailing {:?} {:?}", trivia.kind, trivia.text);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", Input::Stdin.describe(), e);
+
+                error_count += 1;
+                if Some(error_count) == options.error_limit {
+                    break;
+                }
+            }
         }
     }
 
-    println!("\nOutput written to {}", output_path)
+    Ok(error_count == 0)
 }
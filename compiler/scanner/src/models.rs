@@ -1,3 +1,11 @@
 pub mod automata;
+pub mod edit;
+pub mod error;
+pub mod lexer;
+pub mod literal;
 pub mod scanner;
+pub mod source;
+pub mod span;
 pub mod token;
+pub mod trivia;
+pub mod unicode;
@@ -1,81 +1,176 @@
-use super::token::TokenKind;
+use std::env;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
 use automata::Automaton;
 
+// the lexical automata, referred to by name from the lexer specification
+const AUTOMATA: [&str; 8] = [
+    "identifier",
+    "number",
+    "hex_number",
+    "octal_number",
+    "binary_number",
+    "decimal_number",
+    "char",
+    "string",
+];
+
+// the .dfa files are embedded in the binary, so the scanner does not
+// depend on the directory it is run from
+const EMBEDDED: [(&str, &str); 8] = [
+    (
+        "identifier",
+        include_str!("../../../automata/input/identifier.dfa"),
+    ),
+    ("number", include_str!("../../../automata/input/number.dfa")),
+    (
+        "hex_number",
+        include_str!("../../../automata/input/hex_number.dfa"),
+    ),
+    (
+        "octal_number",
+        include_str!("../../../automata/input/octal_number.dfa"),
+    ),
+    (
+        "binary_number",
+        include_str!("../../../automata/input/binary_number.dfa"),
+    ),
+    (
+        "decimal_number",
+        include_str!("../../../automata/input/decimal_number.dfa"),
+    ),
+    ("char", include_str!("../../../automata/input/char.dfa")),
+    ("string", include_str!("../../../automata/input/string.dfa")),
+];
+
+// directories searched for "<name>.dfa" before falling back to the embedded files
+pub const SEARCH_PATH_VARIABLE: &str = "OLIVE_AUTOMATA_PATH";
+const DFA_EXTENSION: &str = ".dfa";
+
+// the lexical automata by name, the lexer combines them with the other patterns
 pub struct Automata {
-    is_identifier: Automaton,
-    is_number: Automaton,
-    is_char: Automaton,
-    is_string: Automaton,
+    automata: Vec<(&'static str, Automaton)>,
 }
 
 impl Automata {
+    // uses the search path from the environment if it is set, the embedded automata otherwise
     pub fn new() -> Result<Self, String> {
-        let is_identifier = match Automaton::new("../automata/input/identifier.dfa") {
-            Ok(automaton) => automaton,
-            Err(e) => {
-                let error = format!("[identifier] {}", e);
-                return Err(error);
-            }
-        };
-        let is_number = match Automaton::new("../automata/input/number.dfa") {
-            Ok(automaton) => automaton,
-            Err(e) => {
-                let error = format!("[number] {}", e);
-                return Err(error);
-            }
-        };
-        let is_char = match Automaton::new("../automata/input/char.dfa") {
-            Ok(automaton) => automaton,
-            Err(e) => {
-                let error = format!("[char] {}", e);
-                return Err(error);
-            }
-        };
-        let is_string = match Automaton::new("../automata/input/string.dfa") {
-            Ok(automaton) => automaton,
-            Err(e) => {
-                let error = format!("[string] {}", e);
-                return Err(error);
-            }
-        };
-
-        let automata = Self {
-            is_identifier,
-            is_number,
-            is_char,
-            is_string,
-        };
-
-        Ok(automata)
+        Self::from_variable(env::var_os(SEARCH_PATH_VARIABLE))
     }
 
-    fn is_identifier(&self, value: &str) -> bool {
-        self.is_identifier.validate(value)
+    // takes the value of the search path variable, if it is set
+    pub fn from_variable(search_path: Option<OsString>) -> Result<Self, String> {
+        match search_path {
+            Some(search_path) => {
+                let directories: Vec<PathBuf> = env::split_paths(&search_path).collect();
+                Self::from_search_path(&directories)
+            }
+            None => Self::embedded(),
+        }
     }
 
-    fn is_number(&self, value: &str) -> bool {
-        self.is_number.validate(value)
+    pub fn embedded() -> Result<Self, String> {
+        Self::from_strs(&EMBEDDED)
     }
 
-    fn is_char(&self, value: &str) -> bool {
-        self.is_char.validate(value)
+    // takes the source of every lexical automaton, by name
+    pub fn from_strs(sources: &[(&str, &str)]) -> Result<Self, String> {
+        let readers = sources
+            .iter()
+            .map(|(name, source)| (*name, source.as_bytes()))
+            .collect();
+
+        Self::from_readers(readers)
     }
 
-    fn is_string(&self, value: &str) -> bool {
-        self.is_string.validate(value)
+    // takes a reader for every lexical automaton, by name
+    pub fn from_readers<R: Read>(readers: Vec<(&str, R)>) -> Result<Self, String> {
+        let mut readers: Vec<(&str, Option<R>)> = readers
+            .into_iter()
+            .map(|(name, reader)| (name, Some(reader)))
+            .collect();
+
+        let mut automata = vec![];
+        for name in AUTOMATA {
+            let reader = match readers.iter_mut().find(|(other, _)| *other == name) {
+                Some((_, reader)) => reader.take(),
+                None => None,
+            };
+            let reader = match reader {
+                Some(reader) => reader,
+                None => {
+                    let error = format!("[{}] missing automaton", name);
+                    return Err(error);
+                }
+            };
+
+            let file_name = format!("{}{}", name, DFA_EXTENSION);
+            let automaton = match Automaton::from_reader(reader) {
+                Ok(automaton) => automaton,
+                Err(e) => {
+                    let error = format!("[{}]\n{}", name, e.with_path(&file_name).diagnostic());
+                    return Err(error);
+                }
+            };
+            automata.push((name, automaton));
+        }
+
+        Ok(Self { automata })
     }
 
-    pub fn classify(&self, value: &str) -> TokenKind {
-        if self.is_identifier(value) {
-            TokenKind::Identifier
-        } else if self.is_number(value) {
-            TokenKind::Constant
-        } else if self.is_char(value) {
-            TokenKind::Constant
-        } else if self.is_string(value) {
-            TokenKind::Constant
-        } else {
-            TokenKind::Unknown
+    // loads every lexical automaton from the first directory that has its .dfa file,
+    // falling back to the embedded file if none of them has it
+    pub fn from_search_path<P: AsRef<Path>>(directories: &[P]) -> Result<Self, String> {
+        let mut automata = vec![];
+        for name in AUTOMATA {
+            let embedded = match EMBEDDED.iter().find(|(other, _)| *other == name) {
+                Some((_, embedded)) => embedded,
+                None => {
+                    let error = format!("[{}] missing automaton", name);
+                    return Err(error);
+                }
+            };
+
+            let file_name = format!("{}{}", name, DFA_EXTENSION);
+            let file_path = directories
+                .iter()
+                .map(|directory| directory.as_ref().join(&file_name))
+                .find(|file_path| file_path.is_file());
+
+            let result = match file_path {
+                Some(file_path) => match File::open(&file_path) {
+                    Ok(file) => Automaton::from_reader(file)
+                        .map_err(|e| e.with_path(&file_path.to_string_lossy())),
+                    Err(e) => {
+                        let error = format!("[{}] could not open file: {}", name, e);
+                        return Err(error);
+                    }
+                },
+                None => {
+                    Automaton::from_reader(embedded.as_bytes()).map_err(|e| e.with_path(&file_name))
+                }
+            };
+
+            let automaton: Automaton = match result {
+                Ok(automaton) => automaton,
+                Err(e) => {
+                    let error = format!("[{}]\n{}", name, e.diagnostic());
+                    return Err(error);
+                }
+            };
+            automata.push((name, automaton));
         }
+
+        Ok(Self { automata })
+    }
+
+    pub fn get_automaton(&self, name: &str) -> Option<&Automaton> {
+        self.automata
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|(_, automaton)| automaton)
     }
 }
@@ -0,0 +1,44 @@
+// a change to the source, as an editor reports it: the bytes removed at an offset,
+// and the text inserted in their place
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Edit {
+    pub offset: usize,
+    pub removed: usize,
+    pub inserted: String,
+}
+
+// built by editors through the library, the binary never edits
+#[allow(dead_code)]
+impl Edit {
+    pub fn new(offset: usize, removed: usize, inserted: &str) -> Self {
+        Self {
+            offset,
+            removed,
+            inserted: String::from(inserted),
+        }
+    }
+
+    // the source after the edit
+    pub fn apply(&self, text: &str) -> Result<String, String> {
+        let end = self.offset + self.removed;
+        if end > text.len() || !text.is_char_boundary(self.offset) || !text.is_char_boundary(end) {
+            let error = format!(
+                "cannot remove {} bytes at offset {} from a source of {} bytes",
+                self.removed,
+                self.offset,
+                text.len()
+            );
+            return Err(error);
+        }
+
+        let mut edited = String::from(&text[..self.offset]);
+        edited.push_str(&self.inserted);
+        edited.push_str(&text[end..]);
+        Ok(edited)
+    }
+
+    // the number of bytes the source grew by, negative if it shrank
+    pub fn delta(&self) -> isize {
+        self.inserted.len() as isize - self.removed as isize
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::{self, Display};
+
+use super::span::Span;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LexErrorKind {
+    // a lexeme that is not a token
+    UndefinedToken(String),
+    // a character that cannot be seen, i.e. a zero width space or a bidirectional control
+    InvisibleCharacter(char),
+    // an identifier that may be mistaken for another one
+    ConfusableIdentifier(String),
+    // a char or string that is not closed before the end of its line
+    UnterminatedLiteral(String),
+    // a backslash followed by a character that has no escape
+    InvalidEscape(String),
+    // a number larger than 2^63, the magnitude of the smallest 64-bit number
+    NumberOutOfRange(String),
+    // a block comment that is not closed before the end of the source
+    UnterminatedComment,
+    // the source could not be read any further
+    Unreadable(String),
+}
+
+// an error found while scanning, along with where it was found
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LexError {
+    kind: LexErrorKind,
+    span: Span,
+}
+
+impl LexError {
+    pub fn new(kind: LexErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn get_kind(&self) -> &LexErrorKind {
+        &self.kind
+    }
+
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start = self.span.start;
+        match &self.kind {
+            LexErrorKind::UndefinedToken(lexeme) => write!(
+                f,
+                "Lexical error on line {}, column {} => undefined token: {}",
+                start.line, start.column, lexeme
+            ),
+            LexErrorKind::InvisibleCharacter(character) => write!(
+                f,
+                "Lexical error on line {}, column {} => invisible character: U+{:04X}",
+                start.line, start.column, *character as u32
+            ),
+            LexErrorKind::ConfusableIdentifier(identifier) => write!(
+                f,
+                "Lexical error on line {}, column {} => confusable identifier: {}",
+                start.line, start.column, identifier
+            ),
+            LexErrorKind::UnterminatedLiteral(lexeme) => write!(
+                f,
+                "Lexical error on line {}, column {} => unterminated literal: {}",
+                start.line, start.column, lexeme
+            ),
+            LexErrorKind::InvalidEscape(escape) => write!(
+                f,
+                "Lexical error on line {}, column {} => invalid escape: {}",
+                start.line, start.column, escape
+            ),
+            LexErrorKind::NumberOutOfRange(number) => write!(
+                f,
+                "Lexical error on line {}, column {} => number out of range: {}",
+                start.line, start.column, number
+            ),
+            LexErrorKind::UnterminatedComment => write!(
+                f,
+                "Lexical error on line {}, column {} => unterminated comment",
+                start.line, start.column
+            ),
+            LexErrorKind::Unreadable(message) => write!(
+                f,
+                "Read error on line {}, column {} => {}",
+                start.line, start.column, message
+            ),
+        }
+    }
+}
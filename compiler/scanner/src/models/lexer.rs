@@ -0,0 +1,270 @@
+use std::str::FromStr;
+
+use super::automata::Automata;
+use super::token::TokenKind;
+use automata::{Automaton, CompiledMoore, Moore};
+
+// the lexer specification, along with the files it includes, embedded in the binary
+const SPECIFICATION: (&str, &str) = (
+    "lexer.in",
+    include_str!("../../../../specification/lexer.in"),
+);
+const INCLUDES: [(&str, &str); 1] = [(
+    "token.in",
+    include_str!("../../../../specification/token.in"),
+)];
+
+const COMMENT: &str = "--";
+const INCLUDE: &str = "include";
+const DFA: &str = "dfa";
+
+// the automaton of a pattern, which is built from a literal or a regex,
+// or is one of the lexical automata
+enum Pattern<'a> {
+    Built(Box<Automaton>),
+    Named(&'a Automaton),
+}
+
+impl<'a> Pattern<'a> {
+    fn automaton(&self) -> &Automaton {
+        match self {
+            Pattern::Built(automaton) => automaton,
+            Pattern::Named(automaton) => automaton,
+        }
+    }
+}
+
+// every pattern of the specification is turned into an automaton, then all of them
+// are combined into a single machine whose output is the kind of the first pattern
+// that accepts the lexeme, so earlier patterns win ties
+pub struct Lexer {
+    machine: CompiledMoore<TokenKind>,
+    // the names of the kinds only the specification gives, in the order of their codes
+    defined: Vec<String>,
+}
+
+impl Lexer {
+    pub fn embedded(automata: &Automata) -> Result<Self, String> {
+        Self::new(SPECIFICATION, &INCLUDES, automata)
+    }
+
+    // takes the name and contents of the specification and of every file it may include,
+    // the lexical automata are looked up by name
+    pub fn new(
+        specification: (&str, &str),
+        includes: &[(&str, &str)],
+        automata: &Automata,
+    ) -> Result<Self, String> {
+        let (file_name, contents) = specification;
+
+        let mut patterns: Vec<(TokenKind, Pattern)> = vec![];
+        // the names of the kinds only the specification gives, in the order they appear
+        let mut defined: Vec<String> = vec![];
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(COMMENT) {
+                continue;
+            }
+
+            let result = match line.strip_prefix(INCLUDE) {
+                Some(included) if included.starts_with(char::is_whitespace) => {
+                    Self::parse_include(included.trim(), includes, &mut defined)
+                }
+                _ => Self::parse_pattern(line, automata, &mut defined).map(|pattern| vec![pattern]),
+            };
+
+            match result {
+                Ok(mut new_patterns) => patterns.append(&mut new_patterns),
+                Err(e) => {
+                    let error = format!("{}:{}: {}", file_name, index + 1, e);
+                    return Err(error);
+                }
+            }
+        }
+
+        let machines: Vec<(TokenKind, &Automaton)> = patterns
+            .iter()
+            .map(|(kind, pattern)| (*kind, pattern.automaton()))
+            .collect();
+        let machine = match Moore::combine(&machines) {
+            Ok(machine) => machine.compile(),
+            Err(e) => {
+                let error = format!("{}: {}", file_name, e);
+                return Err(error);
+            }
+        };
+
+        Ok(Self { machine, defined })
+    }
+
+    // the name of a kind as the specification gives it, for the kinds it defines too
+    pub fn kind_name(&self, kind: TokenKind) -> String {
+        match kind
+            .defined_index()
+            .and_then(|index| self.defined.get(index))
+        {
+            Some(name) => name.clone(),
+            None => kind.to_string(),
+        }
+    }
+
+    // returns the length and kind of the longest token at the start of the program
+    // read so far, which may be empty if nothing matches, and whether the token
+    // is complete; otherwise it may go on in the rest of the program
+    pub fn next_match<I: IntoIterator<Item = char>>(
+        &self,
+        program: I,
+    ) -> (Option<(usize, TokenKind)>, bool) {
+        let (longest, complete) = self.machine.longest_match_partial(program);
+        (longest.map(|(length, kind)| (length, *kind)), complete)
+    }
+
+    // every line of an included file is a literal, of the kind named after it
+    // or else of the kind the lexeme maps to: <lexeme> [<kind>]
+    fn parse_include(
+        file_name: &str,
+        includes: &[(&str, &str)],
+        defined: &mut Vec<String>,
+    ) -> Result<Vec<(TokenKind, Pattern<'static>)>, String> {
+        let contents = match includes.iter().find(|(name, _)| *name == file_name) {
+            Some((_, contents)) => contents,
+            None => {
+                let error = format!("cannot include '{}'", file_name);
+                return Err(error);
+            }
+        };
+
+        let mut patterns = vec![];
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (lexeme, kind) = match line.split_once(char::is_whitespace) {
+                Some((lexeme, name)) => (lexeme, Self::kind(name.trim(), defined)),
+                None => match TokenKind::from(line) {
+                    TokenKind::Unknown => {
+                        let error = format!("no token kind for '{}', name one after it", line);
+                        (line, Err(error))
+                    }
+                    kind => (line, Ok(kind)),
+                },
+            };
+            let kind = match kind {
+                Ok(kind) => kind,
+                Err(e) => {
+                    let error = format!("{}:{}: {}", file_name, index + 1, e);
+                    return Err(error);
+                }
+            };
+
+            match Self::literal(lexeme) {
+                Ok(automaton) => patterns.push((kind, Pattern::Built(Box::new(automaton)))),
+                Err(e) => {
+                    let error = format!("{}:{}: {}", file_name, index + 1, e);
+                    return Err(error);
+                }
+            }
+        }
+
+        Ok(patterns)
+    }
+
+    // <kind> "<literal>" | <kind> /<regex>/ | <kind> dfa <name>
+    fn parse_pattern<'a>(
+        line: &str,
+        automata: &'a Automata,
+        defined: &mut Vec<String>,
+    ) -> Result<(TokenKind, Pattern<'a>), String> {
+        let (kind, pattern) = match line.split_once(char::is_whitespace) {
+            Some((kind, pattern)) => (kind, pattern.trim()),
+            None => {
+                let error = format!("missing pattern for '{}'", line);
+                return Err(error);
+            }
+        };
+
+        let kind = match Self::kind(kind, defined) {
+            Ok(kind) => kind,
+            Err(e) => return Err(e),
+        };
+
+        let pattern = if let Some(literal) = Self::delimited(pattern, '"') {
+            let literal = literal.replace("\\\"", "\"").replace("\\\\", "\\");
+            Self::literal(&literal).map(|automaton| Pattern::Built(Box::new(automaton)))
+        } else if let Some(regex) = Self::delimited(pattern, '/') {
+            // "\/" already stands for a slash in a regex
+            Automaton::from_regex(regex)
+                .map(|automaton| Pattern::Built(Box::new(automaton)))
+                .map_err(|e| e.to_string())
+        } else if let Some(name) = pattern.strip_prefix(DFA) {
+            match automata.get_automaton(name.trim()) {
+                Some(automaton) => Ok(Pattern::Named(automaton)),
+                None => {
+                    let error = format!("unknown automaton '{}'", name.trim());
+                    Err(error)
+                }
+            }
+        } else {
+            let error = format!("invalid pattern '{}'", pattern);
+            Err(error)
+        };
+
+        pattern.map(|pattern| (kind, pattern))
+    }
+
+    // the kind of a name: one of the token kinds, or else a new kind the specification
+    // defines, which takes the next code; a name must start with an uppercase letter
+    fn kind(name: &str, defined: &mut Vec<String>) -> Result<TokenKind, String> {
+        if let Ok(kind) = TokenKind::from_str(name) {
+            return Ok(kind);
+        }
+
+        let mut symbols = name.chars();
+        let valid = symbols.next().is_some_and(|c| c.is_ascii_uppercase())
+            && symbols.all(|c| c.is_ascii_alphanumeric());
+        if !valid {
+            let error = format!("invalid token kind '{}'", name);
+            return Err(error);
+        }
+
+        let index = match defined.iter().position(|other| other == name) {
+            Some(index) => index,
+            None => {
+                defined.push(String::from(name));
+                defined.len() - 1
+            }
+        };
+
+        Ok(TokenKind::defined(index))
+    }
+
+    // the pattern without its delimiters, if it has them
+    fn delimited(pattern: &str, delimiter: char) -> Option<&str> {
+        match pattern.len() > 1 {
+            true => pattern.strip_prefix(delimiter)?.strip_suffix(delimiter),
+            false => None,
+        }
+    }
+
+    // the automaton of a literal is the one of a regex with every symbol escaped
+    fn literal(literal: &str) -> Result<Automaton, String> {
+        let mut regex = String::new();
+        for symbol in literal.chars() {
+            match symbol {
+                '\n' => regex.push_str(r"\n"),
+                '\t' => regex.push_str(r"\t"),
+                '\r' => regex.push_str(r"\r"),
+                ' ' => regex.push_str(r"\s"),
+                _ if symbol.is_alphanumeric() => regex.push(symbol),
+                _ => {
+                    regex.push('\\');
+                    regex.push(symbol);
+                }
+            }
+        }
+
+        Automaton::from_regex(&regex).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use super::error::LexErrorKind;
+
+const CHAR_QUOTE: char = '\'';
+const STRING_QUOTE: char = '"';
+const ESCAPE: char = '\\';
+// separates the digits of a number, i.e. 1_000
+const DIGIT_SEPARATOR: char = '_';
+// the prefixes of numbers in other bases, and their bases
+const RADIXES: [(&str, u32); 3] = [("0x", 16), ("0o", 8), ("0b", 2)];
+// a number with a fraction or an exponent is a decimal
+const DECIMAL_MARKS: [char; 3] = ['.', 'e', 'E'];
+// numbers are magnitudes, the largest is that of the smallest 64-bit number so that
+// it can be written with a minus before it
+const NUMBER_LIMIT: u64 = i64::MIN.unsigned_abs();
+
+// the character after the backslash of an escape, and the character it stands for
+const ESCAPES: [(char, char); 5] = [
+    ('"', '"'),
+    ('\'', '\''),
+    ('\\', '\\'),
+    ('n', '\n'),
+    ('t', '\t'),
+];
+
+// the value of a constant, with the quotes and escapes of chars and strings decoded;
+// numbers have no sign, the parser reads a minus before them as a negation
+#[derive(Clone, Debug)]
+pub enum ConstantValue {
+    Number(u64),
+    Decimal(f64),
+    Char(char),
+    Str(String),
+}
+
+// decimals are never NaN, so they are the same if their bits are
+impl PartialEq for ConstantValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ConstantValue::Number(number), ConstantValue::Number(other)) => number == other,
+            (ConstantValue::Decimal(decimal), ConstantValue::Decimal(other)) => {
+                decimal.to_bits() == other.to_bits()
+            }
+            (ConstantValue::Char(character), ConstantValue::Char(other)) => character == other,
+            (ConstantValue::Str(string), ConstantValue::Str(other)) => string == other,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ConstantValue {}
+
+impl Hash for ConstantValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            ConstantValue::Number(number) => number.hash(state),
+            ConstantValue::Decimal(decimal) => decimal.to_bits().hash(state),
+            ConstantValue::Char(character) => character.hash(state),
+            ConstantValue::Str(string) => string.hash(state),
+        }
+    }
+}
+
+impl Display for ConstantValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstantValue::Number(number) => write!(f, "{}", number),
+            // always with a fraction or an exponent, so it reads as a decimal
+            ConstantValue::Decimal(decimal) => write!(f, "{:?}", decimal),
+            ConstantValue::Char(character) => write!(f, "{}", character),
+            ConstantValue::Str(string) => write!(f, "{}", string),
+        }
+    }
+}
+
+impl ConstantValue {
+    // the type of the value, as the scan output formats write it
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ConstantValue::Number(_) => "Number",
+            ConstantValue::Decimal(_) => "Decimal",
+            ConstantValue::Char(_) => "Char",
+            ConstantValue::Str(_) => "String",
+        }
+    }
+}
+
+// a constant as it is stored in the constant table: its value and its text in the
+// source; constants are the same if their values are, so "0x10" and "16" share an entry,
+// which keeps the text it was first read with
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub value: ConstantValue,
+    pub text: String,
+}
+
+impl PartialEq for Constant {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Constant {}
+
+impl Hash for Constant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+// what is wrong with a literal, and the bytes of the lexeme it is about
+pub struct LiteralError {
+    pub kind: LexErrorKind,
+    pub offset: usize,
+    pub length: usize,
+}
+
+// whether the lexeme is meant to be a char or a string, valid or not
+pub fn is_literal(lexeme: &str) -> bool {
+    lexeme.starts_with([CHAR_QUOTE, STRING_QUOTE])
+}
+
+// the typed value of a constant, numbers must be at most 2^63 and decimals must be finite
+pub fn parse(lexeme: &str) -> Result<ConstantValue, LiteralError> {
+    if lexeme.starts_with(CHAR_QUOTE) {
+        // a char that decodes holds a single character
+        return decode(lexeme)
+            .map(|value| ConstantValue::Char(value.chars().next().unwrap_or_default()));
+    }
+    if lexeme.starts_with(STRING_QUOTE) {
+        return decode(lexeme).map(ConstantValue::Str);
+    }
+
+    let digits = lexeme.replace(DIGIT_SEPARATOR, "");
+    let value = match RADIXES
+        .iter()
+        .find(|(prefix, _)| digits.starts_with(prefix))
+    {
+        Some((prefix, radix)) => u64::from_str_radix(&digits[prefix.len()..], *radix)
+            .ok()
+            .filter(|number| *number <= NUMBER_LIMIT)
+            .map(ConstantValue::Number),
+        None if digits.contains(DECIMAL_MARKS) => match digits.parse::<f64>() {
+            Ok(decimal) if decimal.is_finite() => Some(ConstantValue::Decimal(decimal)),
+            _ => None,
+        },
+        None => digits
+            .parse::<u64>()
+            .ok()
+            .filter(|number| *number <= NUMBER_LIMIT)
+            .map(ConstantValue::Number),
+    };
+
+    match value {
+        Some(value) => Ok(value),
+        None => Err(LiteralError {
+            kind: LexErrorKind::NumberOutOfRange(String::from(lexeme)),
+            offset: 0,
+            length: lexeme.len(),
+        }),
+    }
+}
+
+// the text of a char or string with its quotes and escapes decoded, a char must hold
+// a single character; lexemes that are not chars or strings are their own text
+pub fn decode(lexeme: &str) -> Result<String, LiteralError> {
+    let quote = match lexeme.chars().next() {
+        Some(quote) if quote == CHAR_QUOTE || quote == STRING_QUOTE => quote,
+        _ => return Ok(String::from(lexeme)),
+    };
+
+    let unterminated = LiteralError {
+        kind: LexErrorKind::UnterminatedLiteral(String::from(lexeme)),
+        offset: 0,
+        length: lexeme.len(),
+    };
+
+    let mut value = String::new();
+    let mut characters = lexeme.char_indices().skip(1);
+    let closed = loop {
+        let (offset, character) = match characters.next() {
+            Some(next) => next,
+            None => break false,
+        };
+
+        if character == quote {
+            break true;
+        }
+        if character != ESCAPE {
+            value.push(character);
+            continue;
+        }
+
+        // an escape cut short by the end of the line does not close the literal
+        let escaped = match characters.next() {
+            Some((_, escaped)) => escaped,
+            None => return Err(unterminated),
+        };
+        match ESCAPES.iter().find(|(symbol, _)| *symbol == escaped) {
+            Some((_, decoded)) => value.push(*decoded),
+            None => {
+                let length = ESCAPE.len_utf8() + escaped.len_utf8();
+                let escape = String::from(&lexeme[offset..offset + length]);
+                return Err(LiteralError {
+                    kind: LexErrorKind::InvalidEscape(escape),
+                    offset,
+                    length,
+                });
+            }
+        }
+    };
+
+    if !closed {
+        return Err(unterminated);
+    }
+
+    if quote == CHAR_QUOTE && value.chars().count() != 1 {
+        return Err(LiteralError {
+            kind: LexErrorKind::UndefinedToken(String::from(lexeme)),
+            offset: 0,
+            length: lexeme.len(),
+        });
+    }
+
+    Ok(value)
+}
@@ -1,23 +1,62 @@
-use std::fs;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::str::FromStr;
 
 use super::automata::Automata;
+use super::edit::Edit;
+use super::error::{LexError, LexErrorKind};
+use super::lexer::Lexer;
+use super::literal::{self, Constant};
+use super::source::Source;
+use super::span::{Position, Span};
 use hash_map::Table;
 
 use super::token::{Token, TokenKind};
-use crate::utils::constants::{EOF_CHAR, LINE_COMMENT, NEWLINE_CHAR};
-use crate::utils::writer::write_scan_result;
-
+use super::trivia::{Trivia, TriviaKind};
+use super::unicode::{self, CharacterSet};
+use crate::utils::constants::{
+    BLOCK_COMMENT_END, BLOCK_COMMENT_START, CRLF, DOC_COMMENT, LINE_COMMENT, NEWLINE_CHAR,
+};
+use crate::utils::writer::{write_scan_result, OutputFormat, ScanOutput};
+
+// the scanner reads its source lazily and produces tokens on demand as an iterator,
+// scan runs it over a whole file and keeps the tokens for write_result
 pub struct Scanner {
-    raw_program: Vec<char>,
+    source: Source,
+    // the error that cut the source short, reported once every token before it was read
+    read_error: Option<String>,
     final_result: String,
-    position: usize,
-    current_line: usize,
+    // where the current character is in the source, for the spans of the tokens
+    byte_offset: usize,
+    current_position: Position,
 
     // store the token list, identifier table, and constant table separately
     token_list: Vec<Token>,
     identifier_table: Table<String>,
-    constant_table: Table<String>,
-    automata: Automata,
+    constant_table: Table<Constant>,
+    lexer: Lexer,
+    // the first identifier of every skeleton, an identifier that looks the same as
+    // another one is confusable
+    skeletons: HashMap<String, String>,
+
+    // the lexical errors found so far, scanning stops once there are as many as the limit
+    errors: Vec<LexError>,
+    error_limit: Option<usize>,
+    character_set: CharacterSet,
+
+    // comments are produced as tokens if they are kept, doc comments are attached to
+    // the token after them either way
+    keep_comments: bool,
+    documentation: Option<String>,
+
+    // in lossless mode every byte of the source ends up in a token or in its trivia,
+    // and the trivia at the end of the source is the leading trivia of an EOF token
+    lossless: bool,
+    trivia: Vec<Trivia>,
+    ended: bool,
+    // an error in the trivia after a token, reported after the token
+    trivia_error: Option<LexError>,
 }
 
 impl Scanner {
@@ -29,21 +68,102 @@ impl Scanner {
             }
         };
 
-        let scanner = Self {
-            raw_program: vec![],
+        Self::with_automata(automata)
+    }
+
+    // uses lexical automata loaded by the caller, i.e. from a custom search path
+    pub fn with_automata(automata: Automata) -> Result<Self, String> {
+        match Lexer::embedded(&automata) {
+            Ok(lexer) => Ok(Self::with_lexer(lexer)),
+            Err(e) => Err(e),
+        }
+    }
+
+    // uses a lexer built by the caller, i.e. from a custom specification
+    pub fn with_lexer(lexer: Lexer) -> Self {
+        Self {
+            source: Source::empty(),
+            read_error: None,
             final_result: String::new(),
-            position: 0,
-            current_line: 1,
+            byte_offset: 0,
+            current_position: Position::default(),
 
             token_list: vec![],
             identifier_table: Table::new(),
             constant_table: Table::new(),
-            automata,
+            lexer,
+            skeletons: HashMap::new(),
+
+            errors: vec![],
+            error_limit: None,
+            character_set: CharacterSet::default(),
+
+            keep_comments: false,
+            documentation: None,
+
+            lossless: false,
+            trivia: vec![],
+            ended: false,
+            trivia_error: None,
+        }
+    }
+
+    // scans any source, i.e. stdin or a socket
+    pub fn from_reader<R: Read + 'static>(reader: R) -> Result<Self, String> {
+        let mut scanner = match Self::new() {
+            Ok(scanner) => scanner,
+            Err(e) => return Err(e),
         };
 
+        scanner.set_reader(reader);
         Ok(scanner)
     }
 
+    // starts over on a new source, forgetting everything about the previous one
+    pub fn set_reader<R: Read + 'static>(&mut self, reader: R) {
+        self.source = Source::new(reader);
+        self.read_error = None;
+        self.final_result.clear();
+        self.byte_offset = 0;
+        self.current_position = Position::default();
+
+        self.token_list.clear();
+        self.identifier_table.clear();
+        self.constant_table.clear();
+        self.skeletons.clear();
+        self.errors.clear();
+        self.documentation = None;
+        self.trivia.clear();
+        self.ended = false;
+        self.trivia_error = None;
+    }
+
+    pub fn tokens(&mut self) -> Tokens<'_> {
+        Tokens { scanner: self }
+    }
+
+    // stops scanning after the given number of lexical errors, None never stops
+    pub fn set_error_limit(&mut self, error_limit: Option<usize>) {
+        self.error_limit = error_limit;
+    }
+
+    // the characters identifiers may be made of, literals and comments may hold any
+    pub fn set_character_set(&mut self, character_set: CharacterSet) {
+        self.character_set = character_set;
+    }
+
+    // produces comments as Comment and DocComment tokens, for a formatter or a documentation tool
+    pub fn set_keep_comments(&mut self, keep_comments: bool) {
+        self.keep_comments = keep_comments;
+    }
+
+    // keeps whitespace and comments as the trivia of the tokens, so the tokens can be
+    // turned back into the source; the text is decoded as UTF-8, so only a UTF-8 source
+    // comes back unchanged, bytes that are not UTF-8 come back as U+FFFD
+    pub fn set_lossless(&mut self, lossless: bool) {
+        self.lossless = lossless;
+    }
+
     pub fn get_token_list(&self) -> &Vec<Token> {
         &self.token_list
     }
@@ -52,221 +172,324 @@ impl Scanner {
         &self.identifier_table
     }
 
-    pub fn get_constant_table(&self) -> &Table<String> {
+    pub fn get_constant_table(&self) -> &Table<Constant> {
         &self.constant_table
     }
 
-    fn get_nth(&self, n: usize) -> &char {
-        match self.raw_program.get(self.position + n) {
-            Some(character) => character,
-            None => EOF_CHAR,
+    pub fn get_errors(&self) -> &Vec<LexError> {
+        &self.errors
+    }
+
+    // the nth byte from the current one, None at the end of the source; a source
+    // that cannot be read ends where it failed
+    fn get_nth(&mut self, n: usize) -> Option<u8> {
+        match self.source.get(n) {
+            Ok(byte) => byte,
+            Err(e) => {
+                self.read_error = Some(e);
+                None
+            }
         }
     }
 
-    fn current(&self) -> &char {
+    fn current(&mut self) -> Option<u8> {
         self.get_nth(0)
     }
 
-    fn peek(&self) -> &char {
-        self.get_nth(1)
+    // whether the current byte is a newline
+    fn at_newline(&mut self) -> bool {
+        self.current() == Some(*NEWLINE_CHAR as u8)
+    }
+
+    fn at_whitespace(&mut self) -> bool {
+        self.current()
+            .is_some_and(|byte| byte.is_ascii_whitespace())
     }
 
+    // consumes n bytes, columns only move on the first byte of a character
     fn advance(&mut self, n: usize) {
-        self.position += n;
+        for _ in 0..n {
+            let current = match self.current() {
+                Some(current) => current,
+                None => return,
+            };
+
+            self.byte_offset += 1;
+            if current == *NEWLINE_CHAR as u8 {
+                self.current_position.line += 1;
+                self.current_position.column = 1;
+            } else if current & 0xc0 != 0x80 {
+                self.current_position.column += 1;
+            }
+
+            self.source.advance(1);
+        }
     }
 
     pub fn scan(&mut self, input_file: &str) -> Result<(), String> {
-        println!("Scanning '{}'", input_file);
-        self.raw_program = match fs::read_to_string(input_file) {
-            // Ok(program) => program.replace("\r\n", "\n").chars().collect(),
-            Ok(program) => program.chars().collect(),
+        let file = match File::open(input_file) {
+            Ok(file) => file,
             Err(e) => {
-                let error = format!("could not read program file: {}", e.to_string());
+                let error = format!("could not read program file: {}", e);
                 return Err(error);
             }
         };
 
-        // reset the scanner
-        self.position = 0;
-        self.current_line = 1;
-        self.final_result.clear();
+        self.scan_reader(file)
+    }
 
-        self.token_list.clear();
-        self.identifier_table.clear();
-        self.constant_table.clear();
+    // scans the whole source, i.e. stdin, and keeps the tokens for write_result
+    pub fn scan_reader<R: Read + 'static>(&mut self, reader: R) -> Result<(), String> {
+        self.set_reader(reader);
 
         // parse the program
         self.final_result = match self.parse_program() {
-            Ok(_) => String::from("Lexically correct!"),
+            Ok(_) if self.errors.is_empty() => String::from("Lexically correct!"),
+            Ok(_) => String::from("Lexically incorrect!"),
             Err(e) => e,
         };
 
-        Ok(())
+        match self.read_error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
-    pub fn write_result(&self, output_file: &str) -> Result<(), String> {
-        write_scan_result(
-            output_file,
-            &self.final_result,
-            &self.token_list,
-            &self.identifier_table,
-            &self.constant_table,
-        )
+    pub fn write_result(&self, output_file: &str, format: OutputFormat) -> Result<(), String> {
+        write_scan_result(output_file, format.writer().as_ref(), &self.output())
     }
 
-    fn parse_program(&mut self) -> Result<(), String> {
-        // keep reading tokens until we reach EOF
-        while let Some(token) = self.next_token() {
-            match token.get_kind() {
-                // unknown tokens produce lexical errors
-                TokenKind::Unknown => {
-                    let error = format!(
-                        "Lexical error on line {} => undefined token: {}",
-                        self.current_line,
-                        token.get_inner()
-                    );
-                    return Err(error);
+    // the result of the last scan, as the output formats see it
+    pub fn output(&self) -> ScanOutput<'_> {
+        ScanOutput {
+            result: &self.final_result,
+            errors: &self.errors,
+            tokens: &self.token_list,
+            identifiers: &self.identifier_table,
+            constants: &self.constant_table,
+            lexer: &self.lexer,
+        }
+    }
+
+    // the tokens and lexical errors of text, the source after the edit, reusing old_results,
+    // what the scanner produced for the source before it with the same settings.
+    //
+    // no token but a block comment goes past the end of its line, so the results up to the
+    // last token that ends on a line before the edit stay the same, along with the state of
+    // the scanner after it; an edited comment or literal starts after that token, so it is
+    // read again whole. scanning restarts there and stops once it is past the edit, right
+    // after a token that the previous scan also ended a token at; from then on the source
+    // and the state are the same as they were, so the old results are only moved.
+    //
+    // identifiers and constants keep their keys in the tables, so the tables may still
+    // hold ones that are no longer in the source, and in unicode mode an identifier may be
+    // confusable with one of them; the binary scans whole sources, editors re-lex through
+    // the library
+    #[allow(dead_code)]
+    pub fn relex(
+        &mut self,
+        text: &str,
+        old_results: &[Result<Token, LexError>],
+        edit: &Edit,
+    ) -> Result<Vec<Result<Token, LexError>>, String> {
+        let inserted = edit.offset..edit.offset + edit.inserted.len();
+        if text.as_bytes().get(inserted.clone()) != Some(edit.inserted.as_bytes()) {
+            let error = format!(
+                "the edit at offset {} does not match the source",
+                edit.offset
+            );
+            return Err(error);
+        }
+
+        // the last result before which the scanner can restart, and where
+        let mut kept = 0;
+        let mut restart = (0, Position::default());
+        for (index, result) in old_results.iter().enumerate() {
+            let (offset, position) = match result.as_ref().ok().and_then(Self::resume_point) {
+                Some(point) => point,
+                None => continue,
+            };
+            if offset >= edit.offset {
+                break;
+            }
+            if text.as_bytes()[offset..edit.offset].contains(&(*NEWLINE_CHAR as u8)) {
+                kept = index + 1;
+                restart = (offset, position);
+            }
+        }
+
+        // where the previous scan could have been picked up after the edit
+        let removed_end = edit.offset + edit.removed;
+        let mut resume_points: HashMap<usize, (usize, Position)> = HashMap::new();
+        for (index, result) in old_results.iter().enumerate().skip(kept) {
+            if let Some((offset, position)) = result.as_ref().ok().and_then(Self::resume_point) {
+                if offset >= removed_end {
+                    resume_points.insert(offset, (index, position));
                 }
-                // valid token
-                _ => self.token_list.push(token),
             }
         }
 
-        Ok(())
+        let (offset, position) = restart;
+        self.source = Source::new(Cursor::new(text.as_bytes()[offset..].to_vec()));
+        self.read_error = None;
+        self.byte_offset = offset;
+        self.current_position = position;
+        self.documentation = None;
+        self.trivia.clear();
+        self.ended = false;
+        self.trivia_error = None;
+
+        let mut results = old_results[..kept].to_vec();
+        while let Some(result) = self.next_result() {
+            let point = match &result {
+                Ok(token) if self.trivia_error.is_none() => Self::resume_point(token),
+                _ => None,
+            };
+            results.push(result);
+
+            let (offset, position) = match point {
+                Some(point) if point.0 >= inserted.end => point,
+                _ => continue,
+            };
+            let old_offset = offset.wrapping_add_signed(-edit.delta());
+            if let Some((index, old_position)) = resume_points.get(&old_offset) {
+                let moved = |span: Span| span.moved(edit.delta(), *old_position, position);
+                for result in &old_results[index + 1..] {
+                    results.push(match result {
+                        Ok(token) => {
+                            let mut token = token.clone();
+                            token.set_span(moved(token.get_span()));
+                            Ok(token)
+                        }
+                        Err(e) => Err(LexError::new(e.get_kind().clone(), moved(e.get_span()))),
+                    });
+                }
+                break;
+            }
+        }
+
+        Ok(results)
     }
 
-    fn next_token(&mut self) -> Option<Token> {
-        self.consume_whitespace();
-        self.consume_comments();
+    // where the scanner was right after the token, if nothing it read before carries over
+    // to the tokens after it: no documentation, trivia or error is left over after a token
+    // that is not a comment, unless its trailing trivia holds an error
+    fn resume_point(token: &Token) -> Option<(usize, Position)> {
+        if token.get_kind().is_trivia() || token.get_kind() == TokenKind::EOF {
+            return None;
+        }
 
-        // check if we reached EOF
-        let current = *self.current();
-        if current == *EOF_CHAR {
+        let trailing = token.get_trailing_trivia();
+        if trailing
+            .iter()
+            .any(|trivia| trivia.kind == TriviaKind::Skipped)
+        {
             return None;
         }
 
-        // we are relying on Unknown tokens to be consumed
-        // so that we can catch lexical errors after classifying
-        // them. Those do not pass the DFA remain Unknown tokens
-
-        // start parsing the token
-        self.advance(1);
-        let next = self.current();
-
-        let mut token_value = String::from(current);
-        let kind = match current {
-            // Special Symbols and Operators
-            '+' => match next.is_ascii_digit() {
-                true => TokenKind::Unknown,
-                false => TokenKind::Plus,
-            },
-            '-' => match next.is_ascii_digit() {
-                true => TokenKind::Unknown,
-                false => TokenKind::Minus,
-            },
-            '*' => TokenKind::Multiply,
-            '/' => TokenKind::Divide,
-            '%' => TokenKind::Modulo,
-            '=' => match next {
-                &'=' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::Equal
-                }
-                _ => TokenKind::Assign,
-            },
-            '!' => match next {
-                &'=' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::NotEqual
-                }
-                _ => TokenKind::Unknown,
-            },
-            '<' => match next {
-                &'=' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::LessEqual
-                }
-                _ => TokenKind::Less,
-            },
-            '>' => match next {
-                &'=' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::GreaterEqual
-                }
-                _ => TokenKind::Greater,
-            },
-            '&' => match next {
-                &'&' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::And
-                }
-                _ => TokenKind::Unknown,
-            },
-            '|' => match next {
-                &'|' => {
-                    token_value.push(*next);
-                    self.advance(1);
-
-                    TokenKind::Or
+        // trailing trivia never goes past the end of the line
+        let span = token.get_span();
+        let mut position = span.end;
+        let mut offset = span.offset + span.length;
+        for trivia in trailing {
+            offset += trivia.text.len();
+            position.column += trivia.text.chars().count();
+        }
+
+        Some((offset, position))
+    }
+
+    fn parse_program(&mut self) -> Result<(), String> {
+        // keep reading tokens until we reach EOF
+        while let Some(result) = self.next_result() {
+            match result {
+                // valid token
+                Ok(token) => self.token_list.push(token),
+                // the lexer already consumed the whole bad lexeme,
+                // so we can carry on with the next token
+                Err(e) => {
+                    if let LexErrorKind::Unreadable(message) = e.get_kind() {
+                        self.read_error = Some(message.clone());
+                        break;
+                    }
+                    self.errors.push(e);
+
+                    if Some(self.errors.len()) == self.error_limit {
+                        let error = format!(
+                            "Lexically incorrect, stopped after {} errors!",
+                            self.errors.len()
+                        );
+                        return Err(error);
+                    }
                 }
-                _ => TokenKind::Unknown,
-            },
-
-            // Separators
-            '(' => TokenKind::ParenOpen,
-            ')' => TokenKind::ParenClose,
-            '{' => TokenKind::BraceOpen,
-            '}' => TokenKind::BraceClose,
-            '[' => TokenKind::BracketOpen,
-            ']' => TokenKind::BracketClose,
-            ';' => TokenKind::SemiColon,
-            ':' => TokenKind::Colon,
-
-            // Other
-            '\'' => TokenKind::Constant,
-            '"' => TokenKind::Constant,
-            _ => TokenKind::Unknown,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn next_token(&mut self) -> Token {
+        // the lexer takes the longest token it knows, preferring the patterns listed
+        // first, its error patterns produce Unknown tokens that we report afterwards;
+        // a token may go on past what was read so far, so read until it cannot
+        let (longest, at_end) = loop {
+            let at_end = self.source.is_exhausted();
+            let program = unicode::stand_ins(self.source.remaining(), at_end, self.character_set);
+            let (longest, complete) = self.lexer.next_match(program);
+            if complete || at_end {
+                break (longest, at_end);
+            }
+
+            // the next pass reads whatever is left, even a character cut short
+            if let Err(e) = self.source.fill() {
+                self.read_error = Some(e);
+            }
         };
 
-        // if the token is unknown, consume the rest of the characters
-        let token = match kind {
-            TokenKind::Unknown => self.consume_general(&current),
-            TokenKind::Constant => self.consume_literal(&current),
-            _ => Token::new(kind, &token_value),
+        let (characters, kind) = match longest {
+            Some((characters, kind)) if characters > 0 => (characters, kind),
+            // a character that starts no token at all
+            _ => (1, TokenKind::Unknown),
         };
 
-        Some(token)
+        let remaining = self.source.remaining();
+        let length = unicode::byte_length(remaining, characters, at_end).max(1);
+        let value = String::from_utf8_lossy(&remaining[..length]).into_owned();
+
+        let (offset, start) = (self.byte_offset, self.current_position);
+        self.advance(length);
+        let span = Span::new(
+            offset,
+            self.byte_offset - offset,
+            start,
+            self.current_position,
+        );
+
+        let mut token = Token::new(kind, &value);
+        token.set_span(span);
+        token
     }
 
-    fn consume_general(&mut self, start: &char) -> Token {
-        let mut value = String::from(*start);
-        while self.current().is_alphanumeric() {
-            value.push(*self.current());
-            self.advance(1);
-        }
-
-        // check if the token is a keyword
-        let mut token = Token::unknown(&value);
-        token.classify(&self.automata);
-
+    // adds identifiers and constants to their tables once they are known to be valid
+    fn record(&mut self, mut token: Token) -> Token {
+        let text = String::from(token.get_inner());
         match token.get_kind() {
             TokenKind::Identifier => {
-                let value = self.identifier_table.put(value);
+                if self.character_set == CharacterSet::Unicode {
+                    self.skeletons
+                        .entry(unicode::skeleton(&text))
+                        .or_insert_with(|| text.clone());
+                }
+
+                let value = self.identifier_table.put(text);
                 token.set_position(value);
             }
-            TokenKind::Constant => {
-                let value = self.constant_table.put(value);
-                token.set_position(value);
+            kind if kind.is_constant() => {
+                // the constant was checked, so it has a value
+                if let Ok(value) = literal::parse(&text) {
+                    let value = self.constant_table.put(Constant { value, text });
+                    token.set_position(value);
+                }
             }
             _ => {}
         }
@@ -274,56 +497,310 @@ impl Scanner {
         token
     }
 
-    fn consume_literal(&mut self, quote_type: &char) -> Token {
-        let mut value = String::from(*quote_type);
-        while self.current() != quote_type {
-            value.push(*self.current());
-            self.advance(1);
+    // the lexical error of a token, if it has one
+    fn check(&self, token: &Token) -> Option<LexError> {
+        let (inner, span) = (token.get_inner(), token.get_span());
+        let error = |kind: LexErrorKind| Some(LexError::new(kind, span));
+
+        // characters that cannot be seen are rejected anywhere, whatever the character set
+        if let Some((offset, character)) = inner
+            .char_indices()
+            .find(|(_, c)| unicode::is_invisible(*c))
+        {
+            let span = span.slice(inner, offset, character.len_utf8());
+            return Some(LexError::new(
+                LexErrorKind::InvisibleCharacter(character),
+                span,
+            ));
         }
 
-        // add the closing quote
-        value.push(*self.current());
-        self.advance(1);
+        // constants, and chars and strings that are not valid, point at what is wrong with them
+        let kind = token.get_kind();
+        if kind.is_constant() || literal::is_literal(inner) {
+            return match literal::parse(inner) {
+                Ok(_) if kind.is_constant() => None,
+                Ok(_) => error(LexErrorKind::UndefinedToken(String::from(inner))),
+                Err(e) => {
+                    let span = span.slice(inner, e.offset, e.length);
+                    Some(LexError::new(e.kind, span))
+                }
+            };
+        }
 
-        // check if the literal is valid
-        let mut token = Token::unknown(&value);
-        token.classify(&self.automata);
+        match (token.get_kind(), self.character_set) {
+            (TokenKind::Unknown, _) => error(LexErrorKind::UndefinedToken(String::from(inner))),
+            // the lexer only sees stand-ins, so it may take i.e. a combining mark to start an identifier
+            (TokenKind::Identifier, CharacterSet::Unicode) if !unicode::is_identifier(inner) => {
+                error(LexErrorKind::UndefinedToken(String::from(inner)))
+            }
+            (TokenKind::Identifier, CharacterSet::Unicode) if unicode::is_confusable(inner) => {
+                error(LexErrorKind::ConfusableIdentifier(String::from(inner)))
+            }
+            // an identifier that looks the same as one seen before, but is another one
+            (TokenKind::Identifier, CharacterSet::Unicode)
+                if self
+                    .skeletons
+                    .get(&unicode::skeleton(inner))
+                    .is_some_and(|other| other != inner) =>
+            {
+                error(LexErrorKind::ConfusableIdentifier(String::from(inner)))
+            }
+            _ => None,
+        }
+    }
 
-        match token.get_kind() {
-            TokenKind::Constant => {
-                let value = self.constant_table.put(value);
-                token.set_position(value);
+    // the next token or lexical error, None once the source is over
+    fn next_result(&mut self) -> Option<Result<Token, LexError>> {
+        if let Some(error) = self.trivia_error.take() {
+            return Some(Err(error));
+        }
+
+        loop {
+            self.consume_whitespace(true);
+            match self.next_comment() {
+                Some(Ok(comment)) => {
+                    if comment.get_kind() == TokenKind::DocComment {
+                        self.document(comment.get_inner());
+                    }
+                    if self.keep_comments {
+                        return Some(Ok(self.finish(comment)));
+                    }
+                    self.add_trivia(TriviaKind::Comment, comment.get_inner());
+                }
+                Some(Err(error)) => return Some(Err(error)),
+                None => break,
             }
-            _ => {}
         }
 
+        let span = Span::new(
+            self.byte_offset,
+            0,
+            self.current_position,
+            self.current_position,
+        );
+        if self.current().is_none() {
+            // the source ended early, report it once
+            if let Some(e) = self.read_error.take() {
+                return Some(Err(LexError::new(LexErrorKind::Unreadable(e), span)));
+            }
+
+            if !self.lossless || self.ended {
+                return None;
+            }
+            self.ended = true;
+
+            let mut eof = Token::new(TokenKind::EOF, "");
+            eof.set_span(span);
+            eof.set_leading_trivia(std::mem::take(&mut self.trivia));
+            return Some(Ok(eof));
+        }
+
+        let token = self.next_token();
+        match self.check(&token) {
+            Some(error) => {
+                self.add_trivia(TriviaKind::Skipped, token.get_inner());
+                Some(Err(error))
+            }
+            None => {
+                let mut token = self.record(token);
+                token.set_documentation(self.documentation.take());
+                Some(Ok(self.finish(token)))
+            }
+        }
+    }
+
+    // in lossless mode, gives the token the trivia before it and reads the trivia after it:
+    // whitespace, and a comment if it is not documentation, up to the end of the line
+    fn finish(&mut self, mut token: Token) -> Token {
+        if !self.lossless {
+            return token;
+        }
+
+        token.set_leading_trivia(std::mem::take(&mut self.trivia));
+        self.consume_whitespace(false);
+        if !self.keep_comments && self.starts_with(LINE_COMMENT) && !self.is_doc_comment() {
+            match self.next_comment() {
+                Some(Ok(comment)) => self.add_trivia(TriviaKind::Comment, comment.get_inner()),
+                Some(Err(error)) => self.trivia_error = Some(error),
+                None => {}
+            }
+        }
+        token.set_trailing_trivia(std::mem::take(&mut self.trivia));
+
         token
     }
 
-    fn consume_comments(&mut self) {
-        // check if we have a valid comment
-        let token = format!("{}{}", self.current(), self.peek());
-        if token != LINE_COMMENT {
-            return;
+    fn add_trivia(&mut self, kind: TriviaKind, text: &str) {
+        if self.lossless {
+            self.trivia.push(Trivia::new(kind, text));
         }
+    }
+
+    // adds the text of a doc comment to the documentation of the next token
+    fn document(&mut self, comment: &str) {
+        let text = comment.strip_prefix(DOC_COMMENT).unwrap_or(comment);
+        let text = text.strip_prefix(' ').unwrap_or(text);
 
-        // keep reading until we reach a newline or EOF
-        while self.current() != NEWLINE_CHAR && self.current() != EOF_CHAR {
+        match &mut self.documentation {
+            Some(documentation) => {
+                documentation.push('\n');
+                documentation.push_str(text);
+            }
+            None => self.documentation = Some(String::from(text)),
+        }
+    }
+
+    // whether the source goes on with the text
+    fn starts_with(&mut self, text: &str) -> bool {
+        text.bytes()
+            .enumerate()
+            .all(|(index, byte)| self.get_nth(index) == Some(byte))
+    }
+
+    // "---" documents, "----" and longer are plain comments
+    fn is_doc_comment(&mut self) -> bool {
+        self.starts_with(DOC_COMMENT) && self.get_nth(DOC_COMMENT.len()) != Some(b'-')
+    }
+
+    // reads the comment at the current character, if there is one; a comment that is not
+    // closed, or hides invisible characters, is an error
+    fn next_comment(&mut self) -> Option<Result<Token, LexError>> {
+        let (offset, start) = (self.byte_offset, self.current_position);
+        let mut text = vec![];
+
+        let (kind, closed) = if self.starts_with(BLOCK_COMMENT_START) {
+            (TokenKind::Comment, self.read_block_comment(&mut text))
+        } else if self.starts_with(LINE_COMMENT) {
+            let kind = match self.is_doc_comment() {
+                true => TokenKind::DocComment,
+                false => TokenKind::Comment,
+            };
+            (kind, self.read_line_comment(&mut text))
+        } else {
+            return None;
+        };
+
+        let text = String::from_utf8_lossy(&text).into_owned();
+        let span = Span::new(
+            offset,
+            self.byte_offset - offset,
+            start,
+            self.current_position,
+        );
+        if !closed {
+            self.add_trivia(TriviaKind::Skipped, &text);
+            return Some(Err(LexError::new(LexErrorKind::UnterminatedComment, span)));
+        }
+
+        if let Some((index, character)) =
+            text.char_indices().find(|(_, c)| unicode::is_invisible(*c))
+        {
+            self.add_trivia(TriviaKind::Skipped, &text);
+            return Some(Err(LexError::new(
+                LexErrorKind::InvisibleCharacter(character),
+                span.slice(&text, index, character.len_utf8()),
+            )));
+        }
+
+        let mut comment = Token::new(kind, &text);
+        comment.set_span(span);
+        Some(Ok(comment))
+    }
+
+    // consumes n bytes, keeping them in text
+    fn take(&mut self, text: &mut Vec<u8>, n: usize) {
+        for _ in 0..n {
+            match self.current() {
+                Some(byte) => text.push(byte),
+                None => return,
+            }
             self.advance(1);
         }
+    }
+
+    // keep reading until we reach a newline or EOF
+    fn read_line_comment(&mut self, text: &mut Vec<u8>) -> bool {
+        while self.current().is_some() && !self.at_newline() {
+            self.take(text, 1);
+        }
 
-        // consume the whitespace characters after the comment
-        self.consume_whitespace();
+        true
     }
 
-    fn consume_whitespace(&mut self) {
-        // keep reading until we reach a non-whitespace character
-        while self.current().is_whitespace() {
-            if self.current() == NEWLINE_CHAR {
-                self.current_line += 1;
+    // keep reading until every comment opened in the comment is closed, false if
+    // the source ends first
+    fn read_block_comment(&mut self, text: &mut Vec<u8>) -> bool {
+        let mut depth = 0;
+        loop {
+            if self.starts_with(BLOCK_COMMENT_START) {
+                depth += 1;
+                self.take(text, BLOCK_COMMENT_START.len());
+            } else if self.starts_with(BLOCK_COMMENT_END) {
+                depth -= 1;
+                self.take(text, BLOCK_COMMENT_END.len());
+                if depth == 0 {
+                    return true;
+                }
+            } else if self.current().is_none() {
+                return false;
+            } else {
+                self.take(text, 1);
             }
+        }
+    }
 
-            self.advance(1);
+    // keep reading until we reach a non-whitespace character, or the end of the line
+    // unless newlines are consumed too; every line break is trivia of its own
+    fn consume_whitespace(&mut self, newlines: bool) {
+        while self.at_whitespace() {
+            let mut text = vec![];
+            let kind = if self.starts_with(CRLF) || self.at_newline() {
+                if !newlines {
+                    return;
+                }
+                let length = match self.at_newline() {
+                    true => 1,
+                    false => CRLF.len(),
+                };
+                self.take(&mut text, length);
+                TriviaKind::Newline
+            } else {
+                while self.at_whitespace() && !self.at_newline() && !self.starts_with(CRLF) {
+                    self.take(&mut text, 1);
+                }
+                TriviaKind::Whitespace
+            };
+
+            self.add_trivia(kind, &String::from_utf8_lossy(&text));
         }
     }
 }
+
+// scans a program held in memory
+impl FromStr for Scanner {
+    type Err = String;
+
+    fn from_str(program: &str) -> Result<Self, Self::Err> {
+        Self::from_reader(Cursor::new(program.to_owned().into_bytes()))
+    }
+}
+
+// the tokens of the source, scanned one at a time as they are asked for
+pub struct Tokens<'a> {
+    scanner: &'a mut Scanner,
+}
+
+impl<'a> Tokens<'a> {
+    // the lexer of the scanner, which names the kinds of the tokens
+    pub fn get_lexer(&self) -> &Lexer {
+        &self.scanner.lexer
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scanner.next_result()
+    }
+}
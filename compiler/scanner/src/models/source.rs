@@ -0,0 +1,91 @@
+use std::io::{self, Read};
+
+// the number of bytes read from the source at a time
+const CHUNK_SIZE: usize = 8192;
+
+// the bytes of a program, read from any source in chunks as the scanner
+// needs them; only the bytes that were not consumed yet are kept
+pub struct Source {
+    reader: Box<dyn Read>,
+    bytes: Vec<u8>,
+    // the index of the current byte in bytes
+    start: usize,
+    exhausted: bool,
+}
+
+impl Source {
+    pub fn new<R: Read + 'static>(reader: R) -> Self {
+        Self {
+            reader: Box::new(reader),
+            bytes: vec![],
+            start: 0,
+            exhausted: false,
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(io::empty())
+    }
+
+    // returns the nth byte from the current one, reading more of the source if needed
+    pub fn get(&mut self, n: usize) -> Result<Option<u8>, String> {
+        while self.start + n >= self.bytes.len() {
+            match self.fill() {
+                Ok(true) => {}
+                Ok(false) => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Some(self.bytes[self.start + n]))
+    }
+
+    // the bytes read but not consumed yet
+    pub fn remaining(&self) -> &[u8] {
+        &self.bytes[self.start..]
+    }
+
+    // whether the whole source was read
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        self.start = (self.start + n).min(self.bytes.len());
+
+        // drop the consumed bytes once they take up more than a chunk
+        if self.start >= CHUNK_SIZE {
+            self.bytes.drain(..self.start);
+            self.start = 0;
+        }
+    }
+
+    // reads the next chunk of the source, returns false once there is nothing left
+    pub fn fill(&mut self) -> Result<bool, String> {
+        if self.exhausted {
+            return Ok(false);
+        }
+
+        let mut chunk = [0; CHUNK_SIZE];
+        let size = loop {
+            match self.reader.read(&mut chunk) {
+                Ok(size) => break size,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                // the source ends where it could not be read
+                Err(e) => {
+                    self.exhausted = true;
+                    let error = format!("could not read program: {}", e);
+                    return Err(error);
+                }
+            }
+        };
+
+        if size == 0 {
+            self.exhausted = true;
+            return Ok(false);
+        }
+
+        self.bytes.extend_from_slice(&chunk[..size]);
+        Ok(true)
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::{self, Display};
+
+// a place in the source, lines and columns start at 1 and columns count characters
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self { line: 1, column: 1 }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+// the part of the source a token was read from: its byte offset and length,
+// the position of its first character and the position right after its last one
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Span {
+    pub offset: usize,
+    pub length: usize,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(offset: usize, length: usize, start: Position, end: Position) -> Self {
+        Self {
+            offset,
+            length,
+            start,
+            end,
+        }
+    }
+
+    // the span of the bytes of text, the source it was read from, between offset and
+    // offset + length
+    pub fn slice(&self, text: &str, offset: usize, length: usize) -> Self {
+        let position = |offset: usize| {
+            let mut position = self.start;
+            for character in text[..offset].chars() {
+                if character == '\n' {
+                    position.line += 1;
+                    position.column = 1;
+                } else {
+                    position.column += 1;
+                }
+            }
+            position
+        };
+
+        Self::new(
+            self.offset + offset,
+            length,
+            position(offset),
+            position(offset + length),
+        )
+    }
+
+    // the same span in a source where the text at from was moved to to, delta bytes away;
+    // positions on the line of from move along with it, positions on later lines only
+    // change lines; the span must not start before from
+    pub fn moved(&self, delta: isize, from: Position, to: Position) -> Self {
+        let position = |position: Position| {
+            let column = match position.line == from.line {
+                true => position.column - from.column + to.column,
+                false => position.column,
+            };
+            Position {
+                line: position.line - from.line + to.line,
+                column,
+            }
+        };
+
+        Self::new(
+            self.offset.wrapping_add_signed(delta),
+            self.length,
+            position(self.start),
+            position(self.end),
+        )
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.start)
+    }
+}
@@ -1,6 +1,9 @@
 use lazy_static::lazy_static;
+use std::fmt;
+use std::str::FromStr;
 
-use super::automata::Automata;
+use super::span::Span;
+use super::trivia::Trivia;
 use hash_map::HashMap;
 
 lazy_static! {
@@ -39,6 +42,52 @@ lazy_static! {
     ]);
 }
 
+// the token kinds by name, as they appear in the lexer specification; a kind's code
+// is its index in the list, the kinds the specification defines come after them
+const KINDS: [(&str, TokenKind); 41] = [
+    ("Unknown", TokenKind::Unknown),
+    ("Identifier", TokenKind::Identifier),
+    ("Constant", TokenKind::Constant),
+    ("Plus", TokenKind::Plus),
+    ("Minus", TokenKind::Minus),
+    ("Multiply", TokenKind::Multiply),
+    ("Divide", TokenKind::Divide),
+    ("Modulo", TokenKind::Modulo),
+    ("Assign", TokenKind::Assign),
+    ("Equal", TokenKind::Equal),
+    ("NotEqual", TokenKind::NotEqual),
+    ("Less", TokenKind::Less),
+    ("Greater", TokenKind::Greater),
+    ("LessEqual", TokenKind::LessEqual),
+    ("GreaterEqual", TokenKind::GreaterEqual),
+    ("And", TokenKind::And),
+    ("Or", TokenKind::Or),
+    ("ParenOpen", TokenKind::ParenOpen),
+    ("ParenClose", TokenKind::ParenClose),
+    ("BracketOpen", TokenKind::BracketOpen),
+    ("BracketClose", TokenKind::BracketClose),
+    ("BraceOpen", TokenKind::BraceOpen),
+    ("BraceClose", TokenKind::BraceClose),
+    ("Colon", TokenKind::Colon),
+    ("SemiColon", TokenKind::SemiColon),
+    ("KeywordNumber", TokenKind::KeywordNumber),
+    ("KeywordChar", TokenKind::KeywordChar),
+    ("KeywordString", TokenKind::KeywordString),
+    ("KeywordArray", TokenKind::KeywordArray),
+    ("KeywordInput", TokenKind::KeywordInput),
+    ("KeywordOutput", TokenKind::KeywordOutput),
+    ("KeywordIf", TokenKind::KeywordIf),
+    ("KeywordElse", TokenKind::KeywordElse),
+    ("KeywordWhile", TokenKind::KeywordWhile),
+    ("EOF", TokenKind::EOF),
+    ("NumberConstant", TokenKind::NumberConstant),
+    ("CharConstant", TokenKind::CharConstant),
+    ("StringConstant", TokenKind::StringConstant),
+    ("DecimalConstant", TokenKind::DecimalConstant),
+    ("Comment", TokenKind::Comment),
+    ("DocComment", TokenKind::DocComment),
+];
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum TokenKind {
     Unknown, // Other
@@ -86,6 +135,74 @@ pub enum TokenKind {
 
     // Special Tokens
     EOF,
+
+    // Typed Constants, listed last so the codes of the other kinds stay the same
+    NumberConstant,
+    CharConstant,
+    StringConstant,
+    DecimalConstant,
+
+    // Trivia, only produced when the scanner keeps comments
+    Comment,
+    DocComment,
+
+    // A kind named only in the lexer specification, i.e. an operator added there;
+    // its name is kept by the lexer that defines it
+    Defined { code: usize },
+}
+
+impl TokenKind {
+    pub fn is_constant(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Constant
+                | TokenKind::NumberConstant
+                | TokenKind::CharConstant
+                | TokenKind::StringConstant
+                | TokenKind::DecimalConstant
+        )
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, TokenKind::Comment | TokenKind::DocComment)
+    }
+
+    // the kind of the index-th new name the specification gives
+    pub fn defined(index: usize) -> Self {
+        TokenKind::Defined {
+            code: KINDS.len() + index,
+        }
+    }
+
+    // the index of the name of a kind the specification defines, among the new names
+    pub fn defined_index(&self) -> Option<usize> {
+        match self {
+            TokenKind::Defined { code } => Some(code - KINDS.len()),
+            _ => None,
+        }
+    }
+
+    // the index of the kind in the enum, the kinds the specification defines come last
+    pub fn code(&self) -> usize {
+        match self {
+            TokenKind::Defined { code } => *code,
+            kind => KINDS
+                .iter()
+                .position(|(_, other)| other == kind)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// the name of the kind, as it appears in the lexer specification; only the lexer
+// knows the names of the kinds the specification defines, see `Lexer::kind_name`
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenKind::Defined { code } => write!(f, "Defined({})", code),
+            kind => write!(f, "{}", KINDS[kind.code()].0),
+        }
+    }
 }
 
 impl From<&str> for TokenKind {
@@ -98,11 +215,33 @@ impl From<&str> for TokenKind {
     }
 }
 
+// parses the name of a token kind, i.e. "Plus"
+impl FromStr for TokenKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match KINDS.iter().find(|(other, _)| *other == name) {
+            Some((_, kind)) => Ok(*kind),
+            None => {
+                let error = format!("unknown token kind '{}'", name);
+                Err(error)
+            }
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Token {
     kind: TokenKind,
     inner: String,
     position: usize,
+    span: Span,
+    // the text of the doc comments right before the token
+    documentation: Option<String>,
+    // the source text around the token, only in lossless mode: what comes before it
+    // since the last token, and what follows it on the same line
+    leading_trivia: Vec<Trivia>,
+    trailing_trivia: Vec<Trivia>,
 }
 
 impl From<&str> for Token {
@@ -118,13 +257,13 @@ impl Token {
             kind,
             inner: String::from(inner),
             position: 0,
+            span: Span::default(),
+            documentation: None,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
         }
     }
 
-    pub fn unknown(inner: &str) -> Self {
-        Self::new(TokenKind::Unknown, inner)
-    }
-
     pub fn get_kind(&self) -> TokenKind {
         self.kind
     }
@@ -134,13 +273,13 @@ impl Token {
     }
 
     pub fn key(&self) -> usize {
-        // let kind = match self.kind {
-        //     TokenKind::Number | TokenKind::Char | TokenKind::String => TokenKind::Constant,
-        //     _ => self.kind,
-        // };
+        // every constant has the code of Constant, whatever its type
+        let kind = match self.kind {
+            kind if kind.is_constant() => TokenKind::Constant,
+            kind => kind,
+        };
 
-        // return the index of the token kind in the enum
-        self.kind as usize
+        kind.code()
     }
 
     pub fn value(&self) -> usize {
@@ -151,15 +290,36 @@ impl Token {
         self.position = position;
     }
 
-    pub fn classify(&mut self, automata: &Automata) {
-        if self.kind != TokenKind::Unknown {
-            return;
-        }
+    // where the token was read from, the default span for tokens made up by the caller
+    pub fn get_span(&self) -> Span {
+        self.span
+    }
 
-        self.kind = if let Some(kind) = TOKENS.get(&self.inner.as_str()) {
-            *kind
-        } else {
-            automata.classify(&self.inner)
-        };
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+
+    pub fn get_documentation(&self) -> Option<&str> {
+        self.documentation.as_deref()
+    }
+
+    pub fn set_documentation(&mut self, documentation: Option<String>) {
+        self.documentation = documentation;
+    }
+
+    pub fn get_leading_trivia(&self) -> &Vec<Trivia> {
+        &self.leading_trivia
+    }
+
+    pub fn set_leading_trivia(&mut self, trivia: Vec<Trivia>) {
+        self.leading_trivia = trivia;
+    }
+
+    pub fn get_trailing_trivia(&self) -> &Vec<Trivia> {
+        &self.trailing_trivia
+    }
+
+    pub fn set_trailing_trivia(&mut self, trivia: Vec<Trivia>) {
+        self.trailing_trivia = trivia;
     }
 }
@@ -0,0 +1,28 @@
+// the source text between tokens, kept by the scanner in lossless mode; bytes that are
+// not UTF-8 are kept as U+FFFD, like in the lexemes
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TriviaKind {
+    // spaces and tabs
+    Whitespace,
+    // a single line break, "\n" or "\r\n"
+    Newline,
+    // comments, unless the scanner produces them as tokens
+    Comment,
+    // the text of a lexeme that was reported as a lexical error
+    Skipped,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: String::from(text),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+// the characters a program may use outside of literals and comments
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CharacterSet {
+    // identifiers are made of ASCII letters, other characters only appear in literals
+    #[default]
+    Ascii,
+    // identifiers follow UAX #31: a XID_Start character followed by XID_Continue characters
+    Unicode,
+}
+
+// the lexer only knows ASCII, so every other character is read as an ASCII stand-in:
+// letters of identifiers as a letter, the characters only literals may hold as a symbol
+// that starts no token, and bytes that are not UTF-8 as a character no pattern accepts
+const IDENTIFIER_STAND_IN: char = 'a';
+const LITERAL_STAND_IN: char = '`';
+const INVALID_STAND_IN: char = '\u{1}';
+
+// a character decoded from the start of some bytes, None if the bytes are not UTF-8
+pub struct Decoded {
+    pub character: Option<char>,
+    pub length: usize,
+}
+
+// decodes the first character of the bytes, returns None if there are no bytes or the
+// character is cut short, unless there is nothing more to read
+pub fn decode(bytes: &[u8], at_end: bool) -> Option<Decoded> {
+    let first = *bytes.first()?;
+    let length = match first {
+        0x00..=0x7f => {
+            return Some(Decoded {
+                character: Some(first as char),
+                length: 1,
+            })
+        }
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => 0,
+    };
+
+    let invalid = Decoded {
+        character: None,
+        length: 1,
+    };
+    if length == 0 {
+        return Some(invalid);
+    }
+
+    // every byte after the first must be a continuation byte
+    let available = length.min(bytes.len());
+    if bytes[1..available].iter().any(|byte| byte & 0xc0 != 0x80) {
+        return Some(invalid);
+    }
+    // a character cut short by the end of the source is a single invalid one
+    if available < length {
+        return match at_end {
+            true => Some(Decoded {
+                character: None,
+                length: available,
+            }),
+            false => None,
+        };
+    }
+
+    match std::str::from_utf8(&bytes[..length]) {
+        Ok(text) => Some(Decoded {
+            character: text.chars().next(),
+            length,
+        }),
+        // overlong encodings and surrogates
+        Err(_) => Some(invalid),
+    }
+}
+
+// the characters the lexer reads for the bytes, up to the first character that is cut short
+pub fn stand_ins(
+    bytes: &[u8],
+    at_end: bool,
+    character_set: CharacterSet,
+) -> impl Iterator<Item = char> + '_ {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let decoded = decode(&bytes[position..], at_end)?;
+        position += decoded.length;
+
+        let character = match decoded.character {
+            Some(character) if character.is_ascii() => character,
+            Some(character) => match character_set {
+                CharacterSet::Unicode if is_xid_continue(character) => IDENTIFIER_STAND_IN,
+                _ => LITERAL_STAND_IN,
+            },
+            None => INVALID_STAND_IN,
+        };
+
+        Some(character)
+    })
+}
+
+// the number of bytes taken by the first characters of the bytes
+pub fn byte_length(bytes: &[u8], characters: usize, at_end: bool) -> usize {
+    let mut length = 0;
+    for _ in 0..characters {
+        match decode(&bytes[length..], at_end) {
+            Some(decoded) => length += decoded.length,
+            None => break,
+        }
+    }
+
+    length
+}
+
+// characters that cannot be told apart from a space or from nothing at all when the
+// program is read: the default ignorable code points (UAX #44), bidirectional
+// controls and non-ASCII whitespace
+pub fn is_invisible(character: char) -> bool {
+    matches!(
+        character,
+        '\u{85}'
+            | '\u{a0}'
+            | '\u{ad}'
+            | '\u{34f}'
+            | '\u{61c}'
+            | '\u{115f}'..='\u{1160}'
+            | '\u{1680}'
+            | '\u{17b4}'..='\u{17b5}'
+            | '\u{180b}'..='\u{180f}'
+            | '\u{2000}'..='\u{200f}'
+            | '\u{2028}'..='\u{202f}'
+            | '\u{205f}'..='\u{206f}'
+            | '\u{3000}'
+            | '\u{3164}'
+            | '\u{fe00}'..='\u{fe0f}'
+            | '\u{feff}'
+            | '\u{ffa0}'
+            | '\u{fff0}'..='\u{fff8}'
+            | '\u{1bca0}'..='\u{1bca3}'
+            | '\u{1d173}'..='\u{1d17a}'
+            | '\u{e0000}'..='\u{e0fff}'
+    )
+}
+
+pub fn is_identifier(identifier: &str) -> bool {
+    let mut characters = identifier.chars();
+    match characters.next() {
+        Some(first) => is_xid_start(first) && characters.all(is_xid_continue),
+        None => false,
+    }
+}
+
+// letters of other scripts that look like a Latin one, with the letter they look like,
+// a part of the confusables of UTS #39
+const CONFUSABLES: [(char, char); 55] = [
+    // Cyrillic
+    ('а', 'a'),
+    ('в', 'b'),
+    ('е', 'e'),
+    ('һ', 'h'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('ӏ', 'l'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('ԛ', 'q'),
+    ('ѕ', 's'),
+    ('с', 'c'),
+    ('у', 'y'),
+    ('ԝ', 'w'),
+    ('х', 'x'),
+    ('ԁ', 'd'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('Е', 'E'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('Н', 'H'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('С', 'C'),
+    ('Ѕ', 'S'),
+    ('Т', 'T'),
+    ('Х', 'X'),
+    ('Ү', 'Y'),
+    ('Ԝ', 'W'),
+    // Greek
+    ('α', 'a'),
+    ('ι', 'i'),
+    ('κ', 'k'),
+    ('ν', 'v'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('υ', 'u'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+    ('χ', 'x'),
+    ('ϳ', 'j'),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+// identifiers that may be mistaken for another one (UTS #39): letters from scripts
+// that look alike mixed in the same identifier, i.e. a Cyrillic 'а' among Latin letters,
+// and fullwidth or mathematical forms of ASCII letters
+pub fn is_confusable(identifier: &str) -> bool {
+    let mut scripts: Vec<Script> = vec![];
+    for character in identifier.chars() {
+        let script = match character {
+            'A'..='Z' | 'a'..='z' | '\u{c0}'..='\u{24f}' | '\u{1e00}'..='\u{1eff}' => Script::Latin,
+            '\u{370}'..='\u{3ff}' | '\u{1f00}'..='\u{1fff}' => Script::Greek,
+            '\u{400}'..='\u{52f}' => Script::Cyrillic,
+            '\u{ff00}'..='\u{ffef}' | '\u{1d400}'..='\u{1d7ff}' => return true,
+            _ => continue,
+        };
+
+        if !scripts.contains(&script) {
+            scripts.push(script);
+        }
+    }
+
+    scripts.len() > 1
+}
+
+// the skeleton of an identifier (UTS #39): every letter replaced by the one it looks like,
+// so two identifiers that cannot be told apart, i.e. a Latin 'a' and a Cyrillic 'а',
+// have the same skeleton
+pub fn skeleton(identifier: &str) -> String {
+    identifier
+        .chars()
+        .map(
+            |character| match CONFUSABLES.iter().find(|(other, _)| *other == character) {
+                Some((_, latin)) => *latin,
+                None => match character {
+                    // fullwidth forms of ASCII
+                    '\u{ff01}'..='\u{ff5e}' => char::from_u32(character as u32 - 0xfee0).unwrap(),
+                    _ => character,
+                },
+            },
+        )
+        .collect()
+}
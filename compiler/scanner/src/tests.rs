@@ -0,0 +1,10 @@
+mod automata;
+mod cli;
+mod comment;
+mod lexer;
+mod literal;
+mod relex;
+mod scanner;
+mod trivia;
+mod unicode;
+mod writer;
@@ -0,0 +1,89 @@
+#[allow(unused_imports)]
+use std::{env, fs};
+
+#[allow(unused_imports)]
+use crate::models::automata::Automata;
+
+// a number automaton that only accepts "7"
+#[allow(dead_code)]
+const SEVEN: &str = "7\n0 1\n0\n1\n0 7 1\n";
+
+#[allow(dead_code)]
+fn directory(name: &str) -> std::path::PathBuf {
+    let directory =
+        env::temp_dir().join(format!("scanner-automata-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+#[test]
+fn test_search_path() {
+    let empty = directory("empty");
+    let first = directory("first");
+    let second = directory("second");
+    fs::write(first.join("number.dfa"), SEVEN).unwrap();
+    fs::write(second.join("number.dfa"), "not an automaton").unwrap();
+    fs::write(second.join("char.dfa"), SEVEN).unwrap();
+
+    // the first directory that has a file wins, the others fall back to the embedded files
+    let automata = Automata::from_search_path(&[&empty, &first, &second]).unwrap();
+    let number = automata.get_automaton("number").unwrap();
+    assert!(number.validate("7"));
+    assert!(!number.validate("8"));
+    assert!(automata.get_automaton("char").unwrap().validate("7"));
+    assert!(automata
+        .get_automaton("identifier")
+        .unwrap()
+        .validate("abc"));
+    assert!(automata
+        .get_automaton("string")
+        .unwrap()
+        .validate("\"abc\""));
+
+    // the variable is a list of directories, as PATH is
+    let variable = env::join_paths([&empty, &second]).unwrap();
+    let error = Automata::from_variable(Some(variable)).err().unwrap();
+    assert!(error.starts_with("[number]"), "{}", error);
+
+    // without the variable, or without overrides, the embedded automata are used
+    for automata in [
+        Automata::from_variable(None).unwrap(),
+        Automata::from_search_path(&[&empty]).unwrap(),
+    ] {
+        let number = automata.get_automaton("number").unwrap();
+        assert!(number.validate("1_000"));
+        assert!(!number.validate("1_"));
+    }
+
+    for directory in [empty, first, second] {
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
+
+#[test]
+fn test_from_strs() {
+    // every automaton is loaded by name, not by its position
+    let mut sources = vec![("number", SEVEN)];
+    let embedded = Automata::embedded().unwrap();
+    assert!(embedded.get_automaton("number").unwrap().validate("8"));
+
+    let error = Automata::from_strs(&sources).err().unwrap();
+    assert_eq!(error, "[identifier] missing automaton");
+
+    let identifier = "a\n0 1\n0\n1\n0 a 1\n";
+    for name in [
+        "string",
+        "char",
+        "decimal_number",
+        "binary_number",
+        "octal_number",
+        "hex_number",
+        "identifier",
+    ] {
+        sources.push((name, identifier));
+    }
+    let automata = Automata::from_strs(&sources).unwrap();
+    assert!(automata.get_automaton("number").unwrap().validate("7"));
+    assert!(automata.get_automaton("string").unwrap().validate("a"));
+    assert!(automata.get_automaton("unknown").is_none());
+}
@@ -0,0 +1,146 @@
+#[allow(unused_imports)]
+use std::{env, fs};
+
+#[allow(unused_imports)]
+use crate::cli::{self, Input};
+#[allow(unused_imports)]
+use crate::models::unicode::CharacterSet;
+#[allow(unused_imports)]
+use crate::utils::writer::OutputFormat;
+
+#[allow(dead_code)]
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| String::from(*arg)).collect()
+}
+
+#[allow(dead_code)]
+fn file(path: &str, name: &str) -> Input {
+    Input::File {
+        path: String::from(path),
+        name: String::from(name),
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let options = cli::parse_args(&args(&[
+        "--unicode",
+        "-f",
+        "json",
+        "a.oli",
+        "--output",
+        "out",
+        "-q",
+        "-",
+        "--error-limit",
+        "3",
+        "--",
+        "--comments",
+    ]))
+    .unwrap();
+
+    assert_eq!(options.inputs, ["a.oli", "-", "--comments"]);
+    assert_eq!(options.output, Some(String::from("out")));
+    assert_eq!(options.format, Some(OutputFormat::Json));
+    assert_eq!(options.error_limit, Some(3));
+    assert_eq!(options.character_set, CharacterSet::Unicode);
+    assert!(options.quiet);
+    assert!(!options.keep_comments);
+    assert!(!options.lossless);
+    assert!(!options.help);
+
+    // without inputs the default program is scanned, and stdin is printed as it is read
+    let options = cli::parse_args(&[]).unwrap();
+    assert_eq!(options.inputs, ["p3"]);
+    assert_eq!(options.format, None);
+    assert_eq!(options.output, None);
+}
+
+#[test]
+fn test_parse_args_errors() {
+    let error = |arguments: &[&str]| cli::parse_args(&args(arguments)).err().unwrap();
+
+    assert_eq!(error(&["--bogus"]), "unknown option '--bogus'");
+    assert_eq!(error(&["p1", "--output"]), "--output needs a value");
+    assert_eq!(
+        error(&["-f", "xml"]),
+        "unknown output format 'xml', expected one of text, json, csv, pif"
+    );
+    assert_eq!(
+        error(&["-l", "0"]),
+        "the error limit must be a positive number, not '0'"
+    );
+}
+
+#[test]
+fn test_resolve_inputs() {
+    let directory = env::temp_dir().join("scanner-cli-inputs");
+    fs::create_dir_all(directory.join("nested.oli")).unwrap();
+    fs::write(directory.join("b.oli"), "b = 2;").unwrap();
+    fs::write(directory.join("a.oli"), "a = 1;").unwrap();
+    fs::write(directory.join("c.txt"), "").unwrap();
+    let directory = directory.to_string_lossy();
+
+    // globs are sorted and only match files, programs can be named
+    let inputs = cli::resolve_inputs(&args(&[
+        &format!("{}/*.oli", directory),
+        "-",
+        "p1",
+        &format!("{}/c.txt", directory),
+    ]))
+    .unwrap();
+    let program = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/p1.oli");
+    assert_eq!(
+        inputs,
+        [
+            file(&format!("{}/a.oli", directory), "a"),
+            file(&format!("{}/b.oli", directory), "b"),
+            Input::Stdin,
+            file(program, "p1"),
+            file(&format!("{}/c.txt", directory), "c"),
+        ]
+    );
+
+    let error = |input: &str| cli::resolve_inputs(&args(&[input])).err().unwrap();
+    assert_eq!(error("p9"), "no such file 'p9'");
+    assert_eq!(
+        error(&format!("{}/*.olive", directory)),
+        format!("no files match '{}/*.olive'", directory)
+    );
+    assert_eq!(
+        error(&format!("{}/nested.oli", directory)),
+        format!("'{}/nested.oli' is not a file", directory)
+    );
+}
+
+#[test]
+fn test_output_paths() {
+    let inputs = [file("x/a.oli", "a"), file("y/b.oli", "b"), Input::Stdin];
+
+    let paths = cli::output_paths(&inputs, Some("out/"), "tokens.json").unwrap();
+    assert_eq!(
+        paths,
+        [
+            Some(String::from("out/a/tokens.json")),
+            Some(String::from("out/b/tokens.json")),
+            Some(String::from("out/stdin/tokens.json")),
+        ]
+    );
+
+    // stdin is written to stdout unless an output directory is given
+    let paths = cli::output_paths(&inputs, None, "tokens.out").unwrap();
+    assert!(paths[0].as_ref().unwrap().ends_with("/output/a/tokens.out"));
+    assert_eq!(paths[2], None);
+
+    let paths = cli::output_paths(&inputs, Some("-"), "tokens.out").unwrap();
+    assert_eq!(paths, [None, None, None]);
+
+    // inputs with the same name would overwrite each other
+    let inputs = [file("x/a.oli", "a"), file("y/a.oli", "a")];
+    assert_eq!(
+        cli::output_paths(&inputs, Some("out"), "pif.out"),
+        Err(String::from(
+            "'x/a.oli' and 'y/a.oli' would both be written to out/a/pif.out"
+        ))
+    );
+}
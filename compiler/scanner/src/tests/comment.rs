@@ -0,0 +1,113 @@
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use crate::models::error::{LexError, LexErrorKind};
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::span::{Position, Span};
+#[allow(unused_imports)]
+use crate::models::token::{Token, TokenKind};
+
+#[allow(dead_code)]
+fn scan(program: &str, keep_comments: bool) -> Vec<Result<Token, LexError>> {
+    let mut scanner = Scanner::from_str(program).unwrap();
+    scanner.set_keep_comments(keep_comments);
+    scanner.tokens().collect()
+}
+
+#[allow(dead_code)]
+fn kinds(program: &str, keep_comments: bool) -> Vec<(TokenKind, String)> {
+    scan(program, keep_comments)
+        .into_iter()
+        .map(|result| {
+            let token = result.unwrap();
+            (token.get_kind(), String::from(token.get_inner()))
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+fn token(kind: TokenKind, inner: &str) -> (TokenKind, String) {
+    (kind, String::from(inner))
+}
+
+#[test]
+fn test_skipped_comments() {
+    let program = "-- one\n\n\n-- two\na {- block {- nested -} -} = 1 -- end";
+    assert_eq!(
+        kinds(program, false),
+        [
+            token(TokenKind::Identifier, "a"),
+            token(TokenKind::Assign, "="),
+            token(TokenKind::NumberConstant, "1"),
+        ]
+    );
+}
+
+#[test]
+fn test_kept_comments() {
+    let program = "---- rule\n--- doc\na = 1 {- x {- y -} -}\n";
+    assert_eq!(
+        kinds(program, true),
+        [
+            token(TokenKind::Comment, "---- rule"),
+            token(TokenKind::DocComment, "--- doc"),
+            token(TokenKind::Identifier, "a"),
+            token(TokenKind::Assign, "="),
+            token(TokenKind::NumberConstant, "1"),
+            token(TokenKind::Comment, "{- x {- y -} -}"),
+        ]
+    );
+
+    let spans: Vec<Span> = scan(program, true)
+        .into_iter()
+        .map(|result| result.unwrap().get_span())
+        .collect();
+    let position = |line, column| Position { line, column };
+    assert_eq!(spans[1], Span::new(10, 7, position(2, 1), position(2, 8)));
+    assert_eq!(spans[5], Span::new(24, 15, position(3, 7), position(3, 22)));
+}
+
+#[test]
+fn test_documentation() {
+    let program =
+        "--- the count\n---   of items\ncount: number;\n-- not documentation\nitems: number";
+    let tokens: Vec<Token> = scan(program, false)
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect();
+
+    // the doc comments go to the token that starts the declaration after them
+    assert_eq!(tokens[0].get_inner(), "count");
+    assert_eq!(tokens[0].get_documentation(), Some("the count\n  of items"));
+    assert!(tokens[1..]
+        .iter()
+        .all(|token| token.get_documentation().is_none()));
+}
+
+#[test]
+fn test_unterminated_comment() {
+    let results = scan("a {- one {- two -}\nb", false);
+    let errors: Vec<String> = results
+        .iter()
+        .filter_map(|result| result.as_ref().err())
+        .map(|e| e.to_string())
+        .collect();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        errors,
+        ["Lexical error on line 1, column 3 => unterminated comment"]
+    );
+    assert_eq!(
+        results[1].as_ref().unwrap_err().get_span(),
+        Span::new(
+            2,
+            18,
+            Position { line: 1, column: 3 },
+            Position { line: 2, column: 2 }
+        )
+    );
+}
@@ -0,0 +1,159 @@
+#[allow(unused_imports)]
+use crate::models::automata::Automata;
+#[allow(unused_imports)]
+use crate::models::lexer::Lexer;
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::token::TokenKind;
+
+#[allow(dead_code)]
+fn chars(program: &str) -> Vec<char> {
+    program.chars().collect()
+}
+
+#[allow(dead_code)]
+fn longest_match(lexer: &Lexer, program: &str) -> Option<(usize, TokenKind)> {
+    let (longest, _) = lexer.next_match(program.chars());
+    longest
+}
+
+#[test]
+fn test_longest_match() {
+    let automata = Automata::embedded().unwrap();
+    let lexer = Lexer::embedded(&automata).unwrap();
+
+    // the longest token wins over its prefixes
+    assert_eq!(
+        longest_match(&lexer, "<= 1"),
+        Some((2, TokenKind::LessEqual))
+    );
+    assert_eq!(longest_match(&lexer, "< 1"), Some((1, TokenKind::Less)));
+    assert_eq!(
+        longest_match(&lexer, "1_000;"),
+        Some((5, TokenKind::NumberConstant))
+    );
+    assert_eq!(
+        longest_match(&lexer, "2.5e-3;"),
+        Some((6, TokenKind::DecimalConstant))
+    );
+    // numbers have no sign, the minus is an operator of its own
+    assert_eq!(longest_match(&lexer, "-5;"), Some((1, TokenKind::Minus)));
+    assert_eq!(
+        longest_match(&lexer, "whiles"),
+        Some((6, TokenKind::Identifier))
+    );
+
+    // on a tie, the pattern listed first wins
+    assert_eq!(
+        longest_match(&lexer, "while ("),
+        Some((5, TokenKind::KeywordWhile))
+    );
+
+    // the error patterns
+    assert_eq!(
+        longest_match(&lexer, "1a = 2"),
+        Some((2, TokenKind::Unknown))
+    );
+    assert_eq!(longest_match(&lexer, "'ab'"), Some((4, TokenKind::Unknown)));
+    assert_eq!(longest_match(&lexer, "!"), None);
+}
+
+#[test]
+fn test_specification_change() {
+    let automata = Automata::embedded().unwrap();
+    let includes = [("token.in", "==\n!=\n")];
+
+    let specification = "include token.in\nIdentifier dfa identifier\n";
+    let lexer = Lexer::new(("lexer.in", specification), &includes, &automata).unwrap();
+    assert_eq!(longest_match(&lexer, "<>"), None);
+
+    // adding an operator takes a single line
+    let specification = "include token.in\nNotEqual \"<>\"\nIdentifier dfa identifier\n";
+    let lexer = Lexer::new(("lexer.in", specification), &includes, &automata).unwrap();
+    assert_eq!(longest_match(&lexer, "<>"), Some((2, TokenKind::NotEqual)));
+    assert_eq!(longest_match(&lexer, "!="), Some((2, TokenKind::NotEqual)));
+    assert_eq!(
+        longest_match(&lexer, "ab"),
+        Some((2, TokenKind::Identifier))
+    );
+}
+
+#[test]
+fn test_specification_kinds() {
+    let automata = Automata::embedded().unwrap();
+    let includes = [("token.in", "+\n+= PlusAssign\n;\n")];
+
+    // a new operator takes a single line, with a kind named only in the specification,
+    // whose code comes after the ones of the token kinds
+    let specification =
+        "include token.in\nShift \"<<\"\nPlusAssign \"=+\"\nIdentifier dfa identifier\n";
+    let lexer = Lexer::new(("lexer.in", specification), &includes, &automata).unwrap();
+
+    let (_, plus_assign) = longest_match(&lexer, "+= 1").unwrap();
+    let (_, shift) = longest_match(&lexer, "<< 1").unwrap();
+    assert_eq!(lexer.kind_name(plus_assign), "PlusAssign");
+    assert_eq!(lexer.kind_name(shift), "Shift");
+    assert_eq!(lexer.kind_name(TokenKind::Plus), "Plus");
+    assert_eq!(plus_assign.code(), TokenKind::DocComment.code() + 1);
+    assert_eq!(shift.code(), TokenKind::DocComment.code() + 2);
+    // naming the kind again gives the same kind
+    assert_eq!(longest_match(&lexer, "=+"), Some((2, plus_assign)));
+    assert_eq!(longest_match(&lexer, "+"), Some((1, TokenKind::Plus)));
+
+    // the scanner takes the new kinds like any other token
+    let mut scanner = Scanner::with_lexer(lexer);
+    scanner.scan_reader("a += b << c;".as_bytes()).unwrap();
+    assert_eq!(scanner.get_errors().len(), 0);
+    let tokens: Vec<(String, usize)> = scanner
+        .get_token_list()
+        .iter()
+        .filter(|token| token.get_kind() != TokenKind::EOF)
+        .map(|token| (String::from(token.get_inner()), token.key()))
+        .collect();
+    let identifier = TokenKind::Identifier.code();
+    let semicolon = TokenKind::SemiColon.code();
+    assert_eq!(
+        tokens,
+        vec![
+            (String::from("a"), identifier),
+            (String::from("+="), plus_assign.code()),
+            (String::from("b"), identifier),
+            (String::from("<<"), shift.code()),
+            (String::from("c"), identifier),
+            (String::from(";"), semicolon),
+        ]
+    );
+}
+
+#[test]
+fn test_specification_errors() {
+    let automata = Automata::embedded().unwrap();
+    let includes = [("token.in", "+\n?\n")];
+
+    let cases = [
+        ("plural \"+\"", "lexer.in:1: invalid token kind 'plural'"),
+        ("Plus_2 \"+\"", "lexer.in:1: invalid token kind 'Plus_2'"),
+        ("Plus", "lexer.in:1: missing pattern for 'Plus'"),
+        ("Plus +", "lexer.in:1: invalid pattern '+'"),
+        (
+            "-- comment\nPlus dfa plus",
+            "lexer.in:2: unknown automaton 'plus'",
+        ),
+        ("include other.in", "lexer.in:1: cannot include 'other.in'"),
+        (
+            "include token.in",
+            "lexer.in:1: token.in:2: no token kind for '?', name one after it",
+        ),
+    ];
+    for (specification, expected) in cases {
+        let result = Lexer::new(("lexer.in", specification), &includes, &automata);
+        assert_eq!(result.err(), Some(String::from(expected)));
+    }
+
+    let result = Lexer::new(("lexer.in", "Plus /(+/"), &includes, &automata);
+    assert!(result
+        .err()
+        .unwrap()
+        .starts_with("lexer.in:1: invalid regex '(+'"));
+}
@@ -0,0 +1,198 @@
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use crate::models::error::{LexError, LexErrorKind};
+#[allow(unused_imports)]
+use crate::models::literal::{self, Constant, ConstantValue};
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::span::{Position, Span};
+#[allow(unused_imports)]
+use crate::models::token::TokenKind;
+
+#[allow(dead_code)]
+fn position(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+#[allow(dead_code)]
+fn decode(lexeme: &str) -> Result<String, LexErrorKind> {
+    literal::decode(lexeme).map_err(|e| e.kind)
+}
+
+#[test]
+fn test_escapes() {
+    assert_eq!(decode(r#""a\"b""#), Ok(String::from("a\"b")));
+    assert_eq!(decode(r#""\\ \' \n \t""#), Ok(String::from("\\ ' \n \t")));
+    assert_eq!(decode(r"'\''"), Ok(String::from("'")));
+    assert_eq!(decode(r"'\n'"), Ok(String::from("\n")));
+    assert_eq!(decode("'é'"), Ok(String::from("é")));
+    assert_eq!(decode("42"), Ok(String::from("42")));
+
+    assert_eq!(
+        decode(r#""a\qb""#),
+        Err(LexErrorKind::InvalidEscape(String::from(r"\q")))
+    );
+    assert_eq!(
+        decode(r#""a\""#),
+        Err(LexErrorKind::UnterminatedLiteral(String::from(r#""a\""#)))
+    );
+    assert_eq!(
+        decode("'ab'"),
+        Err(LexErrorKind::UndefinedToken(String::from("'ab'")))
+    );
+}
+
+#[test]
+fn test_constant_table() {
+    let program =
+        r#"s = "say \"hi\"\n"; c = '\t'; n = 0x10; m = 16; k = -9_223_372_036_854_775_808"#;
+    let mut scanner = Scanner::from_str(program).unwrap();
+    let tokens: Vec<_> = scanner.tokens().map(|result| result.unwrap()).collect();
+
+    // the table keeps the typed value along with the source text
+    let constants: Vec<(TokenKind, &Constant)> = tokens
+        .iter()
+        .filter(|token| token.get_kind().is_constant())
+        .map(|token| {
+            let constant = scanner.get_constant_table().get(&token.value()).unwrap();
+            (token.get_kind(), constant)
+        })
+        .collect();
+    let expected = [
+        (
+            TokenKind::StringConstant,
+            r#""say \"hi\"\n""#,
+            ConstantValue::Str(String::from("say \"hi\"\n")),
+        ),
+        (TokenKind::CharConstant, r"'\t'", ConstantValue::Char('\t')),
+        (TokenKind::NumberConstant, "0x10", ConstantValue::Number(16)),
+        // the same value as "0x10", so the same entry
+        (TokenKind::NumberConstant, "0x10", ConstantValue::Number(16)),
+        // the minus is a token of its own, so the number is the magnitude of i64::MIN
+        (
+            TokenKind::NumberConstant,
+            "9_223_372_036_854_775_808",
+            ConstantValue::Number(i64::MIN.unsigned_abs()),
+        ),
+    ];
+    assert_eq!(constants.len(), expected.len());
+    for ((kind, constant), (expected_kind, text, value)) in constants.iter().zip(expected) {
+        assert_eq!(*kind, expected_kind);
+        assert_eq!(constant.text, text);
+        assert_eq!(constant.value, value);
+    }
+    assert_eq!(scanner.get_constant_table().len(), 4);
+}
+
+#[test]
+fn test_numbers() {
+    let program = "a = 1_000 + 0xFF_ff + 0o17 + 0b1010 + 2.5 + 1e3 + 6.02E+23 + 0x1e;";
+    let mut scanner = Scanner::from_str(program).unwrap();
+    let tokens: Vec<_> = scanner.tokens().map(|result| result.unwrap()).collect();
+    let constants: Vec<(TokenKind, ConstantValue)> = tokens
+        .iter()
+        .filter(|token| token.get_kind().is_constant())
+        .map(|token| {
+            let constant = scanner.get_constant_table().get(&token.value()).unwrap();
+            (token.get_kind(), constant.value.clone())
+        })
+        .collect();
+
+    // separators are dropped, and an 'e' in a hex number is a digit
+    let expected = [
+        (TokenKind::NumberConstant, ConstantValue::Number(1000)),
+        (TokenKind::NumberConstant, ConstantValue::Number(0xffff)),
+        (TokenKind::NumberConstant, ConstantValue::Number(0o17)),
+        (TokenKind::NumberConstant, ConstantValue::Number(0b1010)),
+        (TokenKind::DecimalConstant, ConstantValue::Decimal(2.5)),
+        (TokenKind::DecimalConstant, ConstantValue::Decimal(1e3)),
+        (TokenKind::DecimalConstant, ConstantValue::Decimal(6.02e23)),
+        (TokenKind::NumberConstant, ConstantValue::Number(0x1e)),
+    ];
+    assert_eq!(constants, expected);
+    assert_eq!(ConstantValue::Decimal(1e3).to_string(), "1000.0");
+
+    // a separator goes between digits, and a fraction needs digits
+    for lexeme in [
+        "1_", "1__0", "0x", "0x_1", "0b12", "0o8", "1.", "1.e3", "1e",
+    ] {
+        let mut scanner = Scanner::from_str(lexeme).unwrap();
+        let errors: Vec<LexError> = scanner.tokens().filter_map(|result| result.err()).collect();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| e.get_kind().clone())
+                .collect::<Vec<_>>(),
+            [LexErrorKind::UndefinedToken(String::from(lexeme))],
+            "{}",
+            lexeme
+        );
+    }
+}
+
+#[test]
+fn test_number_range() {
+    let mut scanner = Scanner::from_str("a = -9223372036854775809;").unwrap();
+    let errors: Vec<String> = scanner
+        .tokens()
+        .filter_map(|result| result.err())
+        .map(|e| e.to_string())
+        .collect();
+
+    assert_eq!(
+        errors,
+        ["Lexical error on line 1, column 6 => number out of range: 9223372036854775809"]
+    );
+    assert_eq!(scanner.get_constant_table().len(), 0);
+
+    assert_eq!(
+        literal::parse("0x8000_0000_0000_0000").map_err(|e| e.kind),
+        Ok(ConstantValue::Number(1 << 63))
+    );
+    assert_eq!(
+        literal::parse("0x8000_0000_0000_0001").map_err(|e| e.kind),
+        Err(LexErrorKind::NumberOutOfRange(String::from(
+            "0x8000_0000_0000_0001"
+        )))
+    );
+    assert_eq!(
+        literal::parse("18446744073709551616").map_err(|e| e.kind),
+        Err(LexErrorKind::NumberOutOfRange(String::from(
+            "18446744073709551616"
+        )))
+    );
+    assert_eq!(
+        literal::parse("1e400").map_err(|e| e.kind),
+        Err(LexErrorKind::NumberOutOfRange(String::from("1e400")))
+    );
+}
+
+#[test]
+fn test_literal_errors() {
+    let mut scanner = Scanner::from_str("a = \"x\\qy\" + 1\nb = \"open \\\"\nc = 'é\n").unwrap();
+    let errors: Vec<LexError> = scanner.tokens().filter_map(|result| result.err()).collect();
+
+    // an invalid escape points at the escape, an unterminated literal runs to the end of its line
+    let expected = [
+        (
+            LexErrorKind::InvalidEscape(String::from(r"\q")),
+            Span::new(6, 2, position(1, 7), position(1, 9)),
+        ),
+        (
+            LexErrorKind::UnterminatedLiteral(String::from("\"open \\\"")),
+            Span::new(19, 8, position(2, 5), position(2, 13)),
+        ),
+        (
+            LexErrorKind::UnterminatedLiteral(String::from("'é")),
+            Span::new(32, 3, position(3, 5), position(3, 7)),
+        ),
+    ];
+    let errors: Vec<(LexErrorKind, Span)> = errors
+        .into_iter()
+        .map(|e| (e.get_kind().clone(), e.get_span()))
+        .collect();
+    assert_eq!(errors, expected);
+}
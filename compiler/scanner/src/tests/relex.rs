@@ -0,0 +1,244 @@
+#[allow(unused_imports)]
+use std::fs;
+#[allow(unused_imports)]
+use std::io::Cursor;
+
+#[allow(unused_imports)]
+use automata::Random;
+
+#[allow(unused_imports)]
+use crate::models::edit::Edit;
+#[allow(unused_imports)]
+use crate::models::error::LexError;
+#[allow(unused_imports)]
+use crate::models::literal;
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::token::{Token, TokenKind};
+#[allow(unused_imports)]
+use crate::models::unicode::CharacterSet;
+
+#[allow(dead_code)]
+const PROGRAMS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/");
+
+// comments, literals and line breaks of every kind, for the edits to break and mend
+#[allow(dead_code)]
+const SNIPPET: &str =
+    "--- the answer\r\nanswer: number; -- trailing\n{- a {- nested -}\n comment -} \
+    s = \"it's \\\"ok\\\"\\n\"; c = '\\t'\n\tif (answer >= 42) { output(\"yes\"); } --- dangling\n\
+    x=1a; y = 'ab' {- open";
+
+// the edits are made of pieces that open and close comments and literals
+#[allow(dead_code)]
+const PIECES: [&str; 22] = [
+    "", "a", "b1", "9", " ", "\t", "\n", "\r\n", "\"", "'", "\\", "{-", "-}", "--", "---", "<",
+    "=", ";", "if", "é", "\"x\"", "\u{200b}",
+];
+
+#[allow(dead_code)]
+fn configure(scanner: &mut Scanner, mode: usize) {
+    scanner.set_keep_comments(mode & 1 != 0);
+    scanner.set_lossless(mode & 2 != 0);
+    scanner.set_character_set(match mode & 4 != 0 {
+        true => CharacterSet::Unicode,
+        false => CharacterSet::Ascii,
+    });
+}
+
+#[allow(dead_code)]
+fn scan(scanner: &mut Scanner, text: &str) -> Vec<Result<Token, LexError>> {
+    scanner.set_reader(Cursor::new(text.to_owned().into_bytes()));
+    scanner.tokens().collect()
+}
+
+// the keys of identifiers and constants may differ, as long as they lead to the same entry
+#[allow(dead_code)]
+fn assert_same(
+    relexed: &[Result<Token, LexError>],
+    scanner: &Scanner,
+    expected: &[Result<Token, LexError>],
+    context: &str,
+) {
+    assert_eq!(relexed.len(), expected.len(), "{}", context);
+    for (result, expected) in relexed.iter().zip(expected) {
+        let (token, expected) = match (result, expected) {
+            (Ok(token), Ok(expected)) => (token, expected),
+            (result, expected) => {
+                assert_eq!(
+                    result.as_ref().err(),
+                    expected.as_ref().err(),
+                    "{}",
+                    context
+                );
+                continue;
+            }
+        };
+
+        assert_eq!(token.get_kind(), expected.get_kind(), "{}", context);
+        assert_eq!(token.get_inner(), expected.get_inner(), "{}", context);
+        assert_eq!(token.get_span(), expected.get_span(), "{}", context);
+        assert_eq!(
+            token.get_documentation(),
+            expected.get_documentation(),
+            "{}",
+            context
+        );
+        assert_eq!(
+            token.get_leading_trivia(),
+            expected.get_leading_trivia(),
+            "{}",
+            context
+        );
+        assert_eq!(
+            token.get_trailing_trivia(),
+            expected.get_trailing_trivia(),
+            "{}",
+            context
+        );
+
+        match token.get_kind() {
+            TokenKind::Identifier => assert_eq!(
+                scanner.get_identifier_table().get(&token.value()),
+                Some(&String::from(token.get_inner())),
+                "{}",
+                context
+            ),
+            kind if kind.is_constant() => assert_eq!(
+                scanner
+                    .get_constant_table()
+                    .get(&token.value())
+                    .map(|constant| &constant.value),
+                literal::parse(token.get_inner()).ok().as_ref(),
+                "{}",
+                context
+            ),
+            _ => {}
+        }
+    }
+}
+
+// an edit of at most a few characters at a character boundary
+#[allow(dead_code)]
+fn random_edit(text: &str, random: &mut Random) -> Edit {
+    let boundaries: Vec<usize> = (0..=text.len())
+        .filter(|index| text.is_char_boundary(*index))
+        .collect();
+    let start = random.below(boundaries.len());
+    let end = (start + random.below(4)).min(boundaries.len() - 1);
+    let inserted = random.choose(&PIECES).unwrap();
+
+    Edit::new(
+        boundaries[start],
+        boundaries[end] - boundaries[start],
+        inserted,
+    )
+}
+
+#[test]
+fn test_relex_random_edits() {
+    let mut programs = vec![String::from(SNIPPET)];
+    for program in ["p1", "p1err", "p2", "p3"] {
+        programs.push(fs::read_to_string(format!("{}{}.oli", PROGRAMS, program)).unwrap());
+    }
+
+    let mut scanner = Scanner::new().unwrap();
+    let mut reference = Scanner::new().unwrap();
+    let mut random = Random::new(49);
+    for (index, program) in programs.iter().enumerate() {
+        for mode in 0..8 {
+            configure(&mut scanner, mode);
+            configure(&mut reference, mode);
+
+            let mut text = program.clone();
+            let mut results = scan(&mut scanner, &text);
+            // every result is compared with a full scan after every edit, and edits pile up
+            for step in 0..40 {
+                let edit = random_edit(&text, &mut random);
+                text = edit.apply(&text).unwrap();
+
+                results = scanner.relex(&text, &results, &edit).unwrap();
+                let expected = scan(&mut reference, &text);
+                let context = format!(
+                    "program {}, mode {}, step {}, {:?}",
+                    index, mode, step, edit
+                );
+                assert_same(&results, &scanner, &expected, &context);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_relex_literals_and_comments() {
+    let mut scanner = Scanner::new().unwrap();
+    let text = "a = \"one\";\nb = 2;\nc = 3;\n";
+    let results = scan(&mut scanner, text);
+
+    // closing a string early turns the rest of it into an error on its line only
+    let edit = Edit::new(7, 0, "\"");
+    let edited = edit.apply(text).unwrap();
+    let relexed = scanner.relex(&edited, &results, &edit).unwrap();
+    assert_eq!(edited, "a = \"on\"e\";\nb = 2;\nc = 3;\n");
+    assert_eq!(relexed.iter().filter(|result| result.is_err()).count(), 1);
+    assert_eq!(relexed.len(), results.len() + 1);
+
+    // opening a block comment hides everything after it, and the comment is never closed
+    let edit = Edit::new(11, 0, "{-");
+    let edited = edit.apply(text).unwrap();
+    let relexed = scanner.relex(&edited, &results, &edit).unwrap();
+    let kinds: Vec<Option<TokenKind>> = relexed
+        .iter()
+        .map(|result| result.as_ref().ok().map(|token| token.get_kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            Some(TokenKind::Identifier),
+            Some(TokenKind::Assign),
+            Some(TokenKind::StringConstant),
+            Some(TokenKind::SemiColon),
+            None,
+        ]
+    );
+
+    // identifiers keep their keys, even the ones before the edit
+    let edit = Edit::new(18, 1, "d");
+    let edited = edit.apply(text).unwrap();
+    let relexed = scanner.relex(&edited, &results, &edit).unwrap();
+    let a = relexed[0].as_ref().unwrap();
+    assert_eq!(a.value(), results[0].as_ref().unwrap().value());
+    let d = relexed[8].as_ref().unwrap();
+    assert_eq!(d.get_inner(), "d");
+    assert_eq!(
+        scanner.get_identifier_table().get(&d.value()),
+        Some(&String::from("d"))
+    );
+
+    // the trivia after a token may change with an edit further along its line
+    let mut reference = Scanner::new().unwrap();
+    scanner.set_lossless(true);
+    reference.set_lossless(true);
+    let text = "a = b -\nc = 1;\n";
+    let results = scan(&mut scanner, text);
+    let edit = Edit::new(7, 0, "-");
+    let edited = edit.apply(text).unwrap();
+    let relexed = scanner.relex(&edited, &results, &edit).unwrap();
+    let expected = scan(&mut reference, &edited);
+    assert_same(&relexed, &scanner, &expected, "trailing comment");
+    assert_eq!(
+        relexed[2]
+            .as_ref()
+            .unwrap()
+            .get_trailing_trivia()
+            .last()
+            .unwrap()
+            .text,
+        "--"
+    );
+
+    // an edit that does not belong to the source is rejected
+    assert!(scanner
+        .relex(text, &results, &Edit::new(2, 0, "x"))
+        .is_err());
+}
@@ -0,0 +1,256 @@
+#[allow(unused_imports)]
+use std::io::{self, Read};
+#[allow(unused_imports)]
+use std::str::FromStr;
+#[allow(unused_imports)]
+use std::{env, fs};
+
+#[allow(unused_imports)]
+use crate::models::error::{LexError, LexErrorKind};
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::span::{Position, Span};
+#[allow(unused_imports)]
+use crate::models::token::Token;
+
+#[allow(dead_code)]
+fn scan(name: &str, program: &str) -> Scanner {
+    let path = env::temp_dir().join(format!("scanner-{}.oli", name));
+    fs::write(&path, program).unwrap();
+
+    let mut scanner = Scanner::new().unwrap();
+    scanner.scan(&path.to_string_lossy()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    scanner
+}
+
+#[allow(dead_code)]
+fn position(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+#[test]
+fn test_spans() {
+    let scanner = scan("spans", "-- é\nab: number\n\tc = \"x y\";\n");
+    let spans: Vec<(&str, Span)> = scanner
+        .get_token_list()
+        .iter()
+        .map(|token| (token.get_inner(), token.get_span()))
+        .collect();
+
+    // offsets and lengths count bytes, columns count characters
+    let expected = [
+        ("ab", Span::new(6, 2, position(2, 1), position(2, 3))),
+        (":", Span::new(8, 1, position(2, 3), position(2, 4))),
+        ("number", Span::new(10, 6, position(2, 5), position(2, 11))),
+        ("c", Span::new(18, 1, position(3, 2), position(3, 3))),
+        ("=", Span::new(20, 1, position(3, 4), position(3, 5))),
+        ("\"x y\"", Span::new(22, 5, position(3, 6), position(3, 11))),
+        (";", Span::new(27, 1, position(3, 11), position(3, 12))),
+    ];
+    assert_eq!(spans, expected);
+}
+
+#[test]
+fn test_span_display() {
+    let scanner = scan("display", "a =\n  b");
+    let spans: Vec<String> = scanner
+        .get_token_list()
+        .iter()
+        .map(|token| token.get_span().to_string())
+        .collect();
+
+    assert_eq!(spans, ["1:1", "1:3", "2:3"]);
+}
+
+#[test]
+fn test_error_recovery() {
+    let scanner = scan("recovery", "1a: number\nb = 'hello' + 2\nc = 3b;\n");
+
+    let errors: Vec<String> = scanner.get_errors().iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        errors,
+        [
+            "Lexical error on line 1, column 1 => undefined token: 1a",
+            "Lexical error on line 2, column 5 => undefined token: 'hello'",
+            "Lexical error on line 3, column 5 => undefined token: 3b",
+        ]
+    );
+
+    // the tokens around the errors are kept
+    let tokens: Vec<&str> = scanner
+        .get_token_list()
+        .iter()
+        .map(|token| token.get_inner())
+        .collect();
+    assert_eq!(tokens, [":", "number", "b", "=", "+", "2", "c", "=", ";"]);
+}
+
+#[test]
+fn test_error_limit() {
+    let path = env::temp_dir().join("scanner-limit.oli");
+    fs::write(&path, "1a 2b 3c d").unwrap();
+
+    let mut scanner = Scanner::new().unwrap();
+    scanner.set_error_limit(Some(2));
+    scanner.scan(&path.to_string_lossy()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let errors: Vec<&LexErrorKind> = scanner.get_errors().iter().map(|e| e.get_kind()).collect();
+    assert_eq!(
+        errors,
+        [
+            &LexErrorKind::UndefinedToken(String::from("1a")),
+            &LexErrorKind::UndefinedToken(String::from("2b")),
+        ]
+    );
+    assert!(scanner.get_token_list().is_empty());
+}
+
+// hands out the bytes of a program a few at a time, then fails if asked to
+#[allow(dead_code)]
+struct ChunkedReader {
+    bytes: Vec<u8>,
+    chunk_size: usize,
+    fail: bool,
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if self.bytes.is_empty() && self.fail {
+            return Err(io::Error::other("connection lost"));
+        }
+
+        let size = self.chunk_size.min(buffer.len()).min(self.bytes.len());
+        buffer[..size].copy_from_slice(&self.bytes[..size]);
+        self.bytes.drain(..size);
+        Ok(size)
+    }
+}
+
+#[allow(dead_code)]
+fn inners(results: Vec<Result<Token, LexError>>) -> Vec<String> {
+    results
+        .into_iter()
+        .map(|result| match result {
+            Ok(token) => String::from(token.get_inner()),
+            Err(e) => e.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_from_str() {
+    let mut scanner = Scanner::from_str("while (a <= 10) -- loop\n").unwrap();
+    let tokens = inners(scanner.tokens().collect());
+    assert_eq!(tokens, ["while", "(", "a", "<=", "10", ")"]);
+
+    // the tables are filled as the tokens are read
+    assert_eq!(scanner.get_identifier_table().len(), 1);
+    assert_eq!(scanner.get_constant_table().len(), 1);
+}
+
+#[test]
+fn test_from_reader() {
+    let program = "b: string = \"a long string\" -- é\nc: char = 'x'";
+    let expected = inners(Scanner::from_str(program).unwrap().tokens().collect());
+    assert_eq!(expected.len(), 10);
+
+    // tokens and characters split between chunks
+    for chunk_size in 1..=4 {
+        let reader = ChunkedReader {
+            bytes: program.as_bytes().to_vec(),
+            chunk_size,
+            fail: false,
+        };
+        let mut scanner = Scanner::from_reader(reader).unwrap();
+        let tokens = inners(scanner.tokens().collect());
+        assert_eq!(tokens, expected, "{}", chunk_size);
+    }
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let reader = ChunkedReader {
+        bytes: b"a \xff b \xe2\x82".to_vec(),
+        chunk_size: 2,
+        fail: false,
+    };
+    let mut scanner = Scanner::from_reader(reader).unwrap();
+    let tokens = inners(scanner.tokens().collect());
+    assert_eq!(
+        tokens,
+        [
+            "a",
+            "Lexical error on line 1, column 3 => undefined token: \u{fffd}",
+            "b",
+            "Lexical error on line 1, column 7 => undefined token: \u{fffd}",
+        ]
+    );
+}
+
+#[test]
+fn test_nul_byte() {
+    // a NUL byte is a character like any other, not the end of the source
+    let mut scanner = Scanner::from_str("a = 1;\0 b = @;").unwrap();
+    let tokens = inners(scanner.tokens().collect());
+    assert_eq!(
+        tokens,
+        [
+            "a",
+            "=",
+            "1",
+            ";",
+            "Lexical error on line 1, column 7 => undefined token: \0",
+            "b",
+            "=",
+            "Lexical error on line 1, column 13 => undefined token: @",
+            ";",
+        ]
+    );
+
+    // nor does it end a literal or a comment early, it is read like any other control character
+    let program = "a = \"x\0y\"; -- \0\nb = @;";
+    let tokens = inners(Scanner::from_str(program).unwrap().tokens().collect());
+    let control = program.replace('\0', "\u{1}");
+    let control: Vec<String> = inners(Scanner::from_str(&control).unwrap().tokens().collect())
+        .iter()
+        .map(|token| token.replace('\u{1}', "\0"))
+        .collect();
+    assert_eq!(tokens, control);
+    assert!(tokens.contains(&String::from(
+        "Lexical error on line 1, column 7 => undefined token: \0"
+    )));
+    assert_eq!(
+        tokens[tokens.len() - 3..],
+        [
+            "=",
+            "Lexical error on line 2, column 5 => undefined token: @",
+            ";"
+        ]
+    );
+}
+
+#[test]
+fn test_read_error() {
+    let reader = ChunkedReader {
+        bytes: b"a = 1;\nb".to_vec(),
+        chunk_size: 3,
+        fail: true,
+    };
+    let mut scanner = Scanner::from_reader(reader).unwrap();
+    let tokens = inners(scanner.tokens().collect());
+    assert_eq!(
+        tokens,
+        [
+            "a",
+            "=",
+            "1",
+            ";",
+            "b",
+            "Read error on line 2, column 2 => could not read program: connection lost",
+        ]
+    );
+}
@@ -0,0 +1,140 @@
+#[allow(unused_imports)]
+use std::fs::{self, File};
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::token::{Token, TokenKind};
+#[allow(unused_imports)]
+use crate::models::trivia::{Trivia, TriviaKind};
+
+#[allow(dead_code)]
+const PROGRAMS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/");
+
+// the source text of the tokens, with their trivia
+#[allow(dead_code)]
+fn source(tokens: &[Token]) -> String {
+    let mut source = String::new();
+    for token in tokens {
+        for trivia in token.get_leading_trivia() {
+            source.push_str(&trivia.text);
+        }
+        source.push_str(token.get_inner());
+        for trivia in token.get_trailing_trivia() {
+            source.push_str(&trivia.text);
+        }
+    }
+
+    source
+}
+
+#[allow(dead_code)]
+fn lossless(scanner: &mut Scanner) -> Vec<Token> {
+    scanner.set_lossless(true);
+    scanner.tokens().filter_map(|result| result.ok()).collect()
+}
+
+#[test]
+fn test_round_trip() {
+    let mut programs = 0;
+    for entry in fs::read_dir(PROGRAMS).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "oli") {
+            continue;
+        }
+
+        let program = fs::read_to_string(&path).unwrap();
+        let mut scanner = Scanner::from_reader(File::open(&path).unwrap()).unwrap();
+        let tokens = lossless(&mut scanner);
+        assert_eq!(source(&tokens), program, "{}", path.display());
+
+        // kept comments are tokens, with trivia of their own
+        let mut scanner = Scanner::from_reader(File::open(&path).unwrap()).unwrap();
+        scanner.set_keep_comments(true);
+        let tokens = lossless(&mut scanner);
+        assert_eq!(source(&tokens), program, "{}", path.display());
+
+        programs += 1;
+    }
+
+    assert!(programs > 0);
+}
+
+#[test]
+fn test_round_trip_invalid_utf8() {
+    // the text is UTF-8, a byte that is not comes back as a replacement character
+    let mut scanner = Scanner::from_reader(&b"a\xffb -- \xfe\n"[..]).unwrap();
+    let tokens = lossless(&mut scanner);
+    assert_eq!(source(&tokens), "a\u{fffd}b -- \u{fffd}\n");
+
+    let skipped: Vec<&Trivia> = tokens
+        .iter()
+        .flat_map(|token| token.get_leading_trivia())
+        .filter(|trivia| trivia.kind == TriviaKind::Skipped)
+        .collect();
+    assert_eq!(skipped, [&Trivia::new(TriviaKind::Skipped, "\u{fffd}")]);
+}
+
+#[test]
+fn test_trivia() {
+    let program = "a = 1 -- one\n\n  b {- x -} =\r\n'c' ~";
+    let mut scanner = Scanner::from_str(program).unwrap();
+    let tokens = lossless(&mut scanner);
+    assert_eq!(source(&tokens), program);
+
+    let trivia = |token: &Token| {
+        (
+            token.get_leading_trivia().clone(),
+            token.get_trailing_trivia().clone(),
+        )
+    };
+    let text = Trivia::new;
+
+    // a comment on the line of a token follows it, what comes after the line
+    // leads the next token
+    assert_eq!(
+        trivia(&tokens[2]),
+        (
+            vec![],
+            vec![
+                text(TriviaKind::Whitespace, " "),
+                text(TriviaKind::Comment, "-- one")
+            ]
+        )
+    );
+    assert_eq!(
+        trivia(&tokens[3]),
+        (
+            vec![
+                text(TriviaKind::Newline, "\n"),
+                text(TriviaKind::Newline, "\n"),
+                text(TriviaKind::Whitespace, "  ")
+            ],
+            vec![text(TriviaKind::Whitespace, " ")]
+        )
+    );
+    assert_eq!(
+        trivia(&tokens[4]),
+        (
+            vec![
+                text(TriviaKind::Comment, "{- x -}"),
+                text(TriviaKind::Whitespace, " ")
+            ],
+            vec![]
+        )
+    );
+
+    // the lexeme of an error is skipped, the trivia at the end belongs to an EOF token
+    let last = tokens.last().unwrap();
+    assert_eq!(last.get_kind(), TokenKind::EOF);
+    assert_eq!(trivia(last), (vec![text(TriviaKind::Skipped, "~")], vec![]));
+    assert_eq!(
+        trivia(&tokens[5]),
+        (
+            vec![text(TriviaKind::Newline, "\r\n")],
+            vec![text(TriviaKind::Whitespace, " ")]
+        )
+    );
+}
@@ -0,0 +1,147 @@
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use crate::models::error::LexError;
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::token::Token;
+#[allow(unused_imports)]
+use crate::models::unicode::{self, CharacterSet};
+
+#[allow(dead_code)]
+fn scan(program: &str, character_set: CharacterSet) -> Vec<String> {
+    let mut scanner = Scanner::from_str(program).unwrap();
+    scanner.set_character_set(character_set);
+
+    scanner
+        .tokens()
+        .map(|result: Result<Token, LexError>| match result {
+            Ok(token) => format!("{:?} {}", token.get_kind(), token.get_inner()),
+            Err(e) => e.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_decode() {
+    let decoded = unicode::decode("é!".as_bytes(), false).unwrap();
+    assert_eq!((decoded.character, decoded.length), (Some('é'), 2));
+
+    // a character cut short waits for the rest of it, unless there is nothing left
+    assert!(unicode::decode(b"\xe2\x82", false).is_none());
+    let decoded = unicode::decode(b"\xe2\x82", true).unwrap();
+    assert_eq!((decoded.character, decoded.length), (None, 2));
+
+    // stray continuation bytes and overlong encodings
+    let decoded = unicode::decode(b"\x80a", false).unwrap();
+    assert_eq!((decoded.character, decoded.length), (None, 1));
+    let decoded = unicode::decode(b"\xe0\x80\x80", false).unwrap();
+    assert_eq!((decoded.character, decoded.length), (None, 1));
+
+    assert_eq!(unicode::byte_length("aé😀".as_bytes(), 3, true), 7);
+}
+
+#[test]
+fn test_ascii_identifiers() {
+    assert_eq!(
+        scan("naïve = 1", CharacterSet::Ascii),
+        [
+            "Identifier na",
+            "Lexical error on line 1, column 3 => undefined token: ï",
+            "Identifier ve",
+            "Assign =",
+            "NumberConstant 1",
+        ]
+    );
+}
+
+#[test]
+fn test_unicode_identifiers() {
+    assert_eq!(
+        scan("naïve = größe + Δ", CharacterSet::Unicode),
+        [
+            "Identifier naïve",
+            "Assign =",
+            "Identifier größe",
+            "Plus +",
+            "Identifier Δ",
+        ]
+    );
+
+    // a combining mark may continue an identifier but not start one
+    assert_eq!(
+        scan("\u{301}x", CharacterSet::Unicode),
+        ["Lexical error on line 1, column 1 => undefined token: \u{301}x"]
+    );
+}
+
+#[test]
+fn test_confusable_identifiers() {
+    // a Cyrillic 'а' among Latin letters, and fullwidth letters
+    assert_eq!(
+        scan("pаy ｆｏｏ", CharacterSet::Unicode),
+        [
+            "Lexical error on line 1, column 1 => confusable identifier: pаy",
+            "Lexical error on line 1, column 5 => confusable identifier: ｆｏｏ",
+        ]
+    );
+
+    // a Latin 'a' and a Cyrillic 'а' are two identifiers that look the same, each one
+    // of a single script; the one seen first is taken, the other is confusable
+    assert_eq!(
+        scan(
+            "a = 1;
+а = a;
+b = а;",
+            CharacterSet::Unicode
+        ),
+        [
+            "Identifier a",
+            "Assign =",
+            "NumberConstant 1",
+            "SemiColon ;",
+            "Lexical error on line 2, column 1 => confusable identifier: а",
+            "Assign =",
+            "Identifier a",
+            "SemiColon ;",
+            "Identifier b",
+            "Assign =",
+            "Lexical error on line 3, column 5 => confusable identifier: а",
+            "SemiColon ;",
+        ]
+    );
+}
+
+#[test]
+fn test_invisible_characters() {
+    let program = "a\u{200b}b -- hid\u{202e}den\n\"x\u{feff}\"";
+    let expected = [
+        "Identifier a",
+        "Lexical error on line 1, column 2 => invisible character: U+200B",
+        "Identifier b",
+        "Lexical error on line 1, column 11 => invisible character: U+202E",
+        "Lexical error on line 2, column 3 => invisible character: U+FEFF",
+    ];
+
+    // rejected the same way whatever the character set
+    assert_eq!(scan(program, CharacterSet::Ascii), expected);
+    assert_eq!(scan(program, CharacterSet::Unicode), expected);
+}
+
+#[test]
+fn test_literals() {
+    let program = "c = 'é'\ns = \"héllo ✓ 😀\"";
+    let expected = [
+        "Identifier c",
+        "Assign =",
+        "CharConstant 'é'",
+        "Identifier s",
+        "Assign =",
+        "StringConstant \"héllo ✓ 😀\"",
+    ];
+
+    assert_eq!(scan(program, CharacterSet::Ascii), expected);
+    assert_eq!(scan(program, CharacterSet::Unicode), expected);
+}
@@ -0,0 +1,219 @@
+#[allow(unused_imports)]
+use std::str::FromStr;
+
+#[allow(unused_imports)]
+use crate::models::scanner::Scanner;
+#[allow(unused_imports)]
+use crate::models::token::TokenKind;
+#[allow(unused_imports)]
+use crate::utils::writer::{OutputFormat, ScanOutput};
+#[allow(unused_imports)]
+use hash_map::Table;
+
+#[allow(dead_code)]
+const PROGRAM_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/");
+
+#[allow(dead_code)]
+fn scan(program: &str) -> Scanner {
+    let mut scanner = Scanner::new().unwrap();
+    let path = format!("{}{}.oli", PROGRAM_DIR, program);
+    scanner.scan(&path).unwrap();
+    scanner
+}
+
+#[allow(dead_code)]
+fn format(scanner: &Scanner, format: OutputFormat) -> String {
+    format.writer().format(&scanner.output()).unwrap()
+}
+
+#[test]
+fn test_output_format_names() {
+    assert_eq!(OutputFormat::from_str("text"), Ok(OutputFormat::Text));
+    assert_eq!(OutputFormat::from_str("json"), Ok(OutputFormat::Json));
+    assert_eq!(OutputFormat::from_str("csv"), Ok(OutputFormat::Csv));
+    assert_eq!(OutputFormat::from_str("pif"), Ok(OutputFormat::Pif));
+    assert_eq!(
+        OutputFormat::from_str("xml"),
+        Err(String::from(
+            "unknown output format 'xml', expected one of text, json, csv, pif"
+        ))
+    );
+    assert_eq!(OutputFormat::default(), OutputFormat::Text);
+}
+
+#[test]
+fn test_text_output() {
+    // the text format is the one of the committed tokens.out files
+    for program in ["p1", "p2", "p3", "p1err"] {
+        let scanner = scan(program);
+        let expected = std::fs::read_to_string(format!(
+            "{}/output/{}/tokens.out",
+            env!("CARGO_MANIFEST_DIR"),
+            program
+        ))
+        .unwrap();
+        assert_eq!(format(&scanner, OutputFormat::Text), expected);
+    }
+}
+
+#[test]
+fn test_json_output() {
+    let scanner = scan("p2");
+    let json: serde_json::Value =
+        serde_json::from_str(&format(&scanner, OutputFormat::Json)).unwrap();
+
+    assert_eq!(json["format"], "tokens");
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["result"], "Lexically correct!");
+
+    let tokens = json["tokens"].as_array().unwrap();
+    assert_eq!(tokens.len(), scanner.get_token_list().len());
+    for (token, expected) in tokens.iter().zip(scanner.get_token_list()) {
+        assert_eq!(
+            token["kind"],
+            scanner.output().lexer.kind_name(expected.get_kind())
+        );
+        assert_eq!(token["code"], expected.key());
+        assert_eq!(token["lexeme"], expected.get_inner());
+        assert_eq!(token["position"], expected.value());
+        assert_eq!(token["span"]["offset"], expected.get_span().offset);
+        assert_eq!(
+            token["span"]["start"]["line"],
+            expected.get_span().start.line
+        );
+    }
+
+    // the tables are sorted by key
+    let identifiers = json["identifiers"].as_array().unwrap();
+    assert_eq!(identifiers.len(), scanner.get_identifier_table().len());
+    let keys: Vec<u64> = identifiers
+        .iter()
+        .map(|identifier| identifier["key"].as_u64().unwrap())
+        .collect();
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // numbers are JSON numbers, chars and strings are JSON strings
+    let constants = json["constants"].as_array().unwrap();
+    assert_eq!(constants.len(), scanner.get_constant_table().len());
+    for constant in constants {
+        match constant["type"].as_str().unwrap() {
+            "Number" => assert!(constant["value"].is_i64()),
+            _ => assert!(constant["value"].is_string()),
+        }
+    }
+    assert!(json["errors"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_json_errors() {
+    let scanner = scan("p1err");
+    let json: serde_json::Value =
+        serde_json::from_str(&format(&scanner, OutputFormat::Json)).unwrap();
+
+    assert_eq!(json["result"], "Lexically incorrect!");
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), scanner.get_errors().len());
+    assert_eq!(errors[0]["kind"], "UndefinedToken");
+    assert_eq!(errors[0]["value"], "1a");
+    assert_eq!(errors[0]["message"], scanner.get_errors()[0].to_string());
+    assert_eq!(errors[0]["span"]["start"]["line"], 3);
+    assert_eq!(errors[0]["span"]["start"]["column"], 1);
+}
+
+#[test]
+fn test_csv_output() {
+    let scanner = scan("p1err");
+    let csv = format(&scanner, OutputFormat::Csv);
+    let rows: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(
+        rows[0],
+        "record,index,kind,code,lexeme,value,line,column,end_line,end_column,offset,length"
+    );
+    let count = |record: &str| {
+        rows.iter()
+            .filter(|row| row.starts_with(&format!("{},", record)))
+            .count()
+    };
+    assert_eq!(count("token"), scanner.get_token_list().len());
+    assert_eq!(count("identifier"), scanner.get_identifier_table().len());
+    assert_eq!(count("constant"), scanner.get_constant_table().len());
+    assert_eq!(count("error"), scanner.get_errors().len());
+    assert_eq!(rows.last(), Some(&"result,1,,,Lexically incorrect!,,,,,,,"));
+
+    // fields with commas are quoted
+    assert!(rows.contains(
+        &"error,1,UndefinedToken,,1a,\"Lexical error on line 3, column 1 => undefined token: 1a\",3,1,3,3,32,2"
+    ));
+}
+
+#[test]
+fn test_constant_types() {
+    let program = "a = 1 + 2.5; b = 'c'; d = \"e\";";
+    let mut scanner = Scanner::from_str(program).unwrap();
+    assert!(scanner.tokens().all(|result| result.is_ok()));
+
+    // every format names the type of a constant the same way
+    let json = format(&scanner, OutputFormat::Json);
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let json_types: Vec<&str> = json["constants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|constant| constant["type"].as_str().unwrap())
+        .collect();
+
+    let csv = format(&scanner, OutputFormat::Csv);
+    let csv_types: Vec<&str> = csv
+        .lines()
+        .filter(|row| row.starts_with("constant,"))
+        .map(|row| row.split(',').nth(2).unwrap())
+        .collect();
+
+    assert_eq!(json_types, ["Number", "Decimal", "Char", "String"]);
+    assert_eq!(csv_types, json_types);
+}
+
+#[test]
+fn test_csv_quoting() {
+    let scanner = Scanner::new().unwrap();
+    let identifiers = Table::new();
+    let constants = Table::new();
+    let output = ScanOutput {
+        result: "a, \"b\"\nc",
+        errors: &[],
+        tokens: &[],
+        identifiers: &identifiers,
+        constants: &constants,
+        lexer: scanner.output().lexer,
+    };
+
+    // fields with a comma, a quote or a line break are quoted, with their quotes doubled
+    let csv = OutputFormat::Csv.writer().format(&output).unwrap();
+    assert!(csv.ends_with("\nresult,1,,,\"a, \"\"b\"\"\nc\",,,,,,,\n"));
+}
+
+#[test]
+fn test_pif_output() {
+    let scanner = scan("p2");
+    let pif = format(&scanner, OutputFormat::Pif);
+    let rows: Vec<&str> = pif.lines().collect();
+
+    let tokens = scanner.get_token_list();
+    assert_eq!(rows.len(), tokens.len() + 2);
+    assert_eq!(
+        rows.last(),
+        Some(&format!("PIF size: {}", tokens.len()).as_str())
+    );
+
+    for (row, token) in rows[1..rows.len() - 1].iter().zip(tokens) {
+        let fields: Vec<&str> = row.split_whitespace().collect();
+        match token.get_kind() {
+            TokenKind::Identifier => assert_eq!(fields, ["id", &token.value().to_string()]),
+            kind if kind.is_constant() => {
+                assert_eq!(fields, ["const", &token.value().to_string()])
+            }
+            _ => assert_eq!(fields, [token.get_inner(), "-1"]),
+        }
+    }
+}
@@ -1,3 +1,8 @@
-pub const EOF_CHAR: &char = &'\0';
 pub const NEWLINE_CHAR: &char = &'\n';
+pub const CRLF: &str = "\r\n";
 pub const LINE_COMMENT: &str = r"--";
+// a line comment documenting the declaration after it, unless it goes on with more dashes
+pub const DOC_COMMENT: &str = r"---";
+// block comments may be nested
+pub const BLOCK_COMMENT_START: &str = r"{-";
+pub const BLOCK_COMMENT_END: &str = r"-}";
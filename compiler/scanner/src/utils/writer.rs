@@ -1,67 +1,135 @@
+mod csv;
+mod json;
+mod pif;
+mod text;
+
 use hash_map::Table;
+use std::str::FromStr;
 use std::{fs::File, io::Write};
 
+use crate::models::error::{LexError, LexErrorKind};
+use crate::models::lexer::Lexer;
+use crate::models::literal::Constant;
 use crate::models::token::Token;
+use crate::utils::constants::BLOCK_COMMENT_START;
+
+// everything the scanner found in a program, as the writers see it
+pub struct ScanOutput<'a> {
+    pub result: &'a str,
+    pub errors: &'a [LexError],
+    pub tokens: &'a [Token],
+    pub identifiers: &'a Table<String>,
+    pub constants: &'a Table<Constant>,
+    // names the kinds of the tokens
+    pub lexer: &'a Lexer,
+}
+
+// a format the scan result can be written in
+pub trait ScanWriter {
+    // the name of the output file, i.e. "tokens.out"
+    fn file_name(&self) -> &'static str;
+
+    fn format(&self, output: &ScanOutput) -> Result<String, String>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    // the program internal form, in the layout of the course
+    Pif,
+}
+
+const FORMATS: [(&str, OutputFormat); 4] = [
+    ("text", OutputFormat::Text),
+    ("json", OutputFormat::Json),
+    ("csv", OutputFormat::Csv),
+    ("pif", OutputFormat::Pif),
+];
+
+impl OutputFormat {
+    pub fn writer(&self) -> Box<dyn ScanWriter> {
+        match self {
+            OutputFormat::Text => Box::new(text::TextWriter),
+            OutputFormat::Json => Box::new(json::JsonWriter),
+            OutputFormat::Csv => Box::new(csv::CsvWriter),
+            OutputFormat::Pif => Box::new(pif::PifWriter),
+        }
+    }
+}
+
+// parses the name of a format, i.e. "json"
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match FORMATS.iter().find(|(format, _)| *format == name) {
+            Some((_, format)) => Ok(*format),
+            None => {
+                let names: Vec<&str> = FORMATS.iter().map(|(format, _)| *format).collect();
+                let error = format!(
+                    "unknown output format '{}', expected one of {}",
+                    name,
+                    names.join(", ")
+                );
+                Err(error)
+            }
+        }
+    }
+}
 
 pub fn write_scan_result(
     file_path: &str,
-    result: &String,
-    token_list: &Vec<Token>,
-    identifier_table: &Table<String>,
-    constant_table: &Table<String>,
+    writer: &dyn ScanWriter,
+    output: &ScanOutput,
 ) -> Result<(), String> {
+    let text = match writer.format(output) {
+        Ok(text) => text,
+        Err(e) => return Err(e),
+    };
+
     let mut file = match File::create(file_path) {
         Ok(file) => file,
         Err(e) => {
-            let error = format!("could not create output file: {}", e.to_string());
+            let error = format!("could not create output file: {}", e);
             return Err(error);
         }
     };
 
-    let mut output = String::new();
-    let tokens = token_list
-        .iter()
-        .map(|entry| {
-            // println!(
-            //     "{:2}, {:2} -> {}",
-            //     entry.key(),
-            //     entry.value(),
-            //     entry.get_inner().replace("\n", "\\n").replace("\0", "\\0")
-            // );
-
-            format!("({:2}, {:2})", entry.key(), entry.value())
-        })
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    output.push_str("Token list:\n");
-    output.push_str(&tokens);
-    output.push_str("\nToken list size: ");
-    output.push_str(&token_list.len().to_string());
-
-    output.push_str("\n\nIdentifier table:\n");
-    for (key, value) in identifier_table {
-        output.push_str(&format!("K: {}, V: {:?}\n", key, value));
-    }
-    output.push_str("Identifier table size: ");
-    output.push_str(&identifier_table.len().to_string());
-
-    output.push_str("\n\nConstant table:\n");
-    for (key, value) in constant_table {
-        output.push_str(&format!("K: {}, V: {:?}\n", key, value));
-    }
-    output.push_str("Constant table size: ");
-    output.push_str(&constant_table.len().to_string());
-
-    output.push_str("\n\n");
-    output.push_str(result);
-    output.push_str("\n");
-
-    match file.write_all(output.as_bytes()) {
+    match file.write_all(text.as_bytes()) {
         Ok(_) => Ok(()),
         Err(e) => {
-            let error = format!("could not write to output file: {}", e.to_string());
+            let error = format!("could not write to output file: {}", e);
             Err(error)
         }
     }
 }
+
+// the name of the kind of a lexical error, and the part of the source it is about
+pub fn error_value(kind: &LexErrorKind) -> (&'static str, String) {
+    match kind {
+        LexErrorKind::UndefinedToken(lexeme) => ("UndefinedToken", lexeme.clone()),
+        LexErrorKind::InvisibleCharacter(character) => {
+            ("InvisibleCharacter", format!("U+{:04X}", *character as u32))
+        }
+        LexErrorKind::ConfusableIdentifier(identifier) => {
+            ("ConfusableIdentifier", identifier.clone())
+        }
+        LexErrorKind::UnterminatedLiteral(lexeme) => ("UnterminatedLiteral", lexeme.clone()),
+        LexErrorKind::InvalidEscape(escape) => ("InvalidEscape", escape.clone()),
+        LexErrorKind::NumberOutOfRange(number) => ("NumberOutOfRange", number.clone()),
+        LexErrorKind::UnterminatedComment => {
+            ("UnterminatedComment", String::from(BLOCK_COMMENT_START))
+        }
+        LexErrorKind::Unreadable(message) => ("Unreadable", message.clone()),
+    }
+}
+
+// the entries of a table, by key
+pub fn sorted<V>(table: &Table<V>) -> Vec<(usize, &V)> {
+    let mut entries: Vec<(usize, &V)> = table.iter().map(|(key, value)| (*key, value)).collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
@@ -0,0 +1,122 @@
+use super::{error_value, sorted, ScanOutput, ScanWriter};
+use crate::models::span::Span;
+
+// one row per record: tokens, identifiers, constants, errors and the result, told
+// apart by the first column; fields a record does not have are left empty
+const HEADER: [&str; 12] = [
+    "record",
+    "index",
+    "kind",
+    "code",
+    "lexeme",
+    "value",
+    "line",
+    "column",
+    "end_line",
+    "end_column",
+    "offset",
+    "length",
+];
+
+pub struct CsvWriter;
+
+impl ScanWriter for CsvWriter {
+    fn file_name(&self) -> &'static str {
+        "tokens.csv"
+    }
+
+    fn format(&self, output: &ScanOutput) -> Result<String, String> {
+        let mut rows: Vec<Vec<String>> =
+            vec![HEADER.iter().map(|field| field.to_string()).collect()];
+
+        for (index, token) in output.tokens.iter().enumerate() {
+            let position = match token.value() {
+                0 => String::new(),
+                position => position.to_string(),
+            };
+            let mut row = vec![
+                String::from("token"),
+                (index + 1).to_string(),
+                output.lexer.kind_name(token.get_kind()),
+                token.key().to_string(),
+                String::from(token.get_inner()),
+                position,
+            ];
+            row.extend(span_fields(token.get_span()));
+            rows.push(row);
+        }
+
+        for (key, name) in sorted(output.identifiers) {
+            rows.push(record("identifier", key, "Identifier", name, ""));
+        }
+
+        for (key, constant) in sorted(output.constants) {
+            let value = constant.value.to_string();
+            rows.push(record(
+                "constant",
+                key,
+                constant.value.type_name(),
+                &constant.text,
+                &value,
+            ));
+        }
+
+        for (index, error) in output.errors.iter().enumerate() {
+            let (kind, value) = error_value(error.get_kind());
+            let mut row = vec![
+                String::from("error"),
+                (index + 1).to_string(),
+                String::from(kind),
+                String::new(),
+                value,
+                error.to_string(),
+            ];
+            row.extend(span_fields(error.get_span()));
+            rows.push(row);
+        }
+
+        rows.push(record("result", 1, "", output.result, ""));
+
+        let mut text = String::new();
+        for row in rows {
+            let fields: Vec<String> = row.iter().map(|field| quote(field)).collect();
+            text.push_str(&fields.join(","));
+            text.push('\n');
+        }
+
+        Ok(text)
+    }
+}
+
+// a record without a code or a span
+fn record(record: &str, index: usize, kind: &str, lexeme: &str, value: &str) -> Vec<String> {
+    let mut row = vec![
+        String::from(record),
+        index.to_string(),
+        String::from(kind),
+        String::new(),
+        String::from(lexeme),
+        String::from(value),
+    ];
+    row.resize(HEADER.len(), String::new());
+    row
+}
+
+fn span_fields(span: Span) -> Vec<String> {
+    vec![
+        span.start.line.to_string(),
+        span.start.column.to_string(),
+        span.end.line.to_string(),
+        span.end.column.to_string(),
+        span.offset.to_string(),
+        span.length.to_string(),
+    ]
+}
+
+// fields with a comma, a quote or a line break are quoted, with their quotes doubled (RFC 4180)
+fn quote(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => String::from(field),
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{error_value, sorted, ScanOutput, ScanWriter};
+use crate::models::literal::ConstantValue;
+use crate::models::span::{Position, Span};
+
+// the version of the JSON schema, bumped on every incompatible change
+pub const JSON_VERSION: u32 = 1;
+const JSON_FORMAT: &str = "tokens";
+
+// the JSON representation of a scan, i.e.
+//
+// {
+//   "format": "tokens",
+//   "version": 1,
+//   "result": "Lexically correct!",
+//   "tokens": [{ "kind": "Identifier", "code": 1, "lexeme": "a", "position": 1, "span": ... }],
+//   "identifiers": [{ "key": 1, "name": "a" }],
+//   "constants": [{ "key": 1, "type": "Number", "text": "0x10", "value": 16 }],
+//   "errors": [{ "kind": "UndefinedToken", "value": "1a", "message": "...", "span": ... }]
+// }
+//
+// where a span is { "offset": 0, "length": 1, "start": { "line": 1, "column": 1 }, "end": ... }
+#[derive(Serialize)]
+struct ScanJson {
+    format: String,
+    version: u32,
+    result: String,
+    tokens: Vec<TokenJson>,
+    identifiers: Vec<IdentifierJson>,
+    constants: Vec<ConstantJson>,
+    errors: Vec<ErrorJson>,
+}
+
+#[derive(Serialize)]
+struct TokenJson {
+    kind: String,
+    code: usize,
+    lexeme: String,
+    // the key of the token in its table, 0 for tokens that are in none
+    position: usize,
+    span: SpanJson,
+}
+
+#[derive(Serialize)]
+struct IdentifierJson {
+    key: usize,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct ConstantJson {
+    key: usize,
+    #[serde(rename = "type")]
+    kind: String,
+    text: String,
+    value: Value,
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+    kind: String,
+    value: String,
+    message: String,
+    span: SpanJson,
+}
+
+#[derive(Serialize)]
+struct SpanJson {
+    offset: usize,
+    length: usize,
+    start: PositionJson,
+    end: PositionJson,
+}
+
+#[derive(Serialize)]
+struct PositionJson {
+    line: usize,
+    column: usize,
+}
+
+impl From<Position> for PositionJson {
+    fn from(position: Position) -> Self {
+        Self {
+            line: position.line,
+            column: position.column,
+        }
+    }
+}
+
+impl From<Span> for SpanJson {
+    fn from(span: Span) -> Self {
+        Self {
+            offset: span.offset,
+            length: span.length,
+            start: span.start.into(),
+            end: span.end.into(),
+        }
+    }
+}
+
+pub struct JsonWriter;
+
+impl ScanWriter for JsonWriter {
+    fn file_name(&self) -> &'static str {
+        "tokens.json"
+    }
+
+    fn format(&self, output: &ScanOutput) -> Result<String, String> {
+        let tokens = output
+            .tokens
+            .iter()
+            .map(|token| TokenJson {
+                kind: output.lexer.kind_name(token.get_kind()),
+                code: token.key(),
+                lexeme: String::from(token.get_inner()),
+                position: token.value(),
+                span: token.get_span().into(),
+            })
+            .collect();
+
+        let identifiers = sorted(output.identifiers)
+            .into_iter()
+            .map(|(key, name)| IdentifierJson {
+                key,
+                name: name.clone(),
+            })
+            .collect();
+
+        let constants = sorted(output.constants)
+            .into_iter()
+            .map(|(key, constant)| {
+                let value = match &constant.value {
+                    ConstantValue::Number(number) => Value::from(*number),
+                    ConstantValue::Decimal(decimal) => Value::from(*decimal),
+                    ConstantValue::Char(character) => Value::from(character.to_string()),
+                    ConstantValue::Str(string) => Value::from(string.clone()),
+                };
+                ConstantJson {
+                    key,
+                    kind: String::from(constant.value.type_name()),
+                    text: constant.text.clone(),
+                    value,
+                }
+            })
+            .collect();
+
+        let errors = output
+            .errors
+            .iter()
+            .map(|error| {
+                let (kind, value) = error_value(error.get_kind());
+                ErrorJson {
+                    kind: String::from(kind),
+                    value,
+                    message: error.to_string(),
+                    span: error.get_span().into(),
+                }
+            })
+            .collect();
+
+        let json = ScanJson {
+            format: String::from(JSON_FORMAT),
+            version: JSON_VERSION,
+            result: String::from(output.result),
+            tokens,
+            identifiers,
+            constants,
+            errors,
+        };
+
+        match serde_json::to_string_pretty(&json) {
+            Ok(text) => Ok(text + "\n"),
+            Err(e) => {
+                let error = format!("could not write JSON: {}", e);
+                Err(error)
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::{ScanOutput, ScanWriter};
+use crate::models::token::{Token, TokenKind};
+
+// identifiers and constants are listed by category, along with their key in their table;
+// every other token by itself, with no key
+const IDENTIFIER: &str = "id";
+const CONSTANT: &str = "const";
+const NO_POSITION: &str = "-1";
+
+// the program internal form: the tokens of the program in order, one per line
+pub struct PifWriter;
+
+impl ScanWriter for PifWriter {
+    fn file_name(&self) -> &'static str {
+        "pif.out"
+    }
+
+    fn format(&self, output: &ScanOutput) -> Result<String, String> {
+        let tokens: Vec<&Token> = output
+            .tokens
+            .iter()
+            .filter(|token| !token.get_kind().is_trivia() && token.get_kind() != TokenKind::EOF)
+            .collect();
+
+        let mut text = format!("{:<16} {}\n", "Token", "Position");
+        for token in &tokens {
+            let (code, position) = match token.get_kind() {
+                TokenKind::Identifier => (IDENTIFIER, token.value().to_string()),
+                kind if kind.is_constant() => (CONSTANT, token.value().to_string()),
+                _ => (token.get_inner(), String::from(NO_POSITION)),
+            };
+            text.push_str(&format!("{:<16} {}\n", code, position));
+        }
+        text.push_str(&format!("PIF size: {}\n", tokens.len()));
+
+        Ok(text)
+    }
+}
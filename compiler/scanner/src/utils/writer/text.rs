@@ -0,0 +1,70 @@
+use super::{error_value, ScanOutput, ScanWriter};
+
+// the human-oriented format: the (code, position) of every token, then the tables
+// and the lexical errors
+pub struct TextWriter;
+
+impl ScanWriter for TextWriter {
+    fn file_name(&self) -> &'static str {
+        "tokens.out"
+    }
+
+    fn format(&self, output: &ScanOutput) -> Result<String, String> {
+        let mut text = String::new();
+        let tokens = output
+            .tokens
+            .iter()
+            .map(|entry| {
+                // println!(
+                //     "{:2}, {:2} -> {}",
+                //     entry.key(),
+                //     entry.value(),
+                //     entry.get_inner().replace("\n", "\\n").replace("\0", "\\0")
+                // );
+
+                format!("({:2}, {:2})", entry.key(), entry.value())
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        text.push_str("Token list:\n");
+        text.push_str(&tokens);
+        text.push_str("\nToken list size: ");
+        text.push_str(&output.tokens.len().to_string());
+
+        text.push_str("\n\nIdentifier table:\n");
+        for (key, value) in output.identifiers {
+            text.push_str(&format!("K: {}, V: {:?}\n", key, value));
+        }
+        text.push_str("Identifier table size: ");
+        text.push_str(&output.identifiers.len().to_string());
+
+        text.push_str("\n\nConstant table:\n");
+        for (key, constant) in output.constants {
+            text.push_str(&format!(
+                "K: {}, V: {:?}, D: {:?}\n",
+                key, constant.text, constant.value
+            ));
+        }
+        text.push_str("Constant table size: ");
+        text.push_str(&output.constants.len().to_string());
+
+        text.push_str("\n\nLexical errors:\n");
+        for error in output.errors {
+            let start = error.get_span().start;
+            let (_, value) = error_value(error.get_kind());
+            text.push_str(&format!(
+                "L: {}, C: {}, V: {:?}\n",
+                start.line, start.column, value
+            ));
+        }
+        text.push_str("Lexical errors size: ");
+        text.push_str(&output.errors.len().to_string());
+
+        text.push_str("\n\n");
+        text.push_str(output.result);
+        text.push('\n');
+
+        Ok(text)
+    }
+}
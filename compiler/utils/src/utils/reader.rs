@@ -32,7 +32,7 @@ pub fn extract_line_data(line: InputLine) -> Option<String> {
     }
 }
 
-pub fn get_next_line(lines: &mut Lines<BufReader<File>>) -> Result<Option<String>, String> {
+pub fn get_next_line<B: BufRead>(lines: &mut Lines<B>) -> Result<Option<String>, String> {
     // we are no longer ignoring errors from the reader in this function
     // because the lack of data is a serious error that should be handled
     // i.e. a missing line breaks the entire meaning of the file